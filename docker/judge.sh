@@ -0,0 +1,192 @@
+#!/bin/bash
+
+# 모든 언어 이미지가 공유하는 채점 스크립트. 언어별 명령은 languages.toml에서 환경 변수로 전달된다.
+#   JUDGE_PHASE  compile | run. 컴파일과 실행은 제한이 다른 별도 컨테이너에서 이루어진다.
+#   COMPILE_CMD  컴파일 명령 (compile 단계)
+#   RUN_CMD      실행 명령 (run 단계)
+#
+# compile 단계는 /workspace의 소스를 컴파일해 결과물을 /workspace/build 에 남기고,
+# run 단계는 /workspace/build 의 파일로 테스트케이스를 실행한다.
+#
+# 채점 결과 프로토콜: stdout에 한 줄짜리 JSON을 출력한다.
+#   {"type":"compile","verdict":"OK" | "COMPILATION_ERROR"}  (run 단계는 항상 OK)
+#   컴파일에 실패하면 컴파일 명령의 종료 코드를 "exit_code"로 함께 보고한다. (시간 초과: 124/137)
+#   {"type":"testcase","number":N,"name":"...","verdict":"...","wall_ms":..,"cpu_ms":..,"rss_kb":..,"exit_code":..,"signal":..}
+# 정상 종료한 테스트케이스는 verdict "OK"로 보고하고, 출력은 /workspace/output/<이름>.out (stderr는 .err) 에 남긴다.
+# 출력 비교(체커)는 judge.rs가 담당한다.
+# 컴파일러 출력과 인터랙터 오류 메시지는 stderr로 출력한다. 런타임 에러 분류는 judge.rs가 output/<이름>.err 를 읽어 처리한다.
+#
+# 인터랙티브 문제(INTERACTIVE=1)는 /interactor/interactor 를 함께 실행해 표준 입출력을 서로 연결하고,
+# 인터랙터의 종료 코드로 판정한다. (0: 정답, 1/2: 오답, 그 외: 채점 오류)
+# 인터랙터는 `interactor <input> <answer>` 로 실행되며 stderr 메시지는 output/<이름>.interactor 에 남긴다.
+#
+# 커스텀 체커가 있는 문제(CHECKER=1)는 정상 종료한 테스트케이스마다 /checker/checker 를 실행해 판정한다.
+# 체커는 `checker <input> <expected> <actual>` (CHECKER_TESTLIB=1이면 `<input> <actual> <expected>`)로 실행되며
+# 종료 코드 0은 정답, 1/2는 오답, 그 외는 채점 오류다. 출력 메시지는 output/<이름>.checker 에 남긴다.
+#
+# 샌드박스: 소스 코드를 크기 제한이 있는 tmpfs(SANDBOX_DIR)로 복사해 권한 없는 사용자(SANDBOX_UID)로 컴파일/실행한다.
+# 제출 프로그램은 /workspace와 루트 파일시스템에 쓸 수 없고, 결과 파일은 이 스크립트가 리다이렉션으로 남긴다.
+# seccomp에 막힌 시스템 콜(SIGSYS)이나 프로세스 수 제한(pids limit) 초과는 SECURITY_VIOLATION으로 판정한다.
+
+JUDGE_PHASE=${JUDGE_PHASE:-run}
+COMPILE_TIME_LIMIT_SECS=${COMPILE_TIME_LIMIT_SECS:-30}
+CHECKER_TIME_LIMIT_SECS=${CHECKER_TIME_LIMIT_SECS:-10}
+
+# 문제별 제한 (judge.rs가 환경 변수로 전달)
+TIME_LIMIT_MS=${TIME_LIMIT_MS:-5000}
+MEMORY_LIMIT_MB=${MEMORY_LIMIT_MB:-512}
+MEMORY_LIMIT_KB=$((MEMORY_LIMIT_MB * 1024))
+# 테스트케이스 하나의 출력(stdout/stderr 파일) 제한. 넘으면 SIGXFSZ로 종료되어 OUTPUT_LIMIT_EXCEEDED
+OUTPUT_LIMIT_MB=${OUTPUT_LIMIT_MB:-64}
+OUTPUT_LIMIT_KB=$((OUTPUT_LIMIT_MB * 1024))
+# 벽시계 시간은 CPU 시간 제한의 2배까지 허용하고, 판정은 CPU 시간 기준으로 한다
+WALL_LIMIT=$(awk "BEGIN { printf \"%.3f\", $TIME_LIMIT_MS * 2 / 1000 }")
+
+WORKSPACE=$(pwd)
+OUTPUT_DIR="$WORKSPACE/output"
+BUILD_DIR="$WORKSPACE/build"
+SANDBOX_DIR=${SANDBOX_DIR:-/sandbox}
+SANDBOX_UID=${SANDBOX_UID:-65534}
+AS_SANDBOX_USER=(setpriv --reuid="$SANDBOX_UID" --regid="$SANDBOX_UID" --clear-groups --no-new-privs)
+
+# 제출 프로그램 실행 명령 (time이 셸이 아닌 프로그램을 측정하도록 exec, ulimit -f는 KB 단위)
+RUN_CMD=("${AS_SANDBOX_USER[@]}" bash -c "ulimit -f $OUTPUT_LIMIT_KB; exec $RUN_CMD")
+
+emit_testcase() {
+    printf '{"type":"testcase","number":%d,"name":"%s","verdict":"%s","wall_ms":%d,"cpu_ms":%d,"rss_kb":%d,"exit_code":%d,"signal":%d}\n' \
+        "$1" "$2" "$3" "$4" "$5" "$6" "$7" "$8"
+}
+
+# 컨테이너 cgroup에서 pids limit에 걸려 fork가 실패한 횟수 (cgroup v2 / v1)
+pids_limit_hits() {
+    local hits
+    hits=$(cat /sys/fs/cgroup/pids.events /sys/fs/cgroup/pids/pids.events 2>/dev/null | awk '$1 == "max" { print $2; exit }')
+    echo "${hits:-0}"
+}
+
+if [ "$JUDGE_PHASE" = "compile" ]; then
+    # 작업 디렉토리의 소스 파일만 샌드박스로 복사한다
+    find "$WORKSPACE" -maxdepth 1 -type f -exec cp {} "$SANDBOX_DIR/" \;
+    cd "$SANDBOX_DIR" || exit 1
+
+    timeout -k 1s "${COMPILE_TIME_LIMIT_SECS}s" "${AS_SANDBOX_USER[@]}" bash -c "$COMPILE_CMD" \
+        > "$WORKSPACE/compile_error.txt" 2>&1
+    compile_exit=$?
+    if [ $compile_exit -ne 0 ]; then
+        head -c 1048576 "$WORKSPACE/compile_error.txt" >&2
+        if [ $compile_exit -eq 124 ] || [ $compile_exit -eq 137 ]; then
+            echo "컴파일 시간이 초과되었습니다. (${COMPILE_TIME_LIMIT_SECS}초)" >&2
+        fi
+        printf '{"type":"compile","verdict":"COMPILATION_ERROR","exit_code":%d}\n' "$compile_exit"
+        exit 0
+    fi
+
+    # 경고는 컴파일 메시지로 남긴다
+    head -c 1048576 "$WORKSPACE/compile_error.txt" >&2
+    mkdir -p "$BUILD_DIR"
+    cp -r "$SANDBOX_DIR/." "$BUILD_DIR/"
+    echo '{"type":"compile","verdict":"OK"}'
+    exit 0
+fi
+
+cp -r "$BUILD_DIR/." "$SANDBOX_DIR/"
+cd "$SANDBOX_DIR" || exit 1
+echo '{"type":"compile","verdict":"OK"}'
+
+# 테스트케이스 디렉토리가 없으면 컴파일 성공만 보고
+if [ ! -d "/testcases" ]; then
+    exit 0
+fi
+
+mkdir -p "$OUTPUT_DIR"
+
+number=0
+for input_file in /testcases/*.in; do
+    [ -f "$input_file" ] || continue
+    number=$((number + 1))
+    name=$(basename "$input_file" .in)
+
+    rm -f "$WORKSPACE/time.txt"
+    interactor_exit=0
+    pids_hits_before=$(pids_limit_hits)
+    start=$(date +%s%N)
+    if [ -n "$INTERACTIVE" ]; then
+        answer_file="/testcases/$name.out"
+        [ -f "$answer_file" ] || answer_file=/dev/null
+        to_user="$WORKSPACE/to_user"
+        to_interactor="$WORKSPACE/to_interactor"
+        rm -f "$to_user" "$to_interactor"
+        mkfifo "$to_user" "$to_interactor"
+        # FIFO는 반대편이 열릴 때까지 막히므로 두 프로세스가 같은 순서로 열도록 리다이렉션 순서를 맞춘다
+        timeout -k 1s ${WALL_LIMIT}s "${AS_SANDBOX_USER[@]}" /interactor/interactor "$input_file" "$answer_file" \
+            < "$to_interactor" > "$to_user" 2> "$OUTPUT_DIR/$name.interactor" &
+        interactor_pid=$!
+        timeout -k 1s ${WALL_LIMIT}s /usr/bin/time -f "%U %S %M" -o "$WORKSPACE/time.txt" \
+            "${RUN_CMD[@]}" > "$to_interactor" < "$to_user" 2> "$OUTPUT_DIR/$name.err"
+        exit_code=$?
+        wait $interactor_pid
+        interactor_exit=$?
+    else
+        timeout -k 1s ${WALL_LIMIT}s /usr/bin/time -f "%U %S %M" -o "$WORKSPACE/time.txt" \
+            "${RUN_CMD[@]}" < "$input_file" > "$OUTPUT_DIR/$name.out" 2> "$OUTPUT_DIR/$name.err"
+        exit_code=$?
+    fi
+    end=$(date +%s%N)
+    pids_hits_after=$(pids_limit_hits)
+
+    wall_ms=$(( (end - start) / 1000000 ))
+    read -r cpu_ms rss_kb < <(tail -n 1 "$WORKSPACE/time.txt" 2>/dev/null | awk '{printf "%d %d", ($1 + $2) * 1000, $3}')
+    cpu_ms=${cpu_ms:-$wall_ms}
+    rss_kb=${rss_kb:-0}
+
+    signal=0
+    if [ $exit_code -gt 128 ]; then
+        signal=$((exit_code - 128))
+    fi
+
+    if [ $signal -eq 31 ] || [ "$pids_hits_after" -gt "$pids_hits_before" ]; then
+        # fork 폭탄처럼 제한에 걸린 프로그램은 시간 초과보다 보안 위반을 우선한다
+        verdict="SECURITY_VIOLATION"
+    elif [ $exit_code -eq 124 ] || [ "$cpu_ms" -gt "$TIME_LIMIT_MS" ]; then
+        verdict="TIME_LIMIT_EXCEEDED"
+    elif [ "$rss_kb" -gt "$MEMORY_LIMIT_KB" ] || [ $signal -eq 9 ]; then
+        # cgroup OOM killer는 SIGKILL로 종료시킨다
+        verdict="MEMORY_LIMIT_EXCEEDED"
+    elif [ $signal -eq 25 ] || [ "$(stat -c %s "$OUTPUT_DIR/$name.out" 2>/dev/null || echo 0)" -ge $((OUTPUT_LIMIT_KB * 1024)) ]; then
+        # SIGXFSZ를 무시한 프로그램은 쓰기에 실패하므로 출력 파일 크기로도 확인한다
+        verdict="OUTPUT_LIMIT_EXCEEDED"
+    elif [ $interactor_exit -eq 1 ] || [ $interactor_exit -eq 2 ]; then
+        # 인터랙터가 먼저 입력을 끊으면 제출 프로그램이 비정상 종료할 수 있으므로 오답을 우선한다
+        verdict="WRONG_ANSWER"
+    elif [ $exit_code -ne 0 ]; then
+        verdict="RUNTIME_ERROR"
+    elif [ $interactor_exit -ne 0 ]; then
+        verdict="SYSTEM_ERROR"
+        head -c 4096 "$OUTPUT_DIR/$name.interactor" >&2
+    elif [ -n "$INTERACTIVE" ]; then
+        verdict="ACCEPTED"
+    elif [ -n "$CHECKER" ] && [ -f "/testcases/$name.out" ]; then
+        if [ -n "$CHECKER_TESTLIB" ]; then
+            checker_args=("$input_file" "$OUTPUT_DIR/$name.out" "/testcases/$name.out")
+        else
+            checker_args=("$input_file" "/testcases/$name.out" "$OUTPUT_DIR/$name.out")
+        fi
+        timeout -k 1s "${CHECKER_TIME_LIMIT_SECS}s" "${AS_SANDBOX_USER[@]}" /checker/checker "${checker_args[@]}" \
+            > "$OUTPUT_DIR/$name.checker" 2>&1
+        checker_exit=$?
+        if [ $checker_exit -eq 0 ]; then
+            verdict="ACCEPTED"
+        elif [ $checker_exit -eq 1 ] || [ $checker_exit -eq 2 ]; then
+            verdict="WRONG_ANSWER"
+        else
+            verdict="SYSTEM_ERROR"
+            head -c 4096 "$OUTPUT_DIR/$name.checker" >&2
+        fi
+    else
+        verdict="OK"
+    fi
+
+    emit_testcase "$number" "$name" "$verdict" "$wall_ms" "$cpu_ms" "$rss_kb" "$exit_code" "$signal"
+done
+
+exit 0
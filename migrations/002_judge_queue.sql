@@ -0,0 +1,15 @@
+-- ============================================
+-- 채점 대기열
+-- ============================================
+CREATE TABLE judge_queue (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    submission_id INTEGER NOT NULL UNIQUE,
+    status VARCHAR(20) DEFAULT 'queued' NOT NULL, -- 'queued', 'running'
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    enqueued_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    started_at DATETIME,
+
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_judge_queue_status ON judge_queue(status, id);
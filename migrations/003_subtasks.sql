@@ -0,0 +1,20 @@
+-- ============================================
+-- 서브태스크 부분 점수
+-- ============================================
+ALTER TABLE submissions ADD COLUMN max_score INTEGER DEFAULT 100;
+
+CREATE TABLE subtask_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    submission_id INTEGER NOT NULL,
+    subtask_number INTEGER NOT NULL,
+    name TEXT,
+    status VARCHAR(30) NOT NULL,
+    score INTEGER NOT NULL,
+    max_score INTEGER NOT NULL,
+    total_testcases INTEGER NOT NULL,
+    passed_testcases INTEGER NOT NULL,
+
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_subtask_results_submission ON subtask_results(submission_id);
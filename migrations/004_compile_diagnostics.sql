@@ -0,0 +1,5 @@
+-- ============================================
+-- 컴파일 에러 진단 (파일 / 위치 / 심각도)
+-- ============================================
+ALTER TABLE compile_errors ADD COLUMN file_name TEXT;
+ALTER TABLE compile_errors ADD COLUMN severity VARCHAR(20) NOT NULL DEFAULT 'error';
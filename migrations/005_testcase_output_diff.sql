@@ -0,0 +1,6 @@
+-- ============================================
+-- 공개 테스트케이스 출력 비교
+-- ============================================
+ALTER TABLE testcase_results ADD COLUMN output_start_line INTEGER;
+ALTER TABLE testcase_results ADD COLUMN diff_line INTEGER;
+ALTER TABLE testcase_results ADD COLUMN diff_column INTEGER;
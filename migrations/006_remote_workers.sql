@@ -0,0 +1,10 @@
+-- ============================================
+-- 원격 채점 워커 작업 임대 (lease)
+-- ============================================
+-- 로컬 워커가 가져간 작업은 lease_token이 비어 있고, 원격 워커의 작업은 lease_expires_at까지
+-- 하트비트가 없으면 다시 대기열로 돌아간다.
+ALTER TABLE judge_queue ADD COLUMN worker_id TEXT;
+ALTER TABLE judge_queue ADD COLUMN lease_token TEXT;
+ALTER TABLE judge_queue ADD COLUMN lease_expires_at DATETIME;
+
+CREATE INDEX idx_judge_queue_lease ON judge_queue(status, lease_expires_at);
@@ -0,0 +1,16 @@
+-- ============================================
+-- 문제 인덱스
+-- ============================================
+-- 원본은 problems/ 폴더의 마크다운이며, 서버 시작 시와 관리자 요청 시 디스크에서 다시 맞춘다.
+CREATE TABLE problems (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    time_limit INTEGER NOT NULL,                -- ms 단위
+    memory_limit INTEGER NOT NULL,              -- MB 단위
+    tags TEXT NOT NULL DEFAULT '[]',            -- JSON 배열
+    visibility TEXT NOT NULL DEFAULT 'public',  -- public, hidden
+    checksum TEXT NOT NULL,                     -- 마크다운 파일 SHA-256
+    synced_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_problems_visibility ON problems(visibility, id);
@@ -0,0 +1,9 @@
+-- ============================================
+-- 문제 공개 범위와 출제자
+-- ============================================
+-- visibility: public(누구나), hidden(대회 전용), draft(작성 중, 편집자만)
+-- owner_id는 문제 편집기에서 문제를 만든 사용자이며, 출제자(role = 'setter')는 자신의 문제만 편집할 수 있다.
+-- 디스크에서 인덱스를 다시 맞춰도 owner_id는 바뀌지 않는다.
+ALTER TABLE problems ADD COLUMN owner_id INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_problems_owner ON problems(owner_id, id);
@@ -0,0 +1,6 @@
+-- ============================================
+-- 채점 중 재채점 요청
+-- ============================================
+-- 실행 중인 작업에 재채점 요청이 들어오면 표시만 해 두고, 지금 채점이 끝나면 그 결과는 버린 채
+-- 다시 대기열에 넣는다. (두 워커가 같은 제출을 동시에 채점하지 않도록)
+ALTER TABLE judge_queue ADD COLUMN requeue_requested INTEGER DEFAULT 0 NOT NULL;
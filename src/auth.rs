@@ -46,6 +46,16 @@ impl User {
     pub fn is_admin(&self) -> bool {
         self.role == "admin"
     }
+
+    /// 출제자: 문제를 만들고 자신이 만든 문제를 편집할 수 있다
+    pub fn is_setter(&self) -> bool {
+        self.role == "setter"
+    }
+
+    /// 문제 편집기를 쓸 수 있는지 (문제별 권한은 `problem_access::can_edit`)
+    pub fn can_edit_problems(&self) -> bool {
+        self.is_admin() || self.is_setter()
+    }
     
     pub fn verify_password(&self, password: &str) -> bool {
         PasswordHash::new(&self.password_hash)
@@ -0,0 +1,28 @@
+use dotenvy::dotenv;
+use nekonic_oj::judge::{self, language};
+use nekonic_oj::judge_worker::{RemoteWorker, WorkerConfig};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // .env 파일에서 환경 변수 로드
+    dotenv().ok();
+
+    // 채점 언어 레지스트리 로드 (서버와 같은 languages.toml을 써야 한다)
+    language::init_from_env()?;
+    println!("✅ Language registry loaded ({} languages).", language::registry().all().len());
+
+    let config = WorkerConfig::from_env()?;
+    let judge_backend = judge::backend_from_env()?;
+    let slots = std::env::var("JUDGE_WORKERS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(1)
+        .max(1);
+    println!("✅ Judge worker {} connecting to {} ({} slots).", config.worker_id, config.server_url, slots);
+
+    let worker = RemoteWorker::new(config, judge_backend);
+    let handles: Vec<_> = (0..slots).map(|_| tokio::spawn(worker.clone().run())).collect();
+    futures_util::future::join_all(handles).await;
+
+    Ok(())
+}
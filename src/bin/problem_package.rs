@@ -0,0 +1,39 @@
+use nekonic_oj::problem_repository;
+
+const USAGE: &str = "usage:
+  problem-package import <package.zip> <problem_id> [--overwrite]
+  problem-package export <problem_id> <package.zip>";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let repository = problem_repository::repository();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["import", package, problem_id, rest @ ..] => {
+            let problem_id: i64 = problem_id.parse()?;
+            let overwrite = rest.contains(&"--overwrite");
+            let bytes = tokio::fs::read(package).await?;
+
+            let report = repository.import_package(problem_id, bytes, overwrite).await?;
+            println!(
+                "✅ Imported problem {} ({}) from {:?} package with {} testcases.",
+                problem_id, report.title, report.format, report.testcases
+            );
+            // 실행 중인 서버는 관리자 대시보드의 "문제 목록 동기화"나 재시작으로 인덱스를 맞춘다
+            println!("   Sync the problem index from /admin or restart the server to list it.");
+        }
+        ["export", problem_id, package] => {
+            let problem_id: i64 = problem_id.parse()?;
+            let bytes = repository.export_package(problem_id).await?;
+            tokio::fs::write(package, &bytes).await?;
+            println!("✅ Exported problem {} to {} ({} bytes).", problem_id, package, bytes.len());
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+
+    Ok(())
+}
@@ -2,6 +2,8 @@
 use sqlx::SqlitePool;
 use chrono::{DateTime, Utc, NaiveDateTime};
 
+use crate::judge::scoring::DEFAULT_MAX_SCORE;
+
 /// 날짜 파싱 헬퍼
 fn parse_db_datetime(datetime_str: &str) -> Option<DateTime<Utc>> {
     // RFC3339 형식
@@ -25,23 +27,39 @@ fn parse_db_datetime(datetime_str: &str) -> Option<DateTime<Utc>> {
     None
 }
 
-/// ICPC 스타일 순위 업데이트
+/// 문제별 배점을 합산하는 대회 유형 (IOI, CTF). 나머지는 ICPC 방식으로 맞춘 문제 수를 센다.
+fn is_point_based(contest_type: &str) -> bool {
+    matches!(contest_type, "IOI" | "CTF")
+}
+
+/// 순위 업데이트 (`contest_participants.total_score`, `penalty_time`)
+///
+/// ICPC 스타일
 /// - solved: 맞춘 문제 수
 /// - penalty: 각 문제를 맞출 때까지 걸린 시간(분) + 틀린 횟수 * 20분
+///
+/// 배점 합산 (IOI, CTF)
+/// - 문제마다 가장 높은 제출 점수를 배점(`contest_problems.points`)에 맞춰 환산해 더한다
+/// - 패널티는 없다
 pub async fn update_standings(
     pool: &SqlitePool,
     contest_id: i64,
     user_id: i64,
 ) -> Result<(), sqlx::Error> {
-    // 대회 시작 시간 가져오기
-    let (start_time,): (String,) = sqlx::query_as(
-        "SELECT start_time FROM contests WHERE id = ?"
+    // 대회 시작 시간과 유형 가져오기
+    let (start_time, contest_type): (String, Option<String>) = sqlx::query_as(
+        "SELECT start_time, contest_type FROM contests WHERE id = ?"
     )
     .bind(contest_id)
     .fetch_one(pool)
     .await?;
 
-    let contest_start = parse_db_datetime(&start_time).unwrap_or_else(|| Utc::now());
+    if contest_type.as_deref().is_some_and(is_point_based) {
+        let total_points = contest_points(pool, contest_id, user_id).await?;
+        return store_standing(pool, contest_id, user_id, total_points, 0).await;
+    }
+
+    let contest_start = parse_db_datetime(&start_time).unwrap_or_else(Utc::now);
 
     // 대회에 포함된 문제 목록 가져오기
     let contest_problems: Vec<(i64,)> = sqlx::query_as(
@@ -98,7 +116,42 @@ pub async fn update_standings(
         }
     }
 
-    // contest_participants 테이블 업데이트
+    store_standing(pool, contest_id, user_id, total_solved, total_penalty).await
+}
+
+/// 문제별 최고 점수를 배점으로 환산한 합 (만점 제출이면 배점 전부)
+async fn contest_points(
+    pool: &SqlitePool,
+    contest_id: i64,
+    user_id: i64,
+) -> Result<i32, sqlx::Error> {
+    let best_points: Vec<Option<i64>> = sqlx::query_scalar(
+        r#"
+        SELECT MAX(s.score * cp.points / COALESCE(s.max_score, ?))
+        FROM contest_problems cp
+        LEFT JOIN submissions s
+            ON s.contest_id = cp.contest_id AND s.problem_id = cp.problem_id AND s.user_id = ?
+        WHERE cp.contest_id = ?
+        GROUP BY cp.id
+        "#
+    )
+    .bind(DEFAULT_MAX_SCORE)
+    .bind(user_id)
+    .bind(contest_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(best_points.into_iter().map(|points| points.unwrap_or(0) as i32).sum())
+}
+
+/// contest_participants 테이블 업데이트
+async fn store_standing(
+    pool: &SqlitePool,
+    contest_id: i64,
+    user_id: i64,
+    total_score: i32,
+    penalty_time: i32,
+) -> Result<(), sqlx::Error> {
     sqlx::query(
         r#"
         UPDATE contest_participants
@@ -106,8 +159,8 @@ pub async fn update_standings(
         WHERE contest_id = ? AND user_id = ?
         "#
     )
-    .bind(total_solved)
-    .bind(total_penalty)
+    .bind(total_score)
+    .bind(penalty_time)
     .bind(contest_id)
     .bind(user_id)
     .execute(pool)
@@ -3,6 +3,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
+#[derive(Debug)]
 pub enum AppError {
     Sqlx(sqlx::Error),
     Io(std::io::Error),
@@ -13,7 +14,14 @@ pub enum AppError {
     NotFound,
     ProblemNotFound,
     InvalidProblemFormat,
+    UnsupportedLanguage,
+    /// 소스 코드가 최대 크기(바이트)를 넘음
+    SourceTooLong(usize),
+    BadRequest(String),
+    Judge(anyhow::Error),
     Unauthorized,
+    /// 로그인했지만 권한이 없음
+    Forbidden,
 }
 
 impl From<sqlx::Error> for AppError {
@@ -72,7 +80,18 @@ impl IntoResponse for AppError {
             AppError::Json(ref err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("JSON parsing error: {}", err)),
             AppError::NotFound => (StatusCode::NOT_FOUND, "Not found".to_string()),
             AppError::InvalidProblemFormat => (StatusCode::BAD_REQUEST, "Invalid problem format".to_string()),
+            AppError::UnsupportedLanguage => (StatusCode::BAD_REQUEST, "Unsupported language".to_string()),
+            AppError::SourceTooLong(limit) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("소스 코드는 최대 {}바이트까지 제출할 수 있습니다.", limit),
+            ),
+            AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            AppError::Judge(ref err) => {
+                eprintln!("Judge Error: {:?}", err);
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("Judge error: {}", err))
+            }
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden".to_string()),
             AppError::ProblemNotFound => (StatusCode::NOT_FOUND, "Problem not found".to_string()),
             AppError::Regex(ref err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Regex error: {}", err)),
         };
@@ -1,6 +1,6 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
     response::{Html, IntoResponse, Redirect, Response},
     Form, Json,
 };
@@ -8,7 +8,9 @@ use axum_login::AuthSession;
 use serde_json::json;
 use crate::{
     auth::Backend,
+    judge_pipeline,
     models::*,
+    problem_repository,
     AppState,
 };
 
@@ -403,3 +405,285 @@ pub async fn promote_to_admin(
         "message": "사용자를 관리자로 승격했습니다"
     })))
 }
+
+/// 출제자 지정: 문제를 만들고 자신이 만든 문제를 편집할 수 있다
+pub async fn grant_problem_setter(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Path(user_id): Path<i64>,
+) -> Result<Json<serde_json::Value>, Response> {
+    let user = auth_session.user.ok_or_else(|| {
+        (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response()
+    })?;
+
+    if !user.is_admin() {
+        return Err((StatusCode::FORBIDDEN, "관리자 권한이 필요합니다").into_response());
+    }
+
+    // 관리자는 그대로 둔다
+    let result = sqlx::query("UPDATE users SET role = 'setter' WHERE id = ? AND role = 'user'")
+        .bind(user_id)
+        .execute(&state.db_pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::BAD_REQUEST, "일반 사용자만 출제자로 지정할 수 있습니다").into_response());
+    }
+
+    // 관리자 액션 로그
+    sqlx::query(
+        "INSERT INTO admin_actions (admin_id, action_type, target_type, target_id, details)
+         VALUES (?, 'grant_setter', 'user', ?, ?)"
+    )
+    .bind(user.id)
+    .bind(user_id)
+    .bind(format!("Granted problem setter role to user {}", user_id))
+    .execute(&state.db_pool)
+    .await
+    .ok();
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "사용자를 출제자로 지정했습니다"
+    })))
+}
+
+/// 조건에 맞는 제출을 재채점
+pub async fn rejudge_submissions(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Form(form): Form<RejudgeForm>,
+) -> Result<Redirect, Response> {
+    let user = auth_session.user.ok_or_else(|| {
+        (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response()
+    })?;
+
+    if !user.is_admin() {
+        return Err((StatusCode::FORBIDDEN, "관리자 권한이 필요합니다").into_response());
+    }
+
+    let username = form.username.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let status = form.status.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    // 조건 없이 전체 제출을 재채점하는 실수를 막는다
+    if form.problem_id.is_none()
+        && form.contest_id.is_none()
+        && username.is_none()
+        && form.id_from.is_none()
+        && form.id_to.is_none()
+        && status.is_none()
+    {
+        return Err((StatusCode::BAD_REQUEST, "재채점 조건을 하나 이상 입력해주세요").into_response());
+    }
+
+    let target_user_id = match username {
+        Some(username) => {
+            let row: Option<(i64,)> = sqlx::query_as("SELECT id FROM users WHERE username = ?")
+                .bind(username)
+                .fetch_optional(&state.db_pool)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+            let (id,) = row.ok_or_else(|| {
+                (StatusCode::BAD_REQUEST, "존재하지 않는 사용자입니다").into_response()
+            })?;
+            Some(id)
+        }
+        None => None,
+    };
+
+    let submission_ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM submissions
+         WHERE (?1 IS NULL OR problem_id = ?1)
+           AND (?2 IS NULL OR contest_id = ?2)
+           AND (?3 IS NULL OR user_id = ?3)
+           AND (?4 IS NULL OR id >= ?4)
+           AND (?5 IS NULL OR id <= ?5)
+           AND (?6 IS NULL OR status = ?6)
+         ORDER BY id"
+    )
+    .bind(form.problem_id)
+    .bind(form.contest_id)
+    .bind(target_user_id)
+    .bind(form.id_from)
+    .bind(form.id_to)
+    .bind(status)
+    .fetch_all(&state.db_pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+
+    judge_pipeline::rejudge_submissions(&state.db_pool, &state.judge_queue, &submission_ids)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+
+    // 관리자 액션 로그 (가장 구체적인 조건을 대상으로 기록)
+    let (target_type, target_id) = if let Some(problem_id) = form.problem_id {
+        ("problem", problem_id)
+    } else if let Some(contest_id) = form.contest_id {
+        ("contest", contest_id)
+    } else if let Some(user_id) = target_user_id {
+        ("user", user_id)
+    } else {
+        ("submission", form.id_from.or(form.id_to).unwrap_or(0))
+    };
+
+    sqlx::query(
+        "INSERT INTO admin_actions (admin_id, action_type, target_type, target_id, details)
+         VALUES (?, 'rejudge', ?, ?, ?)"
+    )
+    .bind(user.id)
+    .bind(target_type)
+    .bind(target_id)
+    .bind(format!(
+        "Rejudged {} submissions (problem: {:?}, contest: {:?}, user: {:?}, id: {:?}..{:?}, status: {:?})",
+        submission_ids.len(),
+        form.problem_id,
+        form.contest_id,
+        username,
+        form.id_from,
+        form.id_to,
+        status
+    ))
+    .execute(&state.db_pool)
+    .await
+    .ok();
+
+    Ok(Redirect::to("/admin"))
+}
+
+/// 문제 폴더를 다시 읽어 문제 인덱스를 갱신
+pub async fn sync_problems(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+) -> Result<Redirect, Response> {
+    let user = auth_session.user.ok_or_else(|| {
+        (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response()
+    })?;
+
+    if !user.is_admin() {
+        return Err((StatusCode::FORBIDDEN, "관리자 권한이 필요합니다").into_response());
+    }
+
+    let report = problem_repository::repository()
+        .sync_index(&state.db_pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+
+    sqlx::query(
+        "INSERT INTO admin_actions (admin_id, action_type, target_type, target_id, details)
+         VALUES (?, 'sync_problems', 'problem', 0, ?)"
+    )
+    .bind(user.id)
+    .bind(format!(
+        "Synced problem index ({} problems, {} updated, {} removed)",
+        report.total, report.updated, report.removed
+    ))
+    .execute(&state.db_pool)
+    .await
+    .ok();
+
+    Ok(Redirect::to("/admin"))
+}
+
+/// 문제 패키지(zip) 업로드 (`problem_id`, `overwrite`, `package` 필드)
+pub async fn import_problem_package(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    mut multipart: Multipart,
+) -> Result<Redirect, Response> {
+    let user = auth_session.user.ok_or_else(|| {
+        (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response()
+    })?;
+
+    if !user.is_admin() {
+        return Err((StatusCode::FORBIDDEN, "관리자 권한이 필요합니다").into_response());
+    }
+
+    let mut problem_id = None;
+    let mut overwrite = false;
+    let mut package = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()).into_response())?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()).into_response())?;
+        match name.as_str() {
+            "problem_id" => problem_id = String::from_utf8_lossy(&bytes).trim().parse::<i64>().ok(),
+            "overwrite" => overwrite = !bytes.is_empty(),
+            "package" => package = Some(bytes.to_vec()),
+            _ => {}
+        }
+    }
+
+    let problem_id = problem_id
+        .filter(|id| *id > 0)
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "문제 번호를 입력해주세요").into_response())?;
+    let package = package
+        .filter(|bytes| !bytes.is_empty())
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "문제 패키지(zip)를 선택해주세요").into_response())?;
+
+    let report = problem_repository::repository()
+        .import_package(problem_id, package, overwrite)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()).into_response())?;
+
+    problem_repository::repository()
+        .sync_index(&state.db_pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+
+    sqlx::query(
+        "INSERT INTO admin_actions (admin_id, action_type, target_type, target_id, details)
+         VALUES (?, 'import_problem', 'problem', ?, ?)"
+    )
+    .bind(user.id)
+    .bind(problem_id)
+    .bind(format!(
+        "Imported problem {} ({}) from {:?} package with {} testcases",
+        problem_id, report.title, report.format, report.testcases
+    ))
+    .execute(&state.db_pool)
+    .await
+    .ok();
+
+    Ok(Redirect::to(&format!("/problems/{}", problem_id)))
+}
+
+/// 문제를 문제 패키지(zip)로 내려받기
+pub async fn export_problem_package(
+    auth_session: AuthSession<Backend>,
+    Path(problem_id): Path<i64>,
+) -> Result<Response, Response> {
+    let user = auth_session.user.ok_or_else(|| {
+        (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response()
+    })?;
+
+    if !user.is_admin() {
+        return Err((StatusCode::FORBIDDEN, "관리자 권한이 필요합니다").into_response());
+    }
+
+    if !problem_repository::repository().markdown_path(problem_id).exists() {
+        return Err((StatusCode::NOT_FOUND, "문제를 찾을 수 없습니다").into_response());
+    }
+
+    let bytes = problem_repository::repository()
+        .export_package(problem_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"problem-{}.zip\"", problem_id),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
@@ -5,10 +5,20 @@ use axum::{
     http::StatusCode,
 };
 use axum_login::AuthSession;
+use sqlx::SqlitePool;
 use tera::Context;
 use chrono::{DateTime, Utc};
 
-use crate::{auth::Backend, AppState, models::*};
+use crate::{
+    auth::Backend,
+    judge::language,
+    judge_pipeline::{self, NewSubmission},
+    problem_access, problem_repository,
+    AppState,
+    models::*,
+};
+
+use super::problems::load_problem_detail;
 
 // 날짜 파싱 헬퍼 함수
 fn parse_datetime(datetime_str: &str) -> Option<DateTime<Utc>> {
@@ -160,8 +170,9 @@ pub async fn contest_detail(
     let problems = sqlx::query_as::<_, ContestProblem>(
         r#"
         SELECT cp.id, cp.problem_id, cp.points, cp.problem_order,
-               '' as problem_title
+               COALESCE(p.title, '') as problem_title
         FROM contest_problems cp
+        LEFT JOIN problems p ON p.id = cp.problem_id
         WHERE cp.contest_id = ?
         ORDER BY cp.problem_order
         "#
@@ -378,8 +389,9 @@ pub async fn contest_standings(
     let problems = sqlx::query_as::<_, ContestProblem>(
         r#"
         SELECT cp.id, cp.problem_id, cp.points, cp.problem_order,
-               '' as problem_title
+               COALESCE(p.title, '') as problem_title
         FROM contest_problems cp
+        LEFT JOIN problems p ON p.id = cp.problem_id
         WHERE cp.contest_id = ?
         ORDER BY cp.problem_order
         "#
@@ -519,8 +531,9 @@ pub async fn manage_contest(
     let problems = sqlx::query_as::<_, ContestProblem>(
         r#"
         SELECT cp.id, cp.problem_id, cp.points, cp.problem_order,
-               '' as problem_title
+               COALESCE(p.title, '') as problem_title
         FROM contest_problems cp
+        LEFT JOIN problems p ON p.id = cp.problem_id
         WHERE cp.contest_id = ?
         ORDER BY cp.problem_order
         "#
@@ -562,6 +575,11 @@ pub async fn add_contest_problem(
         return (StatusCode::FORBIDDEN, "권한이 없습니다.").into_response();
     }
 
+    // 공개되지 않은 문제는 편집할 수 있는 사람만 대회에 넣을 수 있다
+    if problem_access::ensure_viewable(&state.db_pool, Some(&user), form.problem_id).await.is_err() {
+        return (StatusCode::NOT_FOUND, "문제를 찾을 수 없습니다.").into_response();
+    }
+
     // 문제 추가
     let result = sqlx::query(
         r#"
@@ -680,24 +698,48 @@ pub async fn submit_contest_problem(
         return (StatusCode::FORBIDDEN, "대회가 진행 중이 아닙니다.").into_response();
     }
 
-    // 제출 생성 (contest_id 포함)
-    let result = sqlx::query(
-        r#"
-        INSERT INTO submissions (user_id, problem_id, contest_id, language, source_code, status)
-        VALUES (?, ?, ?, ?, ?, 'PENDING')
-        "#
+    // 대회에 포함된 문제만 제출할 수 있다 (대회 전용 문제를 다른 대회로 제출하지 못하게)
+    let in_contest: Option<i64> = sqlx::query_scalar(
+        "SELECT id FROM contest_problems WHERE contest_id = ? AND problem_id = ?"
     )
-    .bind(user.id)
-    .bind(problem_id)
     .bind(contest_id)
-    .bind(&form.language)
-    .bind(&form.source_code)
-    .execute(&state.db_pool)
+    .bind(problem_id)
+    .fetch_optional(&state.db_pool)
+    .await
+    .unwrap_or(None);
+
+    if in_contest.is_none() || !is_contest_visible(problem_id).await {
+        return (StatusCode::NOT_FOUND, "대회에 포함되지 않은 문제입니다.").into_response();
+    }
+
+    if language::registry().get(&form.language).is_none() {
+        return (StatusCode::BAD_REQUEST, "지원하지 않는 언어입니다.").into_response();
+    }
+
+    if form.source_code.len() > judge_pipeline::max_source_bytes() {
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("소스 코드는 최대 {}바이트까지 제출할 수 있습니다.", judge_pipeline::max_source_bytes()),
+        )
+            .into_response();
+    }
+
+    // 제출 생성 및 채점 시작 (contest_id 포함)
+    let result = judge_pipeline::create_submission(
+        &state.db_pool,
+        &state.judge_queue,
+        NewSubmission {
+            user_id: user.id,
+            problem_id,
+            contest_id: Some(contest_id),
+            language: form.language,
+            source_code: form.source_code,
+        },
+    )
     .await;
 
     match result {
-        Ok(result) => {
-            let submission_id = result.last_insert_rowid();
+        Ok(submission_id) => {
             Redirect::to(&format!("/submissions/{}", submission_id)).into_response()
         }
         Err(e) => {
@@ -719,7 +761,7 @@ pub async fn contest_problem_detail(
     let user = match &auth_session.user {
         Some(u) => u,
         None => {
-            return Html(state.tera.render("error.html", &context).unwrap());
+            return Html(state.tera.render("error.html", &context).unwrap()).into_response();
         }
     };
 
@@ -736,7 +778,7 @@ pub async fn contest_problem_detail(
 
     if participant.is_err() {
         context.insert("error", "대회 참가자가 아닙니다.");
-        return Html(state.tera.render("error.html", &context).unwrap());
+        return Html(state.tera.render("error.html", &context).unwrap()).into_response();
     }
 
     // 대회 정보 조회
@@ -766,16 +808,68 @@ pub async fn contest_problem_detail(
         Ok(p) => p,
         Err(_) => {
             context.insert("error", "대회에 포함되지 않은 문제입니다.");
-            return Html(state.tera.render("error.html", &context).unwrap());
+            return Html(state.tera.render("error.html", &context).unwrap()).into_response();
         }
     };
 
+    // 대회 전용 문제는 이 페이지로만 볼 수 있으므로 대회가 진행 중일 때만 연다
+    if !is_running(&contest.start_time, &contest.end_time) {
+        return (StatusCode::FORBIDDEN, "대회가 진행 중이 아닙니다.").into_response();
+    }
+    if !is_contest_visible(problem_id).await {
+        return (StatusCode::NOT_FOUND, "문제를 찾을 수 없습니다.").into_response();
+    }
+
     // 문제 정보 로드 (기존 problem_detail 로직 재사용)
+    let problem = match load_problem_detail(problem_id, &state).await {
+        Ok(problem) => problem,
+        Err(e) => return e.into_response(),
+    };
+
     context.insert("contest", &contest);
     context.insert("contest_id", &contest_id);
     context.insert("problem_order", &problem_order);
     context.insert("points", &points);
+    context.insert("languages", language::registry().all());
     context.insert("problem_id", &problem_id);
+    context.insert("problem", &problem);
+
+    Html(state.tera.render("contest_problem.html", &context).unwrap()).into_response()
+}
+
+/// 시작 시각과 종료 시각 사이인지
+fn is_running(start_time: &str, end_time: &str) -> bool {
+    let now = Utc::now();
+    match (parse_datetime(start_time), parse_datetime(end_time)) {
+        (Some(start), Some(end)) => start <= now && now <= end,
+        _ => false,
+    }
+}
+
+/// 사용자가 참가한 진행 중인 대회에 포함된 문제인지 (대회 제출과 같은 조건).
+/// 대회 전용 문제도 이때는 실행 모드로 실행해 볼 수 있다.
+pub(crate) async fn is_open_to_participant(
+    pool: &SqlitePool,
+    user_id: i64,
+    problem_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let contests: Vec<(String, String)> = sqlx::query_as(
+        "SELECT c.start_time, c.end_time FROM contests c
+         JOIN contest_participants p ON p.contest_id = c.id AND p.user_id = ?
+         JOIN contest_problems cp ON cp.contest_id = c.id AND cp.problem_id = ?",
+    )
+    .bind(user_id)
+    .bind(problem_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contests.iter().any(|(start, end)| is_running(start, end)) && is_contest_visible(problem_id).await)
+}
 
-    Html(state.tera.render("contest_problem.html", &context).unwrap())
+/// 대회에서 풀 수 있는 문제인지 (작성 중인 문제는 대회에서도 보이지 않는다)
+async fn is_contest_visible(problem_id: i64) -> bool {
+    problem_repository::repository()
+        .load(problem_id)
+        .await
+        .is_ok_and(|problem| problem.meta.visibility != ProblemVisibility::Draft)
 }
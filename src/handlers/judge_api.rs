@@ -0,0 +1,124 @@
+// 원격 채점 워커용 API (프로토콜은 judge_worker.rs 참고)
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tokio::fs;
+
+use crate::{
+    error::AppError,
+    judge::bundle,
+    judge_queue::JudgeQueue,
+    judge_worker::{HeartbeatRequest, LeaseRequest, ResultRequest},
+    problem_repository,
+    AppState,
+};
+
+/// 작업 임대 요청이 기다릴 수 있는 최대 시간 (초)
+const MAX_LEASE_WAIT_SECS: u64 = 30;
+
+/// `Authorization: Bearer <JUDGE_WORKER_TOKEN>` 확인. 토큰이 설정되지 않았으면 API 자체를 숨긴다.
+fn authorize(queue: &JudgeQueue, headers: &HeaderMap) -> Result<(), AppError> {
+    let Some(token) = queue.worker_token() else {
+        return Err(AppError::NotFound);
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if provided != Some(token) {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub async fn lease_judge_job(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<LeaseRequest>,
+) -> Result<Response, AppError> {
+    authorize(&state.judge_queue, &headers)?;
+
+    let wait = Duration::from_secs(payload.wait_secs.min(MAX_LEASE_WAIT_SECS));
+    let job = state
+        .judge_queue
+        .lease(&payload.worker_id, payload.lease_secs, wait)
+        .await
+        .map_err(AppError::Judge)?;
+
+    Ok(match job {
+        Some(job) => Json(job).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    })
+}
+
+pub async fn judge_job_heartbeat(
+    Path(queue_id): Path<i64>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<HeartbeatRequest>,
+) -> Result<StatusCode, AppError> {
+    authorize(&state.judge_queue, &headers)?;
+
+    let renewed = state
+        .judge_queue
+        .heartbeat(queue_id, &payload.lease_token, payload.lease_secs, payload.progress)
+        .await?;
+    Ok(if renewed { StatusCode::NO_CONTENT } else { StatusCode::CONFLICT })
+}
+
+pub async fn judge_job_result(
+    Path(queue_id): Path<i64>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<ResultRequest>,
+) -> Result<StatusCode, AppError> {
+    authorize(&state.judge_queue, &headers)?;
+
+    let result = match (payload.result, payload.error) {
+        (Some(result), None) => Ok(result),
+        (None, Some(error)) => Err(anyhow::anyhow!("Remote worker error: {}", error)),
+        _ => return Err(AppError::BadRequest("result와 error 중 하나만 보내야 합니다.".to_string())),
+    };
+
+    let completed = state
+        .judge_queue
+        .complete(queue_id, &payload.lease_token, result)
+        .await
+        .map_err(AppError::Judge)?;
+    Ok(if completed { StatusCode::NO_CONTENT } else { StatusCode::CONFLICT })
+}
+
+pub async fn problem_bundle(
+    Path(problem_id): Path<i64>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<bundle::BundleManifest>, AppError> {
+    authorize(&state.judge_queue, &headers)?;
+
+    let manifest = bundle::manifest(problem_id)
+        .await
+        .map_err(|_| AppError::ProblemNotFound)?;
+    Ok(Json(manifest))
+}
+
+pub async fn problem_bundle_file(
+    Path((problem_id, path)): Path<(i64, String)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Vec<u8>, AppError> {
+    authorize(&state.judge_queue, &headers)?;
+
+    // 목록에 있는 파일만 보낸다 (상위 디렉토리 접근 방지)
+    let manifest = bundle::manifest(problem_id)
+        .await
+        .map_err(|_| AppError::ProblemNotFound)?;
+    let file = manifest.file(&path).ok_or(AppError::NotFound)?;
+
+    let path = problem_repository::repository().problem_dir(problem_id).join(&file.path);
+    Ok(fs::read(path).await?)
+}
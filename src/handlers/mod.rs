@@ -7,6 +7,8 @@ pub mod submissions;
 pub mod admin;
 pub mod organizations;
 pub mod boards;
+pub mod judge_api;
+pub mod problem_editor;
 
 // Re-export for convenience
 pub use auth::*;
@@ -23,6 +25,8 @@ pub use admin::{
     admin_dashboard, pending_organizations, review_organization,
     create_organization_admin, add_member_to_organization,
     pending_join_requests, review_join_request, promote_to_admin,
+    grant_problem_setter, rejudge_submissions, sync_problems, import_problem_package,
+    export_problem_package,
 };
 pub use organizations::{
     list_organizations, organization_detail, create_organization,
@@ -34,3 +38,12 @@ pub use boards::{
     create_comment, toggle_post_like, delete_post, edit_post_form, update_post,
     toggle_comment_like,
 };
+pub use judge_api::{
+    lease_judge_job, judge_job_heartbeat, judge_job_result, problem_bundle,
+    problem_bundle_file,
+};
+pub use problem_editor::{
+    new_problem_page, create_problem, edit_problem_page, update_problem,
+    preview_statement, upload_testcase, update_testcases, delete_testcase,
+    validate_problem,
+};
@@ -0,0 +1,277 @@
+// 문제 편집기: front matter와 지문 편집, 테스트케이스 관리, 모범 답안 검증
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Multipart, Path, State},
+    response::{Html, Redirect},
+    Form, Json,
+};
+use axum_login::AuthSession;
+use tera::Context;
+
+use crate::{
+    auth::Backend,
+    error::AppError,
+    handlers::problems::markdown_to_html,
+    judge::{language, JudgeResult},
+    judge_pipeline,
+    models::{
+        FrontMatter, NewProblemForm, ProblemEditForm, ProblemVisibility, StatementPreview, StatementPreviewForm,
+        TestcaseRow, ValidateSolutionForm,
+    },
+    problem_access,
+    problem_repository::{self, samples::extract_from_markdown},
+    AppState,
+};
+
+/// 새 문제의 지문 틀
+const NEW_PROBLEM_STATEMENT: &str = "### 입력\n\n### 출력\n";
+
+pub async fn new_problem_page(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+) -> Result<Html<String>, AppError> {
+    let next_id = problem_repository::repository().next_id().await?;
+
+    let mut context = Context::new();
+    context.insert("active_page", "problems");
+    context.insert("next_id", &next_id);
+    if let Some(user) = auth_session.user {
+        context.insert("current_user", &user);
+    }
+
+    let html = state.tera.render("problem_new.html", &context)?;
+    Ok(Html(html))
+}
+
+/// 새 문제는 작성 중 상태로 만들고 편집기로 보낸다. 만든 사람이 문제의 출제자가 된다.
+pub async fn create_problem(
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Form(form): Form<NewProblemForm>,
+) -> Result<Redirect, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    let repository = problem_repository::repository();
+    let id = match form.id {
+        Some(id) => id,
+        None => repository.next_id().await?,
+    };
+    let title = form.title.trim();
+    if id <= 0 || title.is_empty() {
+        return Err(AppError::BadRequest("문제 번호와 제목을 확인해주세요.".to_string()));
+    }
+
+    repository
+        .create(id, &FrontMatter::new(title.to_string()), NEW_PROBLEM_STATEMENT)
+        .await?;
+    repository.refresh_index(&state.db_pool, id).await?;
+    problem_access::set_owner(&state.db_pool, id, user.id).await?;
+
+    Ok(Redirect::to(&format!("/problems/{}/edit", id)))
+}
+
+pub async fn edit_problem_page(
+    Path(id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+) -> Result<Html<String>, AppError> {
+    problem_access::ensure_editable(&state.db_pool, auth_session.user.as_ref(), id).await?;
+    let repository = problem_repository::repository();
+    let (meta, statement) = repository.source(id).await?;
+    let samples = repository.samples(id).await?;
+    let sample_names = repository.sample_testcases(id, &samples).await;
+    let testcases: Vec<TestcaseRow> = repository
+        .testcases(id)
+        .await?
+        .into_iter()
+        .map(|file| TestcaseRow {
+            is_sample: sample_names.contains(&file.name),
+            file,
+        })
+        .collect();
+
+    let mut context = Context::new();
+    context.insert("active_page", "problems");
+    context.insert("problem_id", &id);
+    context.insert("meta", &meta);
+    context.insert("tags", &meta.tags.join(", "));
+    context.insert("statement", &statement);
+    context.insert("testcases", &testcases);
+    context.insert("languages", language::registry().all());
+    if let Some(user) = auth_session.user {
+        context.insert("can_publish", &(user.is_admin() || meta.visibility == ProblemVisibility::Public));
+        context.insert("current_user", &user);
+    }
+
+    let html = state.tera.render("problem_edit.html", &context)?;
+    Ok(Html(html))
+}
+
+/// 제목, 제한, 태그, 공개 범위와 지문 저장. 나머지 front matter(체커, 서브태스크 등)는 그대로 둔다.
+/// 공개로 바꾸는 것은 관리자만 할 수 있다.
+pub async fn update_problem(
+    Path(id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Form(form): Form<ProblemEditForm>,
+) -> Result<Redirect, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    problem_access::ensure_editable(&state.db_pool, Some(&user), id).await?;
+    let repository = problem_repository::repository();
+    let (mut meta, _) = repository.source(id).await?;
+
+    let title = form.title.trim();
+    if title.is_empty() || form.time_limit == 0 || form.memory_limit == 0 {
+        return Err(AppError::BadRequest("제목과 시간/메모리 제한을 확인해주세요.".to_string()));
+    }
+    meta.title = title.to_string();
+    meta.time_limit = form.time_limit;
+    meta.memory_limit = form.memory_limit;
+    meta.tags = form
+        .tags
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    if let Some(visibility) = form.visibility {
+        if visibility == ProblemVisibility::Public && meta.visibility != ProblemVisibility::Public && !user.is_admin() {
+            return Err(AppError::Forbidden);
+        }
+        meta.visibility = visibility;
+    }
+
+    repository.save(id, &meta, &form.statement.replace("\r\n", "\n")).await?;
+    repository.refresh_index(&state.db_pool, id).await?;
+
+    Ok(Redirect::to(&format!("/problems/{}/edit", id)))
+}
+
+/// 편집 중인 지문을 문제 페이지와 같은 방식으로 렌더링 (예제 절은 따로 돌려준다)
+pub async fn preview_statement(Json(form): Json<StatementPreviewForm>) -> Json<StatementPreview> {
+    let (samples, content) = extract_from_markdown(&form.statement.replace("\r\n", "\n"));
+    Json(StatementPreview {
+        html: markdown_to_html(&content),
+        samples,
+    })
+}
+
+/// 테스트케이스 업로드 (`name`, `input`, `output` 필드). 이름을 비우면 다음 번호를 쓴다.
+pub async fn upload_testcase(
+    Path(id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    mut multipart: Multipart,
+) -> Result<Redirect, AppError> {
+    problem_access::ensure_editable(&state.db_pool, auth_session.user.as_ref(), id).await?;
+    let mut name = String::new();
+    let mut input = None;
+    let mut output = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.to_string()))?
+    {
+        let field_name = field.name().unwrap_or_default().to_string();
+        let bytes = field.bytes().await.map_err(|e| AppError::BadRequest(e.to_string()))?;
+        match field_name.as_str() {
+            "name" => name = String::from_utf8_lossy(&bytes).trim().to_string(),
+            "input" => input = Some(bytes),
+            // 파일을 고르지 않은 필드는 빈 내용으로 온다
+            "output" if !bytes.is_empty() => output = Some(bytes),
+            _ => {}
+        }
+    }
+
+    let input = input.ok_or_else(|| AppError::BadRequest("입력 파일을 선택해주세요.".to_string()))?;
+    let repository = problem_repository::repository();
+    if name.is_empty() {
+        let existing: Vec<String> = repository.testcases(id).await?.into_iter().map(|t| t.name).collect();
+        name = (existing.len() + 1..)
+            .map(|number| number.to_string())
+            .find(|candidate| !existing.contains(candidate))
+            .unwrap_or_default();
+    }
+
+    repository
+        .save_testcase(id, &name, &input, output.as_deref())
+        .await?;
+
+    Ok(Redirect::to(&format!("/problems/{}/edit", id)))
+}
+
+/// 테스트케이스 순서(`position_<이름>`)와 예제 표시(`sample_<이름>`) 저장
+pub async fn update_testcases(
+    Path(id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Redirect, AppError> {
+    problem_access::ensure_editable(&state.db_pool, auth_session.user.as_ref(), id).await?;
+    let repository = problem_repository::repository();
+    let mut order: Vec<(i64, usize, String)> = repository
+        .testcases(id)
+        .await?
+        .into_iter()
+        .enumerate()
+        .map(|(index, testcase)| {
+            let position = form
+                .get(&format!("position_{}", testcase.name))
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(index as i64 + 1);
+            (position, index, testcase.name)
+        })
+        .collect();
+    order.sort();
+    let order: Vec<String> = order.into_iter().map(|(_, _, name)| name).collect();
+
+    // 예제는 파일을 복사하므로 이름을 바꾸기 전에 저장한다
+    let samples: Vec<String> = order
+        .iter()
+        .filter(|name| form.contains_key(&format!("sample_{}", name)))
+        .cloned()
+        .collect();
+    repository.set_sample_testcases(id, &samples).await?;
+    repository.reorder_testcases(id, &order).await?;
+    repository.refresh_index(&state.db_pool, id).await?;
+
+    Ok(Redirect::to(&format!("/problems/{}/edit", id)))
+}
+
+pub async fn delete_testcase(
+    Path((id, name)): Path<(i64, String)>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+) -> Result<Redirect, AppError> {
+    problem_access::ensure_editable(&state.db_pool, auth_session.user.as_ref(), id).await?;
+    problem_repository::repository().delete_testcase(id, &name).await?;
+    Ok(Redirect::to(&format!("/problems/{}/edit", id)))
+}
+
+/// 모범 답안을 제출 없이 모든 테스트케이스로 채점해 결과를 돌려준다.
+pub async fn validate_problem(
+    Path(id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Json(form): Json<ValidateSolutionForm>,
+) -> Result<Json<JudgeResult>, AppError> {
+    problem_access::ensure_editable(&state.db_pool, auth_session.user.as_ref(), id).await?;
+    if language::registry().get(&form.language).is_none() {
+        return Err(AppError::UnsupportedLanguage);
+    }
+    if form.source_code.len() > judge_pipeline::max_source_bytes() {
+        return Err(AppError::SourceTooLong(judge_pipeline::max_source_bytes()));
+    }
+    if problem_repository::repository().testcases(id).await?.is_empty() {
+        return Err(AppError::BadRequest("테스트케이스가 없습니다.".to_string()));
+    }
+
+    let request = judge_pipeline::build_request(0, form.language, form.source_code, id).await;
+    let result = state
+        .judge_queue
+        .judge_without_submission(request)
+        .await
+        .map_err(AppError::Judge)?;
+
+    Ok(Json(result))
+}
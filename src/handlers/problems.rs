@@ -1,16 +1,17 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::Html,
 };
 use axum_login::AuthSession;
-use gray_matter::{engine::YAML, Matter, ParsedEntity};
 use pulldown_cmark::{html, Parser};
 use tera::Context;
 
 use crate::{
     auth::Backend,
     error::AppError,
-    models::{FrontMatter, ProblemDetail, ProblemListItem, ProblemMeta, ProblemStats},
+    judge::language,
+    models::{ProblemDetail, ProblemListQuery, ProblemMeta, ProblemStats},
+    problem_access, problem_repository,
     AppState,
 };
 
@@ -18,13 +19,23 @@ use crate::{
 pub async fn problems_list(
     State(state): State<AppState>,
     auth_session: AuthSession<Backend>,
+    Query(query): Query<ProblemListQuery>,
 ) -> Result<Html<String>, AppError> {
-    let problems = get_problems_from_fs(&state).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let (problems, total_pages) = problem_repository::repository()
+        .list_page(&state.db_pool, page)
+        .await?;
 
     let mut context = Context::new();
     context.insert("active_page", "problems");
     context.insert("problems", &problems);
+    context.insert("page", &page.min(total_pages));
+    context.insert("total_pages", &total_pages);
     if let Some(user) = auth_session.user {
+        // 대회 전용, 작성 중인 문제는 편집할 수 있는 사람에게만 따로 보여준다
+        let unlisted = problem_access::unlisted_problems(&state.db_pool, &user).await?;
+        context.insert("unlisted_problems", &unlisted);
+        context.insert("can_edit", &user.can_edit_problems());
         context.insert("current_user", &user);
     }
 
@@ -32,90 +43,20 @@ pub async fn problems_list(
     Ok(Html(html))
 }
 
-async fn get_problems_from_fs(state: &AppState) -> Result<Vec<ProblemListItem>, AppError> {
-    let matter = Matter::<YAML>::new();
-    let mut problems = Vec::new();
-
-    let mut thousand_entries = tokio::fs::read_dir("problems").await?;
-
-    while let Some(thousand_folder) = thousand_entries.next_entry().await? {
-        if !thousand_folder.file_type().await?.is_dir() {
-            continue;
-        }
-
-        let mut problem_entries = tokio::fs::read_dir(thousand_folder.path()).await?;
-
-        while let Some(problem_folder) = problem_entries.next_entry().await? {
-            if !problem_folder.file_type().await?.is_dir() {
-                continue;
-            }
-
-            let problem_id = problem_folder
-                .file_name()
-                .to_string_lossy()
-                .parse::<i64>()
-                .ok();
-
-            if problem_id.is_none() {
-                continue;
-            }
-            let problem_id = problem_id.unwrap();
-
-            let md_path = problem_folder.path().join(format!("{}.md", problem_id));
-            if !md_path.exists() {
-                continue;
-            }
-
-            let content = tokio::fs::read_to_string(&md_path).await?;
-            let parsed: ParsedEntity = matter.parse(&content);
-
-            #[derive(serde::Deserialize)]
-            struct ProblemMeta {
-                title: String,
-            }
-
-            if let Some(data) = parsed.data {
-                if let Ok(meta) = data.deserialize::<ProblemMeta>() {
-                    let problem_stats: Option<ProblemStats> = sqlx::query_as(
-                        "SELECT problem_id, total_submissions, accepted_submissions, acceptance_rate,
-                                avg_execution_time, avg_memory_usage
-                         FROM submission_stats WHERE problem_id = ?",
-                    )
-                    .bind(problem_id as i64)
-                    .fetch_optional(&state.db_pool)
-                    .await?;
-
-                    let accuracy = if let Some(stats) = problem_stats {
-                        stats.acceptance_rate.unwrap_or(0.0)
-                    } else {
-                        0.0
-                    };
-
-                    problems.push(ProblemListItem {
-                        id: problem_id,
-                        title: meta.title,
-                        accuracy: (accuracy * 10.0).round() / 10.0,
-                    });
-                }
-            }
-        }
-    }
-
-    problems.sort_by_key(|p| p.id);
-    Ok(problems)
-}
-
 pub async fn problem_detail(
     Path(id): Path<i64>,
     State(state): State<AppState>,
     auth_session: AuthSession<Backend>,
 ) -> Result<Html<String>, AppError> {
+    problem_access::ensure_viewable(&state.db_pool, auth_session.user.as_ref(), id).await?;
     let problem = load_problem_detail(id, &state).await?;
 
     let mut context = Context::new();
     context.insert("active_page", "problems");
     context.insert("problem", &problem);
+    context.insert("languages", language::registry().all());
     if let Some(user) = auth_session.user {
+        context.insert("can_edit", &problem_access::can_edit(&state.db_pool, &user, id).await?);
         context.insert("current_user", &user);
     }
 
@@ -124,30 +65,14 @@ pub async fn problem_detail(
 }
 
 pub async fn load_problem_detail(id: i64, state: &AppState) -> Result<ProblemDetail, AppError> {
-    // 1001 -> 001000 폴더 (1000단위로 내림)
-    let folder_num = (id / 1000) * 1000;
-    let problem_path = std::path::Path::new("./problems")
-        .join(format!("{:06}", folder_num))
-        .join(id.to_string())
-        .join(format!("{}.md", id));
-
-    let content = tokio::fs::read_to_string(&problem_path)
-        .await
-        .map_err(|_| AppError::ProblemNotFound)?;
-
-    let matter = Matter::<YAML>::new();
-    let parsed = matter.parse(&content);
-
-    let front_matter = parsed.data.ok_or(AppError::InvalidProblemFormat)?;
-
-    let meta: FrontMatter = front_matter.deserialize()?;
+    let problem = problem_repository::repository().load(id).await?;
 
     let problem_stats: Option<ProblemStats> = sqlx::query_as(
         "SELECT problem_id, total_submissions, accepted_submissions, acceptance_rate,
                 avg_execution_time, avg_memory_usage
          FROM submission_stats WHERE problem_id = ?",
     )
-    .bind(id as i64)
+    .bind(id)
     .fetch_optional(&state.db_pool)
     .await?;
 
@@ -161,27 +86,29 @@ pub async fn load_problem_detail(id: i64, state: &AppState) -> Result<ProblemDet
         (0, 0, "0.0%".to_string())
     };
 
-    let parser = Parser::new(&parsed.content);
-    let mut html_content = String::new();
-    html::push_html(&mut html_content, parser);
-
-    // 예제 입출력은 일단 비워둠
-    let example_inputs = Vec::new();
-    let example_outputs = Vec::new();
+    let html_content = markdown_to_html(&problem.content);
+    let samples = problem_repository::repository().samples(id).await?;
 
     Ok(ProblemDetail {
         id,
         meta: ProblemMeta {
-            title: meta.title,
-            time_limit: meta.time_limit,
-            memory_limit: meta.memory_limit,
-            tags: meta.tags,
+            title: problem.meta.title.clone(),
+            time_limit: problem.meta.time_limit,
+            memory_limit: problem.meta.memory_limit,
+            tags: problem.meta.tags.clone(),
         },
         content: html_content,
-        example_inputs,
-        example_outputs,
+        samples,
         total_submits,
         correct_submits,
         accuracy,
     })
 }
+
+/// 문제 본문 마크다운을 HTML로 변환.
+/// 출제자도 지문을 쓰므로 마크다운에 섞인 HTML의 스크립트, 이벤트 핸들러 등은 지운다.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html_content = String::new();
+    html::push_html(&mut html_content, Parser::new(markdown));
+    ammonia::clean(&html_content)
+}
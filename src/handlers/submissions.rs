@@ -1,19 +1,34 @@
+use std::convert::Infallible;
+
 use axum::{
     extract::{Path, State},
-    response::{Html, Redirect},
-    Form,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, Redirect,
+    },
+    Form, Json,
 };
 use axum_login::AuthSession;
+use futures_util::stream::{self, Stream};
+use sqlx::SqlitePool;
 use tera::Context;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
 
 use crate::{
     auth::Backend,
     error::AppError,
-    judge,
-    models::{ProblemStatusData, SubmissionDetailData, SubmissionDetailRow, SubmissionRow, SubmitForm, TestcaseResultRow},
+    judge::{
+        language,
+        progress::{JudgeProgress, ProgressHub, SubmissionProgress},
+        run::{RunInput, RunResult, MAX_RUN_INPUTS, MAX_RUN_INPUT_BYTES},
+    },
+    judge_pipeline::{self, NewSubmission},
+    models::{CompileErrorRow, DiffRow, ProblemStatusData, RunForm, SubmissionDetailData, SubmissionDetailRow, SubmissionRow, SubmitForm, SubtaskResultRow, TestcaseResultRow},
+    problem_access, problem_repository,
     AppState,
 };
 
+use super::contests;
 use super::problems::load_problem_detail;
 
 pub async fn submit_solution(
@@ -23,96 +38,78 @@ pub async fn submit_solution(
     Form(form): Form<SubmitForm>,
 ) -> Result<Redirect, AppError> {
     let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    problem_access::ensure_viewable(&state.db_pool, Some(&user), problem_id).await?;
+    if language::registry().get(&form.language).is_none() {
+        return Err(AppError::UnsupportedLanguage);
+    }
+    if form.source_code.len() > judge_pipeline::max_source_bytes() {
+        return Err(AppError::SourceTooLong(judge_pipeline::max_source_bytes()));
+    }
 
-    let submission_id: i64 = sqlx::query_scalar(
-        "INSERT INTO submissions (user_id, problem_id, language, source_code, status)
-         VALUES (?, ?, ?, ?, 'PENDING') RETURNING id",
+    let submission_id = judge_pipeline::create_submission(
+        &state.db_pool,
+        &state.judge_queue,
+        NewSubmission {
+            user_id: user.id,
+            problem_id,
+            contest_id: None,
+            language: form.language,
+            source_code: form.source_code,
+        },
     )
-    .bind(user.id)
-    .bind(problem_id)
-    .bind(&form.language)
-    .bind(&form.source_code)
-    .fetch_one(&state.db_pool)
     .await?;
 
-    tokio::spawn(async move {
-        let judge_request = judge::JudgeRequest {
-            submission_id,
-            language: form.language,
-            source_code: form.source_code,
-            problem_id,
-        };
-
-        if let Ok(result) = judge::judge_submission(judge_request).await {
-            let _ = sqlx::query(
-                "UPDATE submissions SET status = ?, score = ?, execution_time = ?,
-                 memory_usage = ?, compile_message = ?, runtime_error_type = ?,
-                 runtime_error_message = ?, total_testcases = ?, passed_testcases = ?,
-                 judged_at = CURRENT_TIMESTAMP WHERE id = ?",
-            )
-            .bind(&result.status)
-            .bind(result.score)
-            .bind(result.execution_time)
-            .bind(result.memory_usage)
-            .bind(&result.compile_message)
-            .bind(&result.runtime_error_type)
-            .bind(&result.runtime_error_message)
-            .bind(result.total_testcases)
-            .bind(result.passed_testcases)
-            .bind(submission_id)
-            .execute(&state.db_pool)
-            .await;
-
-            for testcase in result.testcase_results {
-                let _ = sqlx::query(
-                    "INSERT INTO testcase_results
-                     (submission_id, testcase_number, status, execution_time, memory_usage, error_message, expected_output, actual_output)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-                )
-                .bind(submission_id)
-                .bind(testcase.testcase_number)
-                .bind(&testcase.status)
-                .bind(testcase.execution_time)
-                .bind(testcase.memory_usage)
-                .bind(&testcase.error_message)
-                .bind(&testcase.expected_output)
-                .bind(&testcase.actual_output)
-                .execute(&state.db_pool)
-                .await;
-            }
+    Ok(Redirect::to(&format!("/submissions/{}", submission_id)))
+}
 
-            if let Some(compile_errors) = result.compile_errors {
-                for error in compile_errors {
-                    let _ = sqlx::query(
-                        "INSERT INTO compile_errors
-                         (submission_id, line_number, column_number, error_type, error_message)
-                         VALUES (?, ?, ?, ?, ?)",
-                    )
-                    .bind(submission_id)
-                    .bind(error.line_number)
-                    .bind(error.column_number)
-                    .bind(&error.error_type)
-                    .bind(&error.error_message)
-                    .execute(&state.db_pool)
-                    .await;
-                }
-            }
+/// 제출을 만들지 않고 직접 입력하거나 예제 입력으로 코드를 실행한다.
+pub async fn run_solution(
+    Path(problem_id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+    Json(form): Json<RunForm>,
+) -> Result<Json<RunResult>, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    if let Err(e) = problem_access::ensure_viewable(&state.db_pool, Some(&user), problem_id).await
+        && !contests::is_open_to_participant(&state.db_pool, user.id, problem_id).await?
+    {
+        return Err(e);
+    }
+    if language::registry().get(&form.language).is_none() {
+        return Err(AppError::UnsupportedLanguage);
+    }
+    if form.source_code.len() > judge_pipeline::max_source_bytes() {
+        return Err(AppError::SourceTooLong(judge_pipeline::max_source_bytes()));
+    }
 
-            // 대회 제출인 경우 순위 업데이트
-            if let Ok(Some((contest_id, user_id))) = sqlx::query_as::<_, (i64, i64)>(
-                "SELECT contest_id, user_id FROM submissions WHERE id = ? AND contest_id IS NOT NULL"
-            )
-            .bind(submission_id)
-            .fetch_optional(&state.db_pool)
-            .await
-            {
-                // 대회 순위 업데이트
-                let _ = crate::contest_scoring::update_standings(&state.db_pool, contest_id, user_id).await;
+    let inputs = match form.input {
+        Some(input) if input.len() > MAX_RUN_INPUT_BYTES => {
+            return Err(AppError::BadRequest("입력이 너무 큽니다.".to_string()));
+        }
+        Some(input) => vec![RunInput { input, expected_output: None }],
+        None => {
+            let samples: Vec<RunInput> = problem_repository::repository()
+                .samples(problem_id)
+                .await?
+                .into_iter()
+                .take(MAX_RUN_INPUTS)
+                .map(|sample| RunInput { input: sample.input, expected_output: Some(sample.output) })
+                .collect();
+            if samples.is_empty() {
+                return Err(AppError::BadRequest("예제 입력이 없는 문제입니다.".to_string()));
             }
+            samples
         }
-    });
+    };
 
-    Ok(Redirect::to(&format!("/submissions/{}", submission_id)))
+    let request = judge_pipeline::build_request(0, form.language, form.source_code, problem_id).await;
+    let result = state
+        .judge_queue
+        .run_code(request, inputs)
+        .await
+        .map_err(AppError::Judge)?;
+
+    Ok(Json(result))
 }
 
 pub async fn problem_status(
@@ -120,10 +117,11 @@ pub async fn problem_status(
     State(state): State<AppState>,
     auth_session: AuthSession<Backend>,
 ) -> Result<Html<String>, AppError> {
+    problem_access::ensure_viewable(&state.db_pool, auth_session.user.as_ref(), problem_id).await?;
     let problem = load_problem_detail(problem_id, &state).await?;
 
     let submissions: Vec<SubmissionRow> = sqlx::query_as(
-        "SELECT s.id, u.username, s.language, s.status, s.score,
+        "SELECT s.id, u.username, s.language, s.status, s.score, s.max_score,
                 s.execution_time, s.memory_usage,
                 s.created_at as submitted_at
          FROM submissions s
@@ -158,8 +156,9 @@ pub async fn submission_detail(
     State(state): State<AppState>,
     auth_session: AuthSession<Backend>,
 ) -> Result<Html<String>, AppError> {
+    problem_access::ensure_submission_viewable(&state.db_pool, auth_session.user.as_ref(), submission_id).await?;
     let submission: SubmissionDetailRow = sqlx::query_as(
-        "SELECT s.id, s.problem_id, u.username, s.language, s.status, s.score,
+        "SELECT s.id, s.problem_id, u.username, s.language, s.status, s.score, s.max_score,
                 s.execution_time, s.memory_usage, s.compile_message,
                 s.runtime_error_type, s.runtime_error_message,
                 s.total_testcases, s.passed_testcases,
@@ -174,8 +173,9 @@ pub async fn submission_detail(
     .await?
     .ok_or(AppError::NotFound)?;
 
-    let testcase_results: Vec<TestcaseResultRow> = sqlx::query_as(
-        "SELECT testcase_number, status, execution_time, memory_usage, error_message
+    let mut testcase_results: Vec<TestcaseResultRow> = sqlx::query_as(
+        "SELECT testcase_number, status, execution_time, memory_usage, error_message,
+                expected_output, actual_output, output_start_line, diff_line, diff_column
          FROM testcase_results
          WHERE submission_id = ?
          ORDER BY testcase_number",
@@ -183,19 +183,169 @@ pub async fn submission_detail(
     .bind(submission_id)
     .fetch_all(&state.db_pool)
     .await?;
+    for testcase in &mut testcase_results {
+        testcase.diff_rows = diff_rows(testcase);
+    }
+
+    let subtask_results: Vec<SubtaskResultRow> = sqlx::query_as(
+        "SELECT subtask_number, name, status, score, max_score, total_testcases, passed_testcases
+         FROM subtask_results
+         WHERE submission_id = ?
+         ORDER BY subtask_number",
+    )
+    .bind(submission_id)
+    .fetch_all(&state.db_pool)
+    .await?;
+
+    let compile_errors: Vec<CompileErrorRow> = sqlx::query_as(
+        "SELECT file_name, line_number, column_number, severity, error_type, error_message
+         FROM compile_errors
+         WHERE submission_id = ?
+         ORDER BY id",
+    )
+    .bind(submission_id)
+    .fetch_all(&state.db_pool)
+    .await?;
 
     let submission_detail = SubmissionDetailData {
         submission,
         testcase_results,
+        subtask_results,
+        compile_errors,
     };
 
     let mut context = Context::new();
     context.insert("active_page", "problems");
     context.insert("submission_detail", &submission_detail);
     if let Some(user) = auth_session.user {
+        // 소스 코드는 제출한 사용자와 관리자에게만 보여준다
+        if user.is_admin() || user.username == submission_detail.submission.username {
+            let (source_code,): (String,) = sqlx::query_as("SELECT source_code FROM submissions WHERE id = ?")
+                .bind(submission_id)
+                .fetch_one(&state.db_pool)
+                .await?;
+            let source_lines: Vec<&str> = source_code.lines().collect();
+            context.insert("source_lines", &source_lines);
+            context.insert("error_lines", &diagnostic_lines(&submission_detail.compile_errors, "error"));
+            context.insert("warning_lines", &diagnostic_lines(&submission_detail.compile_errors, "warning"));
+        }
         context.insert("current_user", &user);
     }
 
     let html = state.tera.render("submission_detail.html", &context)?;
     Ok(Html(html))
 }
+
+/// 공개 테스트케이스의 예상 출력과 실행 결과를 줄 번호별로 나란히 놓는다
+fn diff_rows(testcase: &TestcaseResultRow) -> Vec<DiffRow> {
+    let (Some(expected), Some(actual)) = (&testcase.expected_output, &testcase.actual_output) else {
+        return Vec::new();
+    };
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let start_line = testcase.output_start_line.unwrap_or(1);
+
+    (0..expected.len().max(actual.len()))
+        .map(|i| {
+            let line_number = start_line + i as i32;
+            DiffRow {
+                line_number,
+                expected: expected.get(i).map(|line| line.to_string()),
+                actual: actual.get(i).map(|line| line.to_string()),
+                differs: testcase.diff_line.is_some_and(|diff| line_number >= diff)
+                    && expected.get(i) != actual.get(i),
+            }
+        })
+        .collect()
+}
+
+/// 소스 코드에서 강조할 줄 번호. 시스템 헤더 등 다른 파일(절대 경로)의 진단은 제외한다.
+fn diagnostic_lines(compile_errors: &[CompileErrorRow], severity: &str) -> Vec<i32> {
+    compile_errors
+        .iter()
+        .filter(|e| e.severity == severity)
+        .filter(|e| !e.file_name.as_deref().is_some_and(|f| f.starts_with('/')))
+        .filter_map(|e| e.line_number)
+        .collect()
+}
+
+/// 제출의 채점 진행 상황을 Server-Sent Events로 보낸다.
+///
+/// 처음에 현재 상태를 보내고, 이후 상태가 바뀔 때마다 `JudgeProgress`를 JSON으로 보낸다.
+/// 채점 결과(`finished`)를 보내면 스트림을 닫는다.
+pub async fn submission_events(
+    Path(submission_id): Path<i64>,
+    State(state): State<AppState>,
+    auth_session: AuthSession<Backend>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    problem_access::ensure_submission_viewable(&state.db_pool, auth_session.user.as_ref(), submission_id).await?;
+    let hub = state.judge_queue.progress().clone();
+    let pool = state.db_pool.clone();
+
+    // 현재 상태를 읽는 사이에 바뀐 상태를 놓치지 않도록 먼저 구독한다
+    let receiver = hub.subscribe();
+    let initial = current_progress(&pool, &hub, submission_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let events = stream::unfold(
+        (Some(initial), Some(receiver)),
+        move |(next, receiver): (Option<JudgeProgress>, Option<Receiver<SubmissionProgress>>)| {
+            let hub = hub.clone();
+            let pool = pool.clone();
+            async move {
+                let mut receiver = receiver?;
+                let progress = match next {
+                    Some(progress) => progress,
+                    None => next_progress(&mut receiver, &hub, &pool, submission_id).await?,
+                };
+
+                let receiver = (!progress.is_finished()).then_some(receiver);
+                let event = Event::default().data(serde_json::to_string(&progress).unwrap_or_default());
+                Some((Ok::<_, Infallible>(event), (None, receiver)))
+            }
+        },
+    );
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// 진행 중이면 채점 워커가 마지막으로 알린 상태, 끝났으면 저장된 결과
+async fn current_progress(
+    pool: &SqlitePool,
+    hub: &ProgressHub,
+    submission_id: i64,
+) -> Result<Option<JudgeProgress>, sqlx::Error> {
+    let row: Option<(String, i32, Option<i32>)> =
+        sqlx::query_as("SELECT status, score, max_score FROM submissions WHERE id = ?")
+            .bind(submission_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(row.map(|(status, score, max_score)| match status.as_str() {
+        "PENDING" => hub.latest(submission_id).unwrap_or(JudgeProgress::Queued),
+        "JUDGING" => hub.latest(submission_id).unwrap_or(JudgeProgress::Compiling),
+        _ => JudgeProgress::Finished {
+            status,
+            score,
+            max_score: max_score.unwrap_or(crate::judge::scoring::DEFAULT_MAX_SCORE),
+        },
+    }))
+}
+
+async fn next_progress(
+    receiver: &mut Receiver<SubmissionProgress>,
+    hub: &ProgressHub,
+    pool: &SqlitePool,
+    submission_id: i64,
+) -> Option<JudgeProgress> {
+    loop {
+        match receiver.recv().await {
+            Ok(update) if update.submission_id == submission_id => return Some(update.progress),
+            Ok(_) => continue,
+            // 이벤트를 놓쳤으면 현재 상태를 다시 읽는다
+            Err(RecvError::Lagged(_)) => return current_progress(pool, hub, submission_id).await.ok().flatten(),
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
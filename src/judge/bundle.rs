@@ -0,0 +1,123 @@
+// 문제 번들 (원격 워커에 보내는 문제 폴더의 파일 목록과 해시)
+//
+// 번들 해시는 파일 경로, 크기, 파일별 SHA-256으로 계산하므로 테스트케이스나 체커가 바뀌면 달라진다.
+// 워커는 해시별로 번들을 캐시하고, 내려받은 파일은 목록의 해시와 비교해 검증한다.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::problem_repository;
+
+/// 경로 -> (크기, 수정 시각, SHA-256)
+type FileHashCache = HashMap<PathBuf, (u64, SystemTime, String)>;
+
+/// 파일 해시 캐시. 큰 테스트케이스를 작업마다 다시 읽지 않는다.
+static FILE_HASHES: Lazy<Mutex<FileHashCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub problem_id: i64,
+    pub hash: String,
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// 문제 폴더 기준 경로 (`/`로 구분)
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl BundleManifest {
+    /// 파일 목록으로 계산한 번들 해시가 `hash`와 같은지 확인
+    pub fn is_consistent(&self) -> bool {
+        bundle_hash(&self.files) == self.hash
+    }
+
+    /// 목록에 있는 파일만 내려받을 수 있다. 상위 디렉토리로 벗어나는 경로는 목록에 들어가지 않는다.
+    pub fn file(&self, path: &str) -> Option<&BundleFile> {
+        self.files.iter().find(|f| f.path == path)
+    }
+}
+
+/// 문제 폴더 전체(문제 설명, 테스트케이스, 체커/인터랙터 소스)의 번들 목록
+pub async fn manifest(problem_id: i64) -> anyhow::Result<BundleManifest> {
+    let root = problem_repository::repository().problem_dir(problem_id);
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Problem {} not found", problem_id));
+    }
+
+    let files = tokio::task::spawn_blocking(move || collect_files(&root)).await??;
+    Ok(BundleManifest {
+        problem_id,
+        hash: bundle_hash(&files),
+        files,
+    })
+}
+
+fn collect_files(root: &Path) -> anyhow::Result<Vec<BundleFile>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root)?;
+        let path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let metadata = entry.metadata()?;
+        files.push(BundleFile {
+            path,
+            size: metadata.len(),
+            sha256: file_hash(entry.path(), metadata.len(), metadata.modified()?)?,
+        });
+    }
+    Ok(files)
+}
+
+fn file_hash(path: &Path, size: u64, modified: SystemTime) -> anyhow::Result<String> {
+    if let Some((cached_size, cached_modified, hash)) = FILE_HASHES.lock().unwrap().get(path)
+        && *cached_size == size
+        && *cached_modified == modified
+    {
+        return Ok(hash.clone());
+    }
+
+    let mut hasher = Sha256::new();
+    let mut file = std::fs::File::open(path)?;
+    std::io::copy(&mut file, &mut hasher)?;
+    let hash = hex(&hasher.finalize());
+
+    FILE_HASHES
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), (size, modified, hash.clone()));
+    Ok(hash)
+}
+
+fn bundle_hash(files: &[BundleFile]) -> String {
+    let mut hasher = Sha256::new();
+    for file in files {
+        hasher.update(format!("{}\0{}\0{}\n", file.path, file.size, file.sha256));
+    }
+    hex(&hasher.finalize())
+}
+
+/// 내려받은 파일 내용의 SHA-256
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,454 @@
+// 출력 검사기 (스페셜 저지 포함)
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+use super::{compile, language};
+use crate::problem_repository;
+use super::native::{run_process, RunLimits};
+use super::{JudgeBackend, JudgeRequest, TestcaseReport, BUILD_DIR};
+
+/// 커스텀 체커 실행 시간 제한 (테스트케이스마다)
+pub(super) const CHECKER_TIME_LIMIT_SECS: u64 = 10;
+/// 결과에 남기는 체커 메시지 최대 길이
+const CHECKER_MESSAGE_LIMIT: usize = 1024;
+/// 컴파일된 체커/인터랙터를 보관하는 디렉토리 기본 경로 (`<캐시>/<키>/<이름>`).
+/// 실행 위치 기준이며 `CHECKER_CACHE_DIR`로 변경 가능
+pub const DEFAULT_PROGRAM_CACHE_DIR: &str = "data/checkers";
+/// 체커/인터랙터를 컴파일하는 언어 (languages.toml의 id). Docker 백엔드는 이 언어의 이미지로 컴파일한다.
+pub(super) const PROGRAM_LANGUAGE: &str = "cpp";
+
+/// 문제 front matter의 `checker` 항목.
+///
+/// 이름만 적거나(`checker: token`) 옵션과 함께 적을 수 있다.
+/// ```yaml
+/// checker:
+///   type: float
+///   abs_eps: 1e-6
+///   rel_eps: 1e-6
+/// ```
+/// 커스텀 체커는 문제 폴더 기준 경로로 지정하며(`type: custom`, `source: checker.cpp`),
+/// `checker <input> <expected> <actual>` 형태로 실행된다. 종료 코드 0은 정답, 1/2는 오답이고
+/// 체커가 출력한 메시지는 테스트케이스 결과에 기록된다.
+/// `testlib: true`이면 testlib 체커 순서(`checker <input> <actual> <expected>`)로 실행한다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckerSpec {
+    /// 줄 단위 비교 (`diff -w`처럼 줄 안의 공백 차이와 마지막 빈 줄은 무시)
+    #[default]
+    Exact,
+    /// 공백으로 나눈 토큰 비교
+    Token,
+    /// 실수 토큰은 절대/상대 오차 안이면 같은 값으로 본다
+    Float {
+        #[serde(default = "default_epsilon")]
+        abs_eps: f64,
+        #[serde(default = "default_epsilon")]
+        rel_eps: f64,
+    },
+    /// 줄 순서를 무시하고 비교
+    LineSet,
+    /// 문제 폴더에 포함된 체커 프로그램
+    Custom {
+        source: String,
+        #[serde(default)]
+        testlib: bool,
+    },
+}
+
+fn default_epsilon() -> f64 {
+    1e-6
+}
+
+/// 이름만 적은 형식과 옵션을 포함한 형식을 모두 허용
+pub fn deserialize_checker<'de, D>(deserializer: D) -> Result<CheckerSpec, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NameOrSpec {
+        Name(String),
+        Spec(CheckerSpec),
+    }
+
+    match NameOrSpec::deserialize(deserializer)? {
+        NameOrSpec::Spec(spec) => Ok(spec),
+        NameOrSpec::Name(name) => match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "exact" => Ok(CheckerSpec::Exact),
+            "token" => Ok(CheckerSpec::Token),
+            "float" => Ok(CheckerSpec::Float {
+                abs_eps: default_epsilon(),
+                rel_eps: default_epsilon(),
+            }),
+            "line_set" | "lineset" => Ok(CheckerSpec::LineSet),
+            other => Err(Error::custom(format!("Unknown checker: {}", other))),
+        },
+    }
+}
+
+/// 검사 결과
+struct CheckOutcome {
+    accepted: bool,
+    message: Option<String>,
+}
+
+/// 정상 종료(`OK`)한 테스트케이스의 출력을 검사해 최종 결과를 채운다.
+///
+/// 프로그램 출력은 작업 디렉토리의 `output/<이름>.out`에 있어야 한다.
+/// Docker 백엔드는 커스텀 체커를 채점 컨테이너 안에서 실행하므로, 여기까지 `OK`로 오는 것은 내장 체커뿐이다.
+pub(crate) async fn check_reports(
+    backend: &dyn JudgeBackend,
+    reports: &mut [TestcaseReport],
+    request: &JudgeRequest,
+    workspace: &Path,
+) -> anyhow::Result<()> {
+    let testcases = request.testcase_path();
+    let mut custom_checker = None;
+
+    for report in reports.iter_mut().filter(|r| r.verdict == "OK") {
+        let input_path = testcases.join(format!("{}.in", report.name));
+        let expected_path = testcases.join(format!("{}.out", report.name));
+        let actual_path = workspace.join("output").join(format!("{}.out", report.name));
+
+        // 예상 출력이 없으면 통과로 간주
+        if !expected_path.exists() {
+            report.verdict = "ACCEPTED".to_string();
+            continue;
+        }
+
+        let outcome = match &request.checker {
+            CheckerSpec::Custom { testlib, .. } => {
+                if custom_checker.is_none() {
+                    custom_checker = prepare(backend, request).await?;
+                }
+                let checker = custom_checker.as_ref().unwrap();
+                let (second, third) = if *testlib {
+                    (&actual_path, &expected_path)
+                } else {
+                    (&expected_path, &actual_path)
+                };
+                run_custom_checker(checker, &input_path, second, third, &actual_path, workspace).await?
+            }
+            spec => {
+                let expected = String::from_utf8_lossy(&fs::read(&expected_path).await?).to_string();
+                let actual = String::from_utf8_lossy(&fs::read(&actual_path).await.unwrap_or_default()).to_string();
+                check_builtin(spec, &expected, &actual)
+            }
+        };
+
+        report.verdict = if outcome.accepted { "ACCEPTED" } else { "WRONG_ANSWER" }.to_string();
+        report.message = outcome.message;
+    }
+
+    Ok(())
+}
+
+fn check_builtin(spec: &CheckerSpec, expected: &str, actual: &str) -> CheckOutcome {
+    let accepted = match spec {
+        CheckerSpec::Exact => {
+            let expected_lines = lines_of(expected);
+            let actual_lines = lines_of(actual);
+            expected_lines.len() == actual_lines.len()
+                && expected_lines
+                    .iter()
+                    .zip(&actual_lines)
+                    .all(|(e, a)| same_ignoring_whitespace(e, a))
+        }
+        CheckerSpec::Token => expected.split_whitespace().eq(actual.split_whitespace()),
+        CheckerSpec::Float { abs_eps, rel_eps } => {
+            let expected_tokens: Vec<&str> = expected.split_whitespace().collect();
+            let actual_tokens: Vec<&str> = actual.split_whitespace().collect();
+            expected_tokens.len() == actual_tokens.len()
+                && expected_tokens
+                    .iter()
+                    .zip(&actual_tokens)
+                    .all(|(e, a)| floats_match(e, a, *abs_eps, *rel_eps))
+        }
+        CheckerSpec::LineSet => {
+            let mut expected_lines = lines_of(expected);
+            let mut actual_lines = lines_of(actual);
+            expected_lines.retain(|line| !line.is_empty());
+            actual_lines.retain(|line| !line.is_empty());
+            expected_lines.sort_unstable();
+            actual_lines.sort_unstable();
+            expected_lines == actual_lines
+        }
+        CheckerSpec::Custom { .. } => unreachable!("custom checker is not built in"),
+    };
+
+    CheckOutcome { accepted, message: None }
+}
+
+/// 줄 끝 공백과 마지막 빈 줄을 제거한 줄 목록
+pub(super) fn lines_of(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// 공백 문자를 모두 빼고 비교 (`diff -w`와 같은 기준)
+fn same_ignoring_whitespace(expected: &str, actual: &str) -> bool {
+    let non_whitespace = |line: &str| line.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    non_whitespace(expected) == non_whitespace(actual)
+}
+
+fn floats_match(expected: &str, actual: &str, abs_eps: f64, rel_eps: f64) -> bool {
+    match (expected.parse::<f64>(), actual.parse::<f64>()) {
+        (Ok(e), Ok(a)) if e.is_finite() && a.is_finite() => {
+            let diff = (e - a).abs();
+            diff <= abs_eps || diff <= rel_eps * e.abs()
+        }
+        _ => expected == actual,
+    }
+}
+
+/// 커스텀 체커 실행 파일 준비. 커스텀 체커가 아니면 `None`.
+///
+/// 채점 컨테이너에서도 실행할 수 있도록 `.cpp` 소스는 정적 링크로 컴파일한다.
+pub(super) async fn prepare(backend: &dyn JudgeBackend, request: &JudgeRequest) -> anyhow::Result<Option<PathBuf>> {
+    let CheckerSpec::Custom { source, .. } = &request.checker else {
+        return Ok(None);
+    };
+
+    let binary = prepare_problem_program(backend, &request.problem_path(), source, "checker", &["-static"]).await?;
+    Ok(Some(binary))
+}
+
+/// 체커/인터랙터 C++ 소스 컴파일 명령. 작업 디렉토리의 `<name>.cpp`를 `<name>`으로 컴파일한다.
+pub(super) fn program_compile_command(name: &str, extra_flags: &[&str]) -> String {
+    let mut command = vec!["g++", "-O2", "-std=c++17"];
+    command.extend(extra_flags);
+    command.extend(["-o", name]);
+    format!("{} {}.cpp", command.join(" "), name)
+}
+
+/// 문제에 포함된 체커/인터랙터 실행 파일 준비.
+///
+/// `source`는 front matter에 적힌 문제 폴더 기준 경로로, 폴더 밖(절대 경로, `..`, 밖을 가리키는 링크)이면 거부한다.
+/// `.cpp` 소스는 제출 프로그램과 같은 백엔드(샌드박스)에서 컴파일하고, 그 외에는 실행 파일로 간주한다.
+/// 컴파일 결과는 소스, 컴파일 명령, 컴파일러 버전의 해시로 캐시한다. 임시 디렉토리에서 컴파일한 뒤
+/// 이름을 바꿔 넣으므로 동시에 채점하는 다른 제출이 반쯤 쓰인 실행 파일을 실행하지 않는다.
+pub(super) async fn prepare_problem_program(
+    backend: &dyn JudgeBackend,
+    problem_dir: &Path,
+    source: &str,
+    name: &str,
+    extra_flags: &[&str],
+) -> anyhow::Result<PathBuf> {
+    let source = resolve_problem_file(problem_dir, source).await?;
+    if source.extension().is_none_or(|ext| ext != "cpp") {
+        return Ok(source);
+    }
+
+    let source_code = fs::read(&source).await?;
+    let command = program_compile_command(name, extra_flags);
+    let version = backend.toolchain_version(language::lookup(PROGRAM_LANGUAGE)?).await?;
+    let key = program_key(&[command.as_bytes(), version.as_bytes(), &source_code]);
+
+    let cache_root = program_cache_root().await?;
+    let binary = cache_root.join(&key).join(name);
+    if fs::try_exists(&binary).await? {
+        return Ok(binary);
+    }
+
+    let staging = cache_root.join(format!(".tmp_{}", Uuid::new_v4()));
+    fs::create_dir_all(&staging).await?;
+    let result: anyhow::Result<()> = async {
+        fs::write(staging.join(format!("{}.cpp", name)), &source_code).await?;
+        let outcome = backend.compile_problem_program(&staging, name, extra_flags).await?;
+        if !outcome.success {
+            return Err(anyhow::anyhow!("{} 컴파일 실패 ({}): {}", name, source.display(), outcome.message));
+        }
+        fs::create_dir_all(cache_root.join(&key)).await?;
+        // 같은 키를 다른 채점이 먼저 만들었어도 내용이 같으므로 덮어써도 된다
+        fs::rename(staging.join(BUILD_DIR).join(name), &binary).await?;
+        Ok(())
+    }
+    .await;
+    let _ = fs::remove_dir_all(&staging).await;
+    result?;
+
+    Ok(binary)
+}
+
+/// 권한을 확인한 체커/인터랙터 캐시 루트.
+/// Docker 백엔드가 실행 파일을 컨테이너에 바인드 마운트하므로 절대 경로로 바꿔 둔다.
+async fn program_cache_root() -> anyhow::Result<&'static Path> {
+    static ROOT: OnceCell<PathBuf> = OnceCell::const_new();
+    let root = ROOT
+        .get_or_try_init(|| async {
+            let root = std::env::var("CHECKER_CACHE_DIR").unwrap_or_else(|_| DEFAULT_PROGRAM_CACHE_DIR.to_string());
+            let root = std::path::absolute(root)?;
+            compile::ensure_private_dir(&root).await?;
+            anyhow::Ok(root)
+        })
+        .await?;
+    Ok(root)
+}
+
+/// 문제 폴더 안의 파일 경로 (심볼릭 링크를 따라간 실제 경로)
+async fn resolve_problem_file(problem_dir: &Path, source: &str) -> anyhow::Result<PathBuf> {
+    let outside = || anyhow::anyhow!("{}: 문제 폴더 밖의 파일은 체커/인터랙터로 쓸 수 없습니다", source);
+    if !problem_repository::is_relative_inside(source) {
+        return Err(outside());
+    }
+
+    let problem_dir = fs::canonicalize(problem_dir).await?;
+    let path = fs::canonicalize(problem_dir.join(source))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: 파일을 찾을 수 없습니다 ({})", source, e))?;
+    if !path.starts_with(&problem_dir) {
+        return Err(outside());
+    }
+    Ok(path)
+}
+
+/// 캐시 키: 각 부분의 SHA-256
+fn program_key(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+        hasher.update([0u8]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// 채점 컨테이너에서 체커가 남긴 메시지(`output/<이름>.checker`)를 테스트케이스 결과에 붙인다.
+pub(super) async fn attach_messages(reports: &mut [TestcaseReport], workspace: &Path) {
+    for report in reports.iter_mut().filter(|r| r.message.is_none()) {
+        let path = workspace.join("output").join(format!("{}.checker", report.name));
+        let message = fs::read_to_string(&path).await.unwrap_or_default();
+        let message: String = message.trim().chars().take(CHECKER_MESSAGE_LIMIT).collect();
+        if !message.is_empty() {
+            report.message = Some(message);
+        }
+    }
+}
+
+/// `second`/`third`는 체커 종류에 따라 예상 출력과 프로그램 출력 순서가 다르다.
+async fn run_custom_checker(
+    checker: &Path,
+    input: &Path,
+    second: &Path,
+    third: &Path,
+    actual: &Path,
+    workspace: &Path,
+) -> anyhow::Result<CheckOutcome> {
+    // 프로그램이 출력 파일을 만들지 못한 경우에도 체커가 읽을 수 있도록 빈 파일 생성
+    if !actual.exists() {
+        if let Some(parent) = actual.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(actual, b"").await?;
+    }
+
+    let args: Vec<String> = [checker, input, second, third]
+        .iter()
+        .map(|path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).to_string_lossy().to_string())
+        .collect();
+
+    let message_path = workspace.join("checker_message.txt");
+    let message_file = std::fs::File::create(&message_path)?;
+    let outcome = run_process(
+        &args,
+        workspace,
+        Stdio::null(),
+        Stdio::from(message_file.try_clone()?),
+        Stdio::from(message_file),
+        RunLimits {
+            cpu_secs: CHECKER_TIME_LIMIT_SECS,
+            address_space_bytes: None,
+            file_size_bytes: 16 * 1024 * 1024,
+        },
+        Duration::from_secs(CHECKER_TIME_LIMIT_SECS),
+    )
+    .await?;
+
+    let message = fs::read_to_string(&message_path).await.unwrap_or_default();
+    let message: String = message.trim().chars().take(CHECKER_MESSAGE_LIMIT).collect();
+    let message = (!message.is_empty()).then_some(message);
+
+    match (outcome.timed_out, outcome.exit_code) {
+        (false, 0) => Ok(CheckOutcome { accepted: true, message }),
+        (false, 1) | (false, 2) => Ok(CheckOutcome { accepted: false, message }),
+        _ => Err(anyhow::anyhow!(
+            "체커 실행 실패 (종료 코드 {}): {}",
+            outcome.exit_code,
+            message.unwrap_or_default()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepts(spec: &CheckerSpec, expected: &str, actual: &str) -> bool {
+        check_builtin(spec, expected, actual).accepted
+    }
+
+    #[test]
+    fn exact_ignores_spacing_inside_lines() {
+        let spec = CheckerSpec::Exact;
+        assert!(accepts(&spec, "1 2 3\n", "1  2\t3\n"));
+        assert!(accepts(&spec, "1 2 3\n", "  1 2 3   \n\n"));
+        assert!(accepts(&spec, "hello world\n", "helloworld\n"));
+    }
+
+    #[test]
+    fn exact_still_compares_lines() {
+        let spec = CheckerSpec::Exact;
+        assert!(!accepts(&spec, "1 2 3\n", "1 2 4\n"));
+        assert!(!accepts(&spec, "1\n2\n", "1 2\n"));
+        assert!(!accepts(&spec, "1\n\n2\n", "1\n2\n"));
+    }
+
+    #[test]
+    fn token_and_float_compare_tokens() {
+        assert!(accepts(&CheckerSpec::Token, "1 2\n3\n", "1\n2 3"));
+        assert!(!accepts(&CheckerSpec::Token, "1 2 3", "1 2"));
+
+        let float = CheckerSpec::Float { abs_eps: 1e-6, rel_eps: 1e-6 };
+        assert!(accepts(&float, "0.5 2", "0.5000001 2"));
+        assert!(!accepts(&float, "0.5", "0.51"));
+    }
+
+    #[test]
+    fn line_set_ignores_order() {
+        assert!(accepts(&CheckerSpec::LineSet, "a\nb\n", "b\n\na\n"));
+        assert!(!accepts(&CheckerSpec::LineSet, "a\nb\n", "a\na\n"));
+    }
+
+    #[tokio::test]
+    async fn problem_programs_must_stay_inside_the_problem_folder() {
+        let root = std::env::temp_dir().join(format!("nekonic_checker_test_{}", Uuid::new_v4()));
+        let problem_dir = root.join("problem");
+        std::fs::create_dir_all(&problem_dir).unwrap();
+        std::fs::write(problem_dir.join("checker.cpp"), "int main() {}").unwrap();
+        std::fs::write(root.join("outside"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("outside"), problem_dir.join("link")).unwrap();
+
+        let resolved = resolve_problem_file(&problem_dir, "checker.cpp").await.unwrap();
+        assert!(resolved.ends_with("problem/checker.cpp"));
+        for source in ["../outside", "/bin/sh", "link", "missing.cpp"] {
+            assert!(resolve_problem_file(&problem_dir, source).await.is_err(), "{} was accepted", source);
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,467 @@
+// 컴파일 단계와 컴파일 캐시
+//
+// 같은 언어, 같은 컴파일러, 같은 소스의 컴파일 결과는 재채점이나 중복 제출에서 다시 쓴다.
+// 캐시 항목은 `<COMPILE_CACHE_DIR>/<키>/`에 `status`, `message.txt`, `build/`로 저장된다.
+// 컴파일러가 직접 낸 결과(성공, 또는 진단을 남기고 실패)만 저장하고, 시간 초과처럼 다시 하면 달라질 수 있는 실패는 저장하지 않는다.
+// `status`의 수정 시각을 마지막 사용 시각으로 삼아, 오래 쓰지 않은 항목부터 크기 제한에 맞춰 지운다.
+// 캐시한 실행 파일을 그대로 실행하므로 캐시 디렉토리는 채점 서버 사용자만 쓸 수 있어야 한다 (`ensure_private_dir`).
+use std::fs::Permissions;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::language::{self, Language};
+use super::{compilation_error_result, CompileOutcome, JudgeBackend, JudgeRequest, JudgeResult, BUILD_DIR};
+
+/// 캐시 디렉토리 기본 경로. 실행 위치 기준이며 `COMPILE_CACHE_DIR`로 변경 가능
+pub const DEFAULT_CACHE_DIR: &str = "data/compile_cache";
+
+/// 캐시 최대 크기 기본값 (`COMPILE_CACHE_MAX_MB`로 변경 가능)
+pub const DEFAULT_CACHE_MAX_MB: u64 = 1024;
+
+/// 이 기간 동안 쓰지 않은 항목은 지운다 (`COMPILE_CACHE_MAX_AGE_DAYS`로 변경 가능)
+pub const DEFAULT_CACHE_MAX_AGE_DAYS: u64 = 7;
+
+/// 캐시 정리는 이 간격보다 자주 하지 않는다
+const EVICTION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 이보다 오래된 `.tmp_` 디렉토리는 중간에 멈춘 저장으로 보고 지운다
+const STALE_STAGING_AGE: Duration = Duration::from_secs(60 * 60);
+
+static CACHE: Lazy<CompileCache> = Lazy::new(|| {
+    let root = std::env::var("COMPILE_CACHE_DIR").unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string());
+    let env_u64 = |name: &str, default: u64| {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    CompileCache::new(
+        PathBuf::from(root),
+        env_u64("COMPILE_CACHE_MAX_MB", DEFAULT_CACHE_MAX_MB) * 1024 * 1024,
+        Duration::from_secs(env_u64("COMPILE_CACHE_MAX_AGE_DAYS", DEFAULT_CACHE_MAX_AGE_DAYS) * 24 * 60 * 60),
+    )
+});
+
+/// 디렉토리를 만들고 현재 사용자만 접근할 수 있게(0700) 한다.
+///
+/// 다른 사용자가 미리 만들어 둔 디렉토리는 안에 든 실행 파일을 믿을 수 없으므로 거부한다.
+pub(crate) async fn ensure_private_dir(path: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(path).await?;
+    let metadata = fs::symlink_metadata(path).await?;
+    // SAFETY: geteuid는 실패하지 않는다
+    let uid = unsafe { libc::geteuid() };
+    if !metadata.is_dir() || metadata.uid() != uid {
+        anyhow::bail!("{}: 다른 사용자의 디렉토리는 캐시로 쓸 수 없습니다", path.display());
+    }
+    if metadata.mode() & 0o777 != 0o700 {
+        fs::set_permissions(path, Permissions::from_mode(0o700)).await?;
+    }
+    Ok(())
+}
+
+/// 컴파일러가 스스로 종료한 코드인지 (`timeout`의 124, 명령을 실행하지 못한 126/127, 시그널로 끝난 128 이상이 아님)
+pub(crate) fn is_compiler_exit(exit_code: i32) -> bool {
+    !matches!(exit_code, 124 | 126 | 127) && (0..128).contains(&exit_code)
+}
+
+/// 작업 디렉토리의 `build/`에 실행할 파일을 준비한다.
+///
+/// 컴파일이 필요 없는 언어는 소스만 복사한다. 컴파일에 실패하면 컴파일 에러 결과를 돌려주고,
+/// 이때는 테스트케이스를 실행하지 않는다.
+pub(crate) async fn prepare_build(
+    backend: &dyn JudgeBackend,
+    request: &JudgeRequest,
+    workspace: &Path,
+) -> anyhow::Result<Option<JudgeResult>> {
+    prepare_build_with(&CACHE, backend, request, workspace).await
+}
+
+async fn prepare_build_with(
+    cache: &'static CompileCache,
+    backend: &dyn JudgeBackend,
+    request: &JudgeRequest,
+    workspace: &Path,
+) -> anyhow::Result<Option<JudgeResult>> {
+    let language = language::lookup(&request.language)?;
+    let build_dir = workspace.join(BUILD_DIR);
+    fs::create_dir_all(&build_dir).await?;
+
+    let Some(command) = language.compile_command(request.memory_limit) else {
+        fs::copy(workspace.join(&language.source_file), build_dir.join(&language.source_file)).await?;
+        return Ok(None);
+    };
+
+    let version = backend.toolchain_version(language).await?;
+    let key = cache_key(language, &command, &version, &request.source_code);
+
+    let outcome = match cache.load(&key, &build_dir).await {
+        Some(outcome) => outcome,
+        None => {
+            let outcome = backend.compile(request, workspace).await?;
+            if outcome.cacheable {
+                // 캐시 저장 실패는 채점 결과에 영향을 주지 않는다
+                if let Err(e) = cache.store(&key, &outcome, &build_dir).await {
+                    eprintln!("Failed to store compile cache {}: {:?}", key, e);
+                }
+                cache.evict_if_due();
+            }
+            outcome
+        }
+    };
+
+    if outcome.success {
+        Ok(None)
+    } else {
+        Ok(Some(compilation_error_result(&outcome.message)))
+    }
+}
+
+/// 언어, 컴파일 명령, 컴파일러 버전, 소스 코드의 SHA-256
+fn cache_key(language: &Language, command: &str, version: &str, source: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [language.id.as_str(), command, version, source] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+struct CompileCache {
+    root: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    last_eviction: Mutex<Option<Instant>>,
+    /// 처음 쓸 때 한 번 `ensure_private_dir`로 루트를 준비한다
+    root_ready: OnceCell<()>,
+}
+
+impl CompileCache {
+    fn new(root: PathBuf, max_bytes: u64, max_age: Duration) -> Self {
+        Self {
+            root,
+            max_bytes,
+            max_age,
+            last_eviction: Mutex::new(None),
+            root_ready: OnceCell::new(),
+        }
+    }
+
+    /// 권한을 확인한 캐시 루트
+    async fn root(&self) -> anyhow::Result<&Path> {
+        self.root_ready.get_or_try_init(|| ensure_private_dir(&self.root)).await?;
+        Ok(&self.root)
+    }
+
+    /// 캐시된 결과가 있으면 결과물을 `build_dir`로 복사하고 컴파일 결과를 돌려준다.
+    async fn load(&self, key: &str, build_dir: &Path) -> Option<CompileOutcome> {
+        let entry = self.root().await.ok()?.join(key);
+        let status = fs::read_to_string(entry.join("status")).await.ok()?;
+        let message = fs::read_to_string(entry.join("message.txt")).await.unwrap_or_default();
+
+        let success = status.trim() == "OK";
+        if success && copy_dir(entry.join(BUILD_DIR), build_dir.to_path_buf()).await.is_err() {
+            // 항목이 지워지는 중이면 다시 컴파일한다
+            return None;
+        }
+        // 마지막 사용 시각 갱신 (실패해도 오래된 항목으로 먼저 지워질 뿐이다)
+        if let Ok(file) = std::fs::File::options().append(true).open(entry.join("status")) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(CompileOutcome { success, message, cacheable: true })
+    }
+
+    /// 임시 디렉토리에 항목을 만든 뒤 이름을 바꿔, 읽는 쪽이 반쯤 쓰인 항목을 보지 않게 한다.
+    async fn store(&self, key: &str, outcome: &CompileOutcome, build_dir: &Path) -> anyhow::Result<()> {
+        let root = self.root().await?;
+        let entry = root.join(key);
+        if fs::try_exists(&entry).await? {
+            return Ok(());
+        }
+
+        let staging = root.join(format!(".tmp_{}", Uuid::new_v4()));
+        fs::create_dir_all(&staging).await?;
+        let result: anyhow::Result<()> = async {
+            if outcome.success {
+                copy_dir(build_dir.to_path_buf(), staging.join(BUILD_DIR)).await?;
+            }
+            fs::write(staging.join("message.txt"), &outcome.message).await?;
+            let status = if outcome.success { "OK" } else { "COMPILATION_ERROR" };
+            fs::write(staging.join("status"), status).await?;
+            fs::rename(&staging, &entry).await?;
+            Ok(())
+        }
+        .await;
+
+        if result.is_err() {
+            // 다른 채점이 먼저 같은 항목을 만든 경우도 여기에 해당한다
+            let _ = fs::remove_dir_all(&staging).await;
+        }
+        result
+    }
+
+    /// 마지막 정리 후 `EVICTION_INTERVAL`이 지났으면 백그라운드에서 캐시를 정리한다.
+    fn evict_if_due(&'static self) {
+        {
+            let mut last_eviction = self.last_eviction.lock().unwrap();
+            if last_eviction.is_some_and(|last| last.elapsed() < EVICTION_INTERVAL) {
+                return;
+            }
+            *last_eviction = Some(Instant::now());
+        }
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = self.evict() {
+                eprintln!("Failed to evict compile cache: {:?}", e);
+            }
+        });
+    }
+
+    /// `max_age` 동안 쓰지 않은 항목을 지우고, 남은 크기가 `max_bytes`를 넘으면 오래 쓰지 않은 항목부터 지운다.
+    fn evict(&self) -> anyhow::Result<()> {
+        let now = SystemTime::now();
+        let age = |time: SystemTime| now.duration_since(time).unwrap_or_default();
+
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let is_staging = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(".tmp_"));
+            if is_staging {
+                if path.metadata().and_then(|m| m.modified()).is_ok_and(|t| age(t) > STALE_STAGING_AGE) {
+                    let _ = std::fs::remove_dir_all(&path);
+                }
+                continue;
+            }
+
+            // 저장 중에 이름이 바뀐 항목은 status가 있으므로, 없으면 지워지는 중인 항목이다
+            let Ok(last_used) = path.join("status").metadata().and_then(|m| m.modified()) else {
+                continue;
+            };
+            if age(last_used) > self.max_age {
+                let _ = std::fs::remove_dir_all(&path);
+                continue;
+            }
+            let size: u64 = WalkDir::new(&path)
+                .into_iter()
+                .filter_map(|entry| entry.ok()?.metadata().ok())
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+                .sum();
+            entries.push((last_used, size, path));
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort_by_key(|(last_used, _, _)| *last_used);
+        for (_, size, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_dir_all(&path).is_ok() {
+                total -= size;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `from` 아래의 파일을 권한과 함께 `to`로 복사한다.
+async fn copy_dir(from: PathBuf, to: PathBuf) -> anyhow::Result<()> {
+    tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        for entry in WalkDir::new(&from) {
+            let entry = entry?;
+            let target = to.join(entry.path().strip_prefix(&from)?);
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&target)?;
+            } else if entry.file_type().is_file() {
+                std::fs::copy(entry.path(), &target)?;
+            }
+        }
+        Ok(())
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::judge::progress::ProgressSink;
+
+    fn temp_cache(max_bytes: u64) -> &'static CompileCache {
+        let root = std::env::temp_dir().join(format!("nekonic_compile_cache_test_{}", Uuid::new_v4()));
+        Box::leak(Box::new(CompileCache::new(root, max_bytes, Duration::from_secs(24 * 60 * 60))))
+    }
+
+    fn temp_workspace() -> PathBuf {
+        let workspace = std::env::temp_dir().join(format!("nekonic_compile_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(workspace.join(BUILD_DIR)).unwrap();
+        workspace
+    }
+
+    /// `compile`이 몇 번 불렸는지 세고, 정해 둔 결과를 돌려주는 백엔드
+    struct CountingBackend {
+        outcome: CompileOutcome,
+        compiles: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl JudgeBackend for CountingBackend {
+        async fn toolchain_version(&self, _language: &Language) -> anyhow::Result<String> {
+            Ok("test".to_string())
+        }
+
+        async fn compile(&self, _request: &JudgeRequest, workspace: &Path) -> anyhow::Result<CompileOutcome> {
+            self.compiles.fetch_add(1, Ordering::SeqCst);
+            if self.outcome.success {
+                std::fs::write(workspace.join(BUILD_DIR).join("main"), "binary")?;
+            }
+            Ok(self.outcome.clone())
+        }
+
+        async fn judge(
+            &self,
+            _request: &JudgeRequest,
+            _workspace: &Path,
+            _progress: &dyn ProgressSink,
+        ) -> anyhow::Result<JudgeResult> {
+            unreachable!("compile cache tests do not judge")
+        }
+
+        async fn compile_problem_program(
+            &self,
+            _workspace: &Path,
+            _name: &str,
+            _extra_flags: &[&str],
+        ) -> anyhow::Result<CompileOutcome> {
+            unreachable!("compile cache tests do not build checkers")
+        }
+    }
+
+    /// 같은 소스를 두 번 빌드했을 때 컴파일러가 불린 횟수
+    async fn compiles_for_two_builds(outcome: CompileOutcome) -> usize {
+        language::init_from_env().unwrap();
+        let cache = temp_cache(u64::MAX);
+        let backend = CountingBackend { outcome, compiles: AtomicUsize::new(0) };
+        let request = JudgeRequest::new(1, "cpp".to_string(), "int main() {}".to_string(), 1001);
+        for _ in 0..2 {
+            let workspace = temp_workspace();
+            prepare_build_with(cache, &backend, &request, &workspace).await.unwrap();
+            std::fs::remove_dir_all(&workspace).unwrap();
+        }
+        std::fs::remove_dir_all(&cache.root).unwrap();
+        backend.compiles.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn cache_key_covers_every_part() {
+        language::init_from_env().unwrap();
+        let cpp = language::lookup("cpp").unwrap();
+        let c = language::lookup("c").unwrap();
+        let key = cache_key(cpp, "g++ main.cpp", "13.2", "int main() {}");
+
+        assert_eq!(key, cache_key(cpp, "g++ main.cpp", "13.2", "int main() {}"));
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, cache_key(c, "g++ main.cpp", "13.2", "int main() {}"));
+        assert_ne!(key, cache_key(cpp, "g++ -O2 main.cpp", "13.2", "int main() {}"));
+        assert_ne!(key, cache_key(cpp, "g++ main.cpp", "14.1", "int main() {}"));
+        assert_ne!(key, cache_key(cpp, "g++ main.cpp", "13.2", "int main() { return 0; }"));
+        // 구분자 덕분에 경계가 달라지면 키도 달라진다
+        assert_ne!(cache_key(cpp, "ab", "c", ""), cache_key(cpp, "a", "bc", ""));
+    }
+
+    #[test]
+    fn only_compiler_exits_are_compiler_verdicts() {
+        for code in [0, 1, 2] {
+            assert!(is_compiler_exit(code), "{} should be cacheable", code);
+        }
+        // timeout, 실행 불가, SIGKILL/SIGSEGV로 끝난 경우, 시그널로 끝나 코드가 없는 경우
+        for code in [124, 126, 127, 137, 139, -1] {
+            assert!(!is_compiler_exit(code), "{} should not be cacheable", code);
+        }
+    }
+
+    #[tokio::test]
+    async fn store_and_load_round_trip() {
+        let cache = temp_cache(u64::MAX);
+        let build_dir = temp_workspace().join(BUILD_DIR);
+        std::fs::write(build_dir.join("main"), "binary").unwrap();
+
+        let success = CompileOutcome { success: true, message: "warning".to_string(), cacheable: true };
+        cache.store("ok", &success, &build_dir).await.unwrap();
+        let failure = CompileOutcome { success: false, message: "error".to_string(), cacheable: true };
+        cache.store("error", &failure, &build_dir).await.unwrap();
+
+        let restored = temp_workspace().join(BUILD_DIR);
+        let loaded = cache.load("ok", &restored).await.unwrap();
+        assert!(loaded.success);
+        assert_eq!(loaded.message, "warning");
+        assert_eq!(std::fs::read_to_string(restored.join("main")).unwrap(), "binary");
+
+        let loaded = cache.load("error", &restored).await.unwrap();
+        assert!(!loaded.success);
+        assert_eq!(loaded.message, "error");
+        assert!(cache.load("missing", &restored).await.is_none());
+
+        let mode = std::fs::metadata(&cache.root).unwrap().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        std::fs::remove_dir_all(&cache.root).unwrap();
+        std::fs::remove_dir_all(build_dir.parent().unwrap()).unwrap();
+        std::fs::remove_dir_all(restored.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn compiler_verdicts_are_reused() {
+        let outcome = CompileOutcome { success: false, message: "error".to_string(), cacheable: true };
+        assert_eq!(compiles_for_two_builds(outcome).await, 1);
+        let outcome = CompileOutcome { success: true, message: String::new(), cacheable: true };
+        assert_eq!(compiles_for_two_builds(outcome).await, 1);
+    }
+
+    #[tokio::test]
+    async fn timed_out_or_killed_compiles_are_not_cached() {
+        // native/Docker 백엔드는 종료 코드 124(timeout)나 137(SIGKILL)이면 cacheable을 끈다
+        let outcome = CompileOutcome { success: false, message: String::new(), cacheable: false };
+        assert_eq!(compiles_for_two_builds(outcome).await, 2);
+    }
+
+    #[tokio::test]
+    async fn eviction_removes_least_recently_used_entries_first() {
+        let build_dir = temp_workspace().join(BUILD_DIR);
+        std::fs::write(build_dir.join("main"), vec![0u8; 1000]).unwrap();
+        let outcome = CompileOutcome { success: true, message: String::new(), cacheable: true };
+
+        // 항목 하나가 1000바이트 남짓이므로 두 개까지만 남는다
+        let cache = temp_cache(2500);
+        let now = SystemTime::now();
+        for (key, minutes_ago) in [("old", 30), ("newest", 1), ("middle", 10)] {
+            cache.store(key, &outcome, &build_dir).await.unwrap();
+            let status = std::fs::File::options().append(true).open(cache.root.join(key).join("status")).unwrap();
+            status.set_modified(now - Duration::from_secs(minutes_ago * 60)).unwrap();
+        }
+        // 오래된 항목도 다시 쓰면 최근 사용으로 바뀐다
+        cache.load("old", &build_dir).await.unwrap();
+
+        cache.evict().unwrap();
+        assert!(cache.root.join("old").exists());
+        assert!(cache.root.join("newest").exists());
+        assert!(!cache.root.join("middle").exists());
+
+        std::fs::remove_dir_all(&cache.root).unwrap();
+        std::fs::remove_dir_all(build_dir.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+// 컴파일러 출력을 에러/경고 단위로 나누는 파서
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::CompileErrorData;
+
+/// 제출 하나에 저장하는 최대 진단 수
+const MAX_DIAGNOSTICS: usize = 100;
+
+/// languages.toml의 `diagnostics` 항목. 컴파일러 출력 형식을 지정한다.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticFormat {
+    /// gcc / g++ (`Main.cpp:4:3: error: ...`)
+    Gcc,
+    /// javac (`Main.java:4: error: ...` 다음 줄의 `^`로 열 위치 표시)
+    Javac,
+    /// Python traceback 및 `py_compile` (`File "Main.py", line 2` ... `SyntaxError: ...`)
+    Python,
+}
+
+static GCC_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?:(?P<column>\d+):)? (?P<severity>fatal error|error|warning|note): (?P<message>.*)$")
+        .unwrap()
+});
+static GCC_OPTION: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*\[(?P<option>-W[^\]]+)\]$").unwrap());
+
+static JAVAC_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?P<file>[^:\s][^:]*):(?P<line>\d+): (?P<severity>error|warning): (?P<message>.*)$").unwrap()
+});
+static JAVAC_LINT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(?P<lint>[\w-]+)\]\s*").unwrap());
+static JAVAC_SUMMARY: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d+ (errors?|warnings?)$").unwrap());
+
+static PYTHON_FRAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*File "(?P<file>[^"]+)", line (?P<line>\d+)"#).unwrap());
+static PYTHON_EXCEPTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:Sorry: )?(?P<kind>[A-Za-z_][\w.]*)(?::\s?(?P<message>.*))?$").unwrap());
+
+static CARET: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*\^[\^~]*\s*$").unwrap());
+
+/// 컴파일러 출력을 진단 목록으로 나눈다.
+///
+/// 위치를 알 수 있는 에러가 하나도 없으면(링커 에러, 형식을 모르는 컴파일러 등)
+/// 전체 출력을 위치 없는 에러 하나로 남긴다.
+pub fn parse(format: Option<DiagnosticFormat>, output: &str) -> Vec<CompileErrorData> {
+    let mut diagnostics = match format {
+        Some(DiagnosticFormat::Gcc) => parse_gcc(output),
+        Some(DiagnosticFormat::Javac) => parse_javac(output),
+        Some(DiagnosticFormat::Python) => parse_python(output),
+        None => Vec::new(),
+    };
+    diagnostics.truncate(MAX_DIAGNOSTICS);
+
+    if !diagnostics.iter().any(|d| d.severity == "error") {
+        diagnostics.push(CompileErrorData {
+            file_name: None,
+            line_number: None,
+            column_number: None,
+            severity: "error".to_string(),
+            error_type: Some("COMPILATION_ERROR".to_string()),
+            error_message: output.to_string(),
+        });
+    }
+    diagnostics
+}
+
+fn parse_gcc(output: &str) -> Vec<CompileErrorData> {
+    let mut diagnostics: Vec<CompileErrorData> = Vec::new();
+    for line in output.lines() {
+        let Some(caps) = GCC_HEADER.captures(line) else {
+            // 소스 코드 인용(`    4 |   x = 1;`)과 "In function ..." 같은 문맥 줄은 건너뛴다
+            continue;
+        };
+
+        let severity = &caps["severity"];
+        if severity == "note" {
+            // note는 바로 앞 진단의 부연 설명이다
+            if let Some(last) = diagnostics.last_mut() {
+                last.error_message.push_str("\nnote: ");
+                last.error_message.push_str(&caps["message"]);
+            }
+            continue;
+        }
+
+        let mut message = caps["message"].to_string();
+        let mut error_type = None;
+        if let Some(option) = GCC_OPTION.captures(&message) {
+            error_type = Some(option["option"].to_string());
+            let end = option.get(0).unwrap().start();
+            message.truncate(end);
+        }
+
+        diagnostics.push(CompileErrorData {
+            file_name: Some(caps["file"].to_string()),
+            line_number: caps["line"].parse().ok(),
+            column_number: caps.name("column").and_then(|c| c.as_str().parse().ok()),
+            severity: if severity == "warning" { "warning" } else { "error" }.to_string(),
+            error_type,
+            error_message: message,
+        });
+    }
+    diagnostics
+}
+
+fn parse_javac(output: &str) -> Vec<CompileErrorData> {
+    let mut diagnostics: Vec<CompileErrorData> = Vec::new();
+    // 헤더 다음 줄은 소스 인용, 그 다음 `^` 줄이 열 위치, 이후 들여쓴 줄은 부연 설명이다
+    let mut after_caret = false;
+    for line in output.lines() {
+        if let Some(caps) = JAVAC_HEADER.captures(line) {
+            let mut message = caps["message"].to_string();
+            let mut error_type = None;
+            if let Some(lint) = JAVAC_LINT.captures(&message) {
+                error_type = Some(lint["lint"].to_string());
+                let end = lint.get(0).unwrap().end();
+                message.drain(..end);
+            }
+
+            diagnostics.push(CompileErrorData {
+                file_name: Some(caps["file"].to_string()),
+                line_number: caps["line"].parse().ok(),
+                column_number: None,
+                severity: caps["severity"].to_string(),
+                error_type,
+                error_message: message,
+            });
+            after_caret = false;
+            continue;
+        }
+
+        let Some(current) = diagnostics.last_mut() else {
+            continue;
+        };
+        if JAVAC_SUMMARY.is_match(line) || line.starts_with("Note: ") {
+            after_caret = false;
+        } else if !after_caret && CARET.is_match(line) {
+            let column = line.chars().position(|c| c == '^').unwrap_or(0);
+            current.column_number = Some(column as i32 + 1);
+            after_caret = true;
+        } else if after_caret && line.starts_with(char::is_whitespace) {
+            current.error_message.push('\n');
+            current.error_message.push_str(line.trim());
+        }
+    }
+    diagnostics
+}
+
+/// traceback은 예외 하나만 알려주므로 마지막 프레임의 위치로 진단 하나를 만든다.
+/// 라이브러리 내부에서 발생한 예외는 제출 파일(상대 경로)의 마지막 프레임을 가리킨다.
+fn parse_python(output: &str) -> Vec<CompileErrorData> {
+    let mut location: Option<(String, i32, Option<i32>)> = None;
+    let mut submission_location = None;
+    let mut exception = None;
+
+    let mut lines = output.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(caps) = PYTHON_FRAME.captures(line) {
+            let file = caps["file"].to_string();
+            let line_number = caps["line"].parse().unwrap_or(0);
+
+            // SyntaxError는 소스 인용 다음 줄에 `^`로 열 위치를 표시한다 (인용은 4칸 들여쓰기)
+            let mut column = None;
+            if lines.peek().is_some_and(|l| l.starts_with("    ")) {
+                lines.next();
+                if let Some(caret) = lines.peek().filter(|l| CARET.is_match(l)) {
+                    let position = caret.chars().position(|c| c == '^').unwrap_or(0);
+                    column = Some(position.saturating_sub(4) as i32 + 1);
+                    lines.next();
+                }
+            }
+
+            if !file.starts_with('/') && !file.starts_with('<') {
+                submission_location = Some((file.clone(), line_number, column));
+            }
+            location = Some((file, line_number, column));
+            continue;
+        }
+
+        if location.is_some()
+            && !line.starts_with(char::is_whitespace)
+            && let Some(caps) = PYTHON_EXCEPTION.captures(line)
+        {
+            let kind = caps["kind"].to_string();
+            let message = caps.name("message").map(|m| m.as_str().to_string()).unwrap_or_else(|| kind.clone());
+            exception = Some((kind, message));
+        }
+    }
+
+    let Some((kind, message)) = exception else {
+        return Vec::new();
+    };
+    let (file, line_number, column) = submission_location.or(location).unwrap();
+    vec![CompileErrorData {
+        file_name: Some(file),
+        line_number: Some(line_number),
+        column_number: column,
+        severity: "error".to_string(),
+        error_type: Some(kind),
+        error_message: message,
+    }]
+}
@@ -0,0 +1,397 @@
+use bollard::Docker;
+use bollard::container::{
+    CreateContainerOptions, Config, LogsOptions, StartContainerOptions,
+    RemoveContainerOptions
+};
+use bollard::models::{HostConfig, Mount, MountTypeEnum, ResourcesUlimits};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::checker::{self, CheckerSpec, CHECKER_TIME_LIMIT_SECS};
+use super::language::{self, Language};
+use super::progress::ProgressSink;
+use super::{
+    count_testcases, interactor, parse_compile_output, parse_judge_result, progress_from_line,
+    push_bounded, CompileOutcome, JudgeBackend, JudgeRequest, JudgeResult, COMPILE_MEMORY_LIMIT_MB,
+    COMPILE_TIME_BUDGET_SECS, LOG_LIMIT_BYTES,
+};
+
+/// 컴파일러와 채점 스크립트를 위해 컨테이너 메모리 제한에 더해주는 여유분
+const CONTAINER_MEMORY_HEADROOM_MB: i64 = 256;
+/// 컨테이너 생성/시작과 judge.sh 준비에 허용하는 시간
+const CONTAINER_STARTUP_SECS: u64 = 10;
+/// 컨테이너 안의 최대 프로세스(스레드 포함) 수. fork 폭탄 방지
+const CONTAINER_PIDS_LIMIT: i64 = 128;
+/// 컴파일/실행 디렉토리(/sandbox)와 /tmp tmpfs 크기
+const SANDBOX_TMPFS_SIZE_MB: u32 = 256;
+/// 제출 프로그램을 실행하는 사용자 (nobody)
+const SANDBOX_UID: u32 = 65534;
+/// 열 수 있는 파일 수
+const OPEN_FILES_LIMIT: i64 = 256;
+
+/// 로컬 Docker 데몬에서 언어별 judge.sh 이미지로 채점
+pub struct DockerBackend;
+
+#[async_trait::async_trait]
+impl JudgeBackend for DockerBackend {
+    /// 이미지가 다시 빌드되면 컴파일러가 바뀔 수 있으므로 이미지 ID를 버전으로 쓴다
+    async fn toolchain_version(&self, language: &Language) -> anyhow::Result<String> {
+        let docker = Docker::connect_with_local_defaults()?;
+        let image = docker.inspect_image(&language.image).await?;
+        Ok(image.id.unwrap_or_default())
+    }
+
+    async fn compile(&self, request: &JudgeRequest, workspace: &Path) -> anyhow::Result<CompileOutcome> {
+        let language = language::lookup(&request.language)?;
+        let command = language.compile_command(request.memory_limit).unwrap_or_default();
+        compile_with_bollard(language, &command, workspace).await
+    }
+
+    async fn judge(
+        &self,
+        request: &JudgeRequest,
+        workspace: &Path,
+        progress: &dyn ProgressSink,
+    ) -> anyhow::Result<JudgeResult> {
+        run_docker_judge_with_bollard(self, request, workspace, progress).await
+    }
+
+    /// 체커/인터랙터도 제출 프로그램처럼 C++ 이미지의 컴파일 전용 컨테이너에서 컴파일한다
+    async fn compile_problem_program(
+        &self,
+        workspace: &Path,
+        name: &str,
+        extra_flags: &[&str],
+    ) -> anyhow::Result<CompileOutcome> {
+        let language = language::lookup(checker::PROGRAM_LANGUAGE)?;
+        let command = checker::program_compile_command(name, extra_flags);
+        compile_with_bollard(language, &command, workspace).await
+    }
+}
+
+fn workspace_mount(workspace: &Path) -> Mount {
+    Mount {
+        target: Some("/workspace".to_string()),
+        source: Some(workspace.to_string_lossy().to_string()),
+        typ: Some(MountTypeEnum::BIND),
+        read_only: Some(false),
+        ..Default::default()
+    }
+}
+
+/// 컴파일 전용 컨테이너. 테스트케이스 없이 컴파일 제한으로 실행하고 결과물을 `build/`에 남긴다.
+async fn compile_with_bollard(language: &Language, command: &str, workspace: &Path) -> anyhow::Result<CompileOutcome> {
+    let env_vars = vec![
+        "JUDGE_PHASE=compile".to_string(),
+        format!("COMPILE_CMD={}", command),
+        format!("COMPILE_TIME_LIMIT_SECS={}", COMPILE_TIME_BUDGET_SECS),
+        format!("SANDBOX_UID={}", SANDBOX_UID),
+        "HOME=/sandbox".to_string(),
+    ];
+
+    // judge.sh의 timeout보다 조금 더 기다린다
+    let wait_budget = Duration::from_secs(COMPILE_TIME_BUDGET_SECS + CONTAINER_STARTUP_SECS);
+    let output = run_container(
+        language,
+        vec![workspace_mount(workspace)],
+        env_vars,
+        COMPILE_MEMORY_LIMIT_MB as i64,
+        wait_budget,
+        |_| {},
+    )
+    .await?;
+
+    parse_compile_output(&output.stdout, &output.stderr, output.completed)
+}
+
+/// `build/`의 실행 파일로 테스트케이스를 채점하는 컨테이너
+async fn run_docker_judge_with_bollard(
+    backend: &DockerBackend,
+    request: &JudgeRequest,
+    workspace: &Path,
+    progress: &dyn ProgressSink,
+) -> anyhow::Result<JudgeResult> {
+    let language = language::lookup(&request.language)?;
+
+    // 테스트케이스 경로 계산
+    let testcase_path = request.testcase_path();
+
+    let testcase_path_str = testcase_path.to_string_lossy().to_string();
+    let testcase_count = count_testcases(&testcase_path).await;
+
+    let mut mounts = vec![workspace_mount(workspace)];
+
+    // 테스트케이스 디렉토리가 존재하는 경우에만 마운트
+    if testcase_path.exists() {
+        mounts.push(Mount {
+            target: Some("/testcases".to_string()),
+            source: Some(testcase_path_str),
+            typ: Some(MountTypeEnum::BIND),
+            read_only: Some(true),
+            ..Default::default()
+        });
+    }
+
+    // 인터랙터와 커스텀 체커는 컨테이너에서 컴파일해 두고, 채점 컨테이너 안에서 권한 없는 사용자로 실행한다
+    let interactor_binary = interactor::prepare(backend, request).await?;
+    let checker_binary = match interactor_binary {
+        Some(_) => None,
+        None => checker::prepare(backend, request).await?,
+    };
+    for (binary, target) in [(&interactor_binary, "/interactor/interactor"), (&checker_binary, "/checker/checker")] {
+        if let Some(binary) = binary {
+            mounts.push(Mount {
+                target: Some(target.to_string()),
+                source: Some(binary.canonicalize()?.to_string_lossy().to_string()),
+                typ: Some(MountTypeEnum::BIND),
+                read_only: Some(true),
+                ..Default::default()
+            });
+        }
+    }
+
+    let mut env_vars = vec![
+        "JUDGE_PHASE=run".to_string(),
+        format!("TIME_LIMIT_MS={}", request.time_limit),
+        format!("MEMORY_LIMIT_MB={}", request.memory_limit),
+        format!("OUTPUT_LIMIT_MB={}", request.output_limit),
+        format!("SANDBOX_UID={}", SANDBOX_UID),
+        "HOME=/sandbox".to_string(),
+        format!("RUN_CMD={}", language.run_command(request.memory_limit)),
+    ];
+    if interactor_binary.is_some() {
+        env_vars.push("INTERACTIVE=1".to_string());
+    }
+    let mut check_budget_ms = 0;
+    if checker_binary.is_some() {
+        env_vars.push("CHECKER=1".to_string());
+        env_vars.push(format!("CHECKER_TIME_LIMIT_SECS={}", CHECKER_TIME_LIMIT_SECS));
+        if matches!(request.checker, CheckerSpec::Custom { testlib: true, .. }) {
+            env_vars.push("CHECKER_TESTLIB=1".to_string());
+        }
+        check_budget_ms = CHECKER_TIME_LIMIT_SECS * 1000;
+    }
+
+    // 테스트케이스별 실행 시간(시간 제한의 2배 + 1초, 체커 시간 포함)
+    let run_budget_ms = (request.time_limit as u64 * 2 + 1000 + check_budget_ms) * testcase_count as u64;
+    let wait_budget = Duration::from_secs(CONTAINER_STARTUP_SECS) + Duration::from_millis(run_budget_ms);
+
+    // judge.sh 이벤트마다 진행 상황을 알린다
+    let total = testcase_count as i32;
+    let output = run_container(
+        language,
+        mounts,
+        env_vars,
+        request.memory_limit as i64,
+        wait_budget,
+        |line| {
+            if let Some(event) = progress_from_line(line, total) {
+                progress.report(event);
+            }
+        },
+    )
+    .await?;
+
+    // 결과 분석
+    parse_judge_result(backend, &output.stdout, &output.stderr, output.completed, request, workspace).await
+}
+
+struct ContainerOutput {
+    stdout: String,
+    stderr: String,
+    /// 제한 시간 안에 끝났는지
+    completed: bool,
+}
+
+/// 채점 이미지로 judge.sh를 실행하고, 컨테이너가 끝날 때까지 로그를 따라가며 stdout 줄마다 `on_line`을 부른다.
+async fn run_container(
+    language: &Language,
+    mounts: Vec<Mount>,
+    env_vars: Vec<String>,
+    memory_limit_mb: i64,
+    wait_budget: Duration,
+    on_line: impl Fn(&str),
+) -> anyhow::Result<ContainerOutput> {
+    let docker = Docker::connect_with_local_defaults()?;
+
+    // 컨테이너 생성 옵션
+    let container_name = format!("judge_{}", Uuid::new_v4());
+
+    // 컨테이너 전체 메모리는 cgroup으로 제한하고, 테스트케이스별 초과 여부는 judge.sh가 판정
+    let container_memory = (memory_limit_mb + CONTAINER_MEMORY_HEADROOM_MB) * 1024 * 1024;
+    let host_config = HostConfig {
+        memory: Some(container_memory),
+        memory_swap: Some(container_memory), // 스왑 사용 금지
+        cpu_quota: Some(100000), // 1 CPU core
+        network_mode: Some("none".to_string()),
+        mounts: Some(mounts),
+        ..sandbox_profile()
+    };
+
+    let config = Config {
+        image: Some(language.image.as_str()),
+        working_dir: Some("/workspace"),
+        env: Some(env_vars.iter().map(|v| v.as_str()).collect()),
+        host_config: Some(host_config),
+        ..Default::default()
+    };
+
+    let options = CreateContainerOptions {
+        name: container_name.clone(),
+        platform: None,
+    };
+
+    // 컨테이너 생성 및 실행
+    let container = docker.create_container(Some(options), config).await?;
+    let output = follow_container(&docker, &container.id, wait_budget, on_line).await;
+
+    // 시작이나 로그 수집에 실패해도 컨테이너(tmpfs, 바인드 마운트 포함)는 지운다
+    let removed = docker.remove_container(
+        &container.id,
+        Some(RemoveContainerOptions {
+            force: true,
+            ..Default::default()
+        })
+    ).await;
+
+    let output = output?;
+    removed?;
+    Ok(output)
+}
+
+/// 컨테이너를 시작하고 끝나거나 `wait_budget`이 지날 때까지 로그를 모은다.
+async fn follow_container(
+    docker: &Docker,
+    container_id: &str,
+    wait_budget: Duration,
+    on_line: impl Fn(&str),
+) -> anyhow::Result<ContainerOutput> {
+    docker.start_container(container_id, None::<StartContainerOptions<String>>).await?;
+
+    let mut log_stream = docker.logs(
+        container_id,
+        Some(LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        }),
+    );
+
+    let mut stdout_output = String::new();
+    let mut stderr_output = String::new();
+
+    use futures_util::stream::StreamExt;
+    let collect_logs = async {
+        // 아직 처리하지 않은 stdout 줄의 시작 위치
+        let mut line_start = 0;
+        while let Some(log_result) = log_stream.next().await {
+            match log_result {
+                Ok(bollard::container::LogOutput::StdOut { message }) => {
+                    push_bounded(&mut stdout_output, &message, LOG_LIMIT_BYTES);
+                    while let Some(end) = stdout_output[line_start..].find('\n') {
+                        on_line(&stdout_output[line_start..line_start + end]);
+                        line_start += end + 1;
+                    }
+                },
+                Ok(bollard::container::LogOutput::StdErr { message }) => {
+                    push_bounded(&mut stderr_output, &message, LOG_LIMIT_BYTES);
+                },
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    };
+    let wait_result = tokio::time::timeout(wait_budget, collect_logs).await;
+
+    Ok(ContainerOutput {
+        stdout: stdout_output,
+        stderr: stderr_output,
+        completed: wait_result.is_ok(),
+    })
+}
+
+/// 채점 컨테이너 보안 설정.
+///
+/// 루트 파일시스템은 읽기 전용이고, 쓸 수 있는 곳은 크기 제한이 있는 tmpfs와 결과를 남기는 /workspace 뿐이다.
+/// judge.sh는 소스 코드를 /sandbox로 복사한 뒤 권한을 낮춰(setpriv) 컴파일/실행하므로
+/// 제출 프로그램은 권한(capability) 없이 nobody 사용자로 실행되어 /workspace에도 쓸 수 없다.
+/// judge.sh에 남기는 권한은 사용자 전환(SETUID/SETGID)과 결과 파일 기록(DAC_OVERRIDE) 뿐이다.
+fn sandbox_profile() -> HostConfig {
+    let tmpfs_options = |mode: &str| {
+        format!(
+            "rw,nosuid,nodev,size={}m,uid={},gid={},mode={}",
+            SANDBOX_TMPFS_SIZE_MB, SANDBOX_UID, SANDBOX_UID, mode
+        )
+    };
+
+    HostConfig {
+        pids_limit: Some(CONTAINER_PIDS_LIMIT),
+        cap_drop: Some(vec!["ALL".to_string()]),
+        cap_add: Some(vec![
+            "SETUID".to_string(),
+            "SETGID".to_string(),
+            "DAC_OVERRIDE".to_string(),
+        ]),
+        security_opt: Some(vec!["no-new-privileges".to_string()]),
+        readonly_rootfs: Some(true),
+        tmpfs: Some(HashMap::from([
+            ("/sandbox".to_string(), tmpfs_options("0700")),
+            ("/tmp".to_string(), tmpfs_options("1777")),
+        ])),
+        ulimits: Some(vec![
+            ResourcesUlimits {
+                name: Some("nofile".to_string()),
+                soft: Some(OPEN_FILES_LIMIT),
+                hard: Some(OPEN_FILES_LIMIT),
+            },
+            // 코어 덤프로 디스크를 채우지 않도록
+            ResourcesUlimits {
+                name: Some("core".to_string()),
+                soft: Some(0),
+                hard: Some(0),
+            },
+        ]),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sandbox_profile_drops_privileges_and_bounds_resources() {
+        let profile = sandbox_profile();
+        assert_eq!(profile.readonly_rootfs, Some(true));
+        assert_eq!(profile.pids_limit, Some(CONTAINER_PIDS_LIMIT));
+        assert_eq!(profile.cap_drop.as_deref(), Some(&["ALL".to_string()][..]));
+        let mut cap_add = profile.cap_add.clone().unwrap();
+        cap_add.sort();
+        assert_eq!(cap_add, vec!["DAC_OVERRIDE", "SETGID", "SETUID"]);
+        assert_eq!(profile.security_opt.as_deref(), Some(&["no-new-privileges".to_string()][..]));
+
+        // 쓸 수 있는 tmpfs는 크기가 제한되고 실행 파일의 권한 상승을 막는다
+        let tmpfs = profile.tmpfs.as_ref().unwrap();
+        assert_eq!(tmpfs.len(), 2);
+        for (path, mode) in [("/sandbox", "0700"), ("/tmp", "1777")] {
+            let options = &tmpfs[path];
+            assert!(options.contains("nosuid") && options.contains("nodev"), "{}", options);
+            assert!(options.contains(&format!("size={}m", SANDBOX_TMPFS_SIZE_MB)), "{}", options);
+            assert!(options.ends_with(&format!("mode={}", mode)), "{}", options);
+        }
+
+        let ulimits = profile.ulimits.as_ref().unwrap();
+        let limit = |name: &str| {
+            let ulimit = ulimits.iter().find(|u| u.name.as_deref() == Some(name)).unwrap();
+            (ulimit.soft, ulimit.hard)
+        };
+        assert_eq!(limit("nofile"), (Some(OPEN_FILES_LIMIT), Some(OPEN_FILES_LIMIT)));
+        assert_eq!(limit("core"), (Some(0), Some(0)));
+
+        // 네트워크와 메모리는 실행마다 run_container가 채운다
+        assert!(profile.network_mode.is_none() && profile.memory.is_none());
+    }
+}
@@ -0,0 +1,169 @@
+// 테스트용 결정적 채점 백엔드 (컨테이너/컴파일러 없이 동작)
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use super::language::Language;
+use super::progress::{JudgeProgress, ProgressSink};
+use super::{
+    aggregate_reports, count_testcases, CompileOutcome, JudgeBackend, JudgeRequest, JudgeResult,
+    TestcaseReport,
+};
+
+/// 소스 코드에 들어있는 마커나 미리 정해둔 스크립트대로 결과를 돌려준다.
+///
+/// 마커는 `VERDICT: <결과> [tc=<번호>] [total=<개수>]` 형식이며 주석 안에 적으면 된다.
+/// (`// VERDICT: WRONG_ANSWER tc=3`) `tc`를 생략하면 1번 테스트케이스에 적용되고,
+/// 마커가 없는 테스트케이스는 모두 `ACCEPTED`로 처리한다.
+/// `VERDICT: COMPILATION_ERROR`는 컴파일 단계에서 컴파일 에러 결과를 만든다.
+pub struct FakeBackend {
+    script: Mutex<VecDeque<String>>,
+    /// 인스턴스마다 다른 컴파일러 버전을 써서 테스트끼리 컴파일 캐시를 공유하지 않는다
+    toolchain: String,
+}
+
+impl Default for FakeBackend {
+    fn default() -> Self {
+        Self {
+            script: Mutex::new(VecDeque::new()),
+            toolchain: format!("fake-{}", Uuid::new_v4()),
+        }
+    }
+}
+
+impl FakeBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 마커가 없는 제출에 순서대로 적용할 결과 목록 (예: `"WRONG_ANSWER tc=2"`)
+    pub fn with_script<I, S>(verdicts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            script: Mutex::new(verdicts.into_iter().map(Into::into).collect()),
+            ..Self::default()
+        }
+    }
+}
+
+/// 마커 한 줄을 해석한 결과
+struct VerdictMarker {
+    verdict: String,
+    testcase: Option<i32>,
+    total: Option<i32>,
+}
+
+fn parse_marker(spec: &str) -> Option<VerdictMarker> {
+    let mut parts = spec.split_whitespace();
+    let verdict = parts.next()?.to_string();
+    let mut marker = VerdictMarker { verdict, testcase: None, total: None };
+
+    for part in parts {
+        if let Some(value) = part.strip_prefix("tc=") {
+            marker.testcase = value.parse().ok();
+        } else if let Some(value) = part.strip_prefix("total=") {
+            marker.total = value.parse().ok();
+        }
+    }
+    Some(marker)
+}
+
+fn markers_from_source(source_code: &str) -> Vec<VerdictMarker> {
+    source_code
+        .lines()
+        .filter_map(|line| line.split_once("VERDICT:").map(|(_, spec)| spec))
+        .filter_map(parse_marker)
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl JudgeBackend for FakeBackend {
+    async fn toolchain_version(&self, _language: &Language) -> anyhow::Result<String> {
+        Ok(self.toolchain.clone())
+    }
+
+    /// 스크립트의 컴파일 에러는 여기서 소비한다. 나머지 결과는 `judge`가 사용한다.
+    async fn compile(&self, request: &JudgeRequest, _workspace: &Path) -> anyhow::Result<CompileOutcome> {
+        let markers = markers_from_source(&request.source_code);
+        let failed = if markers.is_empty() {
+            let mut script = self.script.lock().unwrap();
+            let scripted = script.front().and_then(|spec| parse_marker(spec));
+            let failed = scripted.is_some_and(|m| m.verdict == "COMPILATION_ERROR");
+            if failed {
+                script.pop_front();
+            }
+            failed
+        } else {
+            markers.iter().any(|m| m.verdict == "COMPILATION_ERROR")
+        };
+
+        Ok(CompileOutcome {
+            success: !failed,
+            message: if failed { "fake compilation error".to_string() } else { String::new() },
+            cacheable: true,
+        })
+    }
+
+    async fn compile_problem_program(
+        &self,
+        _workspace: &Path,
+        name: &str,
+        _extra_flags: &[&str],
+    ) -> anyhow::Result<CompileOutcome> {
+        Err(anyhow::anyhow!("테스트용 백엔드는 {}를 컴파일하지 않습니다", name))
+    }
+
+    async fn judge(
+        &self,
+        request: &JudgeRequest,
+        _workspace: &Path,
+        progress: &dyn ProgressSink,
+    ) -> anyhow::Result<JudgeResult> {
+        let mut markers = markers_from_source(&request.source_code);
+        if markers.is_empty() {
+            let scripted = self.script.lock().unwrap().pop_front();
+            markers.extend(scripted.as_deref().and_then(parse_marker));
+        }
+
+        let on_disk = count_testcases(&request.testcase_path()).await as i32;
+        let total = markers
+            .iter()
+            .flat_map(|m| [m.total.unwrap_or(0), m.testcase.unwrap_or(1)])
+            .chain([on_disk, 1])
+            .max()
+            .unwrap_or(1);
+
+        let reports = (1..=total)
+            .map(|number| {
+                progress.report(JudgeProgress::Running { testcase: number, total });
+                let verdict = markers
+                    .iter()
+                    .find(|m| m.testcase.unwrap_or(1) == number)
+                    .map(|m| m.verdict.clone())
+                    .unwrap_or_else(|| "ACCEPTED".to_string());
+                let exit_code = if verdict == "RUNTIME_ERROR" { 1 } else { 0 };
+
+                TestcaseReport {
+                    number,
+                    name: number.to_string(),
+                    verdict,
+                    message: None,
+                    wall_ms: 1,
+                    cpu_ms: 1,
+                    rss_kb: 1024,
+                    exit_code,
+                    signal: 0,
+                    runtime_error: None,
+                    output_diff: None,
+                }
+            })
+            .collect();
+
+        Ok(aggregate_reports(reports))
+    }
+}
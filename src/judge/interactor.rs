@@ -0,0 +1,147 @@
+// 인터랙티브 문제 지원 (제출 프로그램과 인터랙터를 파이프로 연결)
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use super::checker::prepare_problem_program;
+use super::{JudgeBackend, JudgeRequest, TestcaseReport};
+
+/// 결과에 남기는 인터랙터 메시지 최대 길이
+const INTERACTOR_MESSAGE_LIMIT: usize = 1024;
+
+/// 인터랙터 실행 파일 준비.
+///
+/// 인터랙터는 `interactor <input> <answer>` 로 실행되어 표준 입출력으로 제출 프로그램과 통신하고,
+/// 종료 코드로 결과를 알린다. (0: 정답, 1/2: 오답, 그 외: 채점 오류)
+/// 채점 컨테이너에서도 실행할 수 있도록 `.cpp` 소스는 정적 링크로 컴파일한다.
+pub(super) async fn prepare(backend: &dyn JudgeBackend, request: &JudgeRequest) -> anyhow::Result<Option<PathBuf>> {
+    let Some(source) = &request.interactor else {
+        return Ok(None);
+    };
+
+    let binary = prepare_problem_program(backend, &request.problem_path(), source, "interactor", &["-static"]).await?;
+    Ok(Some(binary))
+}
+
+/// 인터랙터가 stderr에 남긴 메시지(`output/<이름>.interactor`)를 테스트케이스 결과에 붙인다.
+pub(super) async fn attach_messages(reports: &mut [TestcaseReport], workspace: &Path) {
+    for report in reports.iter_mut().filter(|r| r.message.is_none()) {
+        let path = workspace.join("output").join(format!("{}.interactor", report.name));
+        let message = fs::read_to_string(&path).await.unwrap_or_default();
+        let message: String = message.trim().chars().take(INTERACTOR_MESSAGE_LIMIT).collect();
+        if !message.is_empty() {
+            report.message = Some(message);
+        }
+    }
+}
+
+/// 인터랙터 종료 코드와 제출 프로그램 실행 결과로 판정 (judge.sh와 같은 우선순위)
+pub(super) fn verdict(interactor_exit: i32, program_failed: bool) -> &'static str {
+    match interactor_exit {
+        // 인터랙터가 먼저 입력을 끊으면 제출 프로그램이 비정상 종료할 수 있으므로 오답을 우선한다
+        1 | 2 => "WRONG_ANSWER",
+        _ if program_failed => "RUNTIME_ERROR",
+        0 => "ACCEPTED",
+        _ => "SYSTEM_ERROR",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge::progress::NoProgress;
+    use crate::judge::{judge_submission, language, NativeBackend};
+
+    const INTERACTOR: &str = r#"
+#include <cstdio>
+int main(int argc, char** argv) {
+    FILE* input = fopen(argv[1], "r");
+    long long n = 0, answer = 0;
+    fscanf(input, "%lld", &n);
+    printf("%lld\n", n);
+    fflush(stdout);
+    if (scanf("%lld", &answer) != 1 || answer != n * 2) {
+        fprintf(stderr, "expected %lld, got %lld\n", n * 2, answer);
+        return 1;
+    }
+    return 0;
+}
+"#;
+
+    /// 3이 주어지면 틀린 답을, 4가 주어지면 답하지 않고 비정상 종료한다
+    const SOLUTION: &str = r#"
+#include <cstdio>
+int main() {
+    long long n;
+    scanf("%lld", &n);
+    if (n == 4) return 7;
+    printf("%lld\n", n == 3 ? n : n * 2);
+}
+"#;
+
+    fn report(name: &str) -> TestcaseReport {
+        TestcaseReport {
+            number: 1,
+            name: name.to_string(),
+            verdict: "WRONG_ANSWER".to_string(),
+            message: None,
+            wall_ms: 0,
+            cpu_ms: 0,
+            rss_kb: 0,
+            exit_code: 0,
+            signal: 0,
+            runtime_error: None,
+            output_diff: None,
+        }
+    }
+
+    #[test]
+    fn wrong_answers_take_priority_over_program_failures() {
+        assert_eq!(verdict(0, false), "ACCEPTED");
+        assert_eq!(verdict(1, true), "WRONG_ANSWER");
+        assert_eq!(verdict(2, false), "WRONG_ANSWER");
+        assert_eq!(verdict(0, true), "RUNTIME_ERROR");
+        assert_eq!(verdict(3, false), "SYSTEM_ERROR");
+    }
+
+    #[tokio::test]
+    async fn interactor_messages_are_trimmed_and_bounded() {
+        let workspace = std::env::temp_dir().join(format!("nekonic_interactor_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(workspace.join("output")).await.unwrap();
+        fs::write(workspace.join("output/1.interactor"), "  expected 4, got 3\n").await.unwrap();
+        fs::write(workspace.join("output/2.interactor"), "x".repeat(INTERACTOR_MESSAGE_LIMIT * 2)).await.unwrap();
+
+        let mut reports = vec![report("1"), report("2"), report("3")];
+        reports[2].message = Some("checker".to_string());
+        attach_messages(&mut reports, &workspace).await;
+        assert_eq!(reports[0].message.as_deref(), Some("expected 4, got 3"));
+        assert_eq!(reports[1].message.as_ref().unwrap().len(), INTERACTOR_MESSAGE_LIMIT);
+        assert_eq!(reports[2].message.as_deref(), Some("checker"));
+
+        fs::remove_dir_all(&workspace).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn interactive_problems_are_judged_through_the_interactor() {
+        language::init_from_env().unwrap();
+        let problem = std::env::temp_dir().join(format!("nekonic_interactive_problem_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(problem.join("testcases")).await.unwrap();
+        fs::write(problem.join("interactor.cpp"), INTERACTOR).await.unwrap();
+        for (name, n) in [("1", 5), ("2", 3), ("3", 4)] {
+            fs::write(problem.join("testcases").join(format!("{}.in", name)), n.to_string()).await.unwrap();
+        }
+
+        let mut request = JudgeRequest::new(1, "cpp".to_string(), SOLUTION.to_string(), 0);
+        request.time_limit = 1000;
+        request.interactor = Some("interactor.cpp".to_string());
+        request.problem_root = Some(problem.clone());
+        let result = judge_submission(&NativeBackend::new().unwrap(), request, &NoProgress).await.unwrap();
+
+        let verdicts: Vec<&str> = result.testcase_results.iter().map(|t| t.status.as_str()).collect();
+        assert_eq!(verdicts, vec!["ACCEPTED", "WRONG_ANSWER", "WRONG_ANSWER"]);
+        assert_eq!(result.testcase_results[1].error_message.as_deref(), Some("expected 6, got 3"));
+        assert_eq!(result.status, "WRONG_ANSWER");
+
+        fs::remove_dir_all(&problem).await.unwrap();
+    }
+}
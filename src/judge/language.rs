@@ -0,0 +1,207 @@
+// 채점 언어 레지스트리 (languages.toml)
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::diagnostics::DiagnosticFormat;
+
+/// 레지스트리 설정 파일 기본 경로 (`LANGUAGES_CONFIG`로 변경 가능)
+pub const DEFAULT_CONFIG_PATH: &str = "languages.toml";
+
+static REGISTRY: OnceLock<LanguageRegistry> = OnceLock::new();
+
+/// 채점 언어 하나의 설정.
+///
+/// 명령은 작업 디렉토리(`/workspace`)에서 셸로 실행되며, `{memory_limit_mb}`는 문제의 메모리 제한으로 치환된다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Language {
+    /// 제출에 저장되는 언어 ID (예: `cpp`)
+    pub id: String,
+    /// 제출 폼에 보여줄 이름
+    pub name: String,
+    /// 소스 코드를 저장할 파일 이름
+    pub source_file: String,
+    /// 컴파일 명령 (인터프리터 언어는 생략)
+    #[serde(default)]
+    pub compile: Option<String>,
+    pub run: String,
+    /// Docker 백엔드에서 쓰는 이미지
+    pub image: String,
+    /// 문제 시간 제한에 곱하는 배수
+    #[serde(default = "default_time_multiplier")]
+    pub time_multiplier: f64,
+    /// JVM처럼 가상 메모리를 크게 예약하는 런타임은 네이티브 백엔드에서 RLIMIT_AS를 걸 수 없다
+    #[serde(default = "default_limit_address_space")]
+    pub limit_address_space: bool,
+    /// 컴파일 에러 출력 형식 (생략하면 전체 출력을 에러 하나로 저장)
+    #[serde(default)]
+    pub diagnostics: Option<DiagnosticFormat>,
+    /// 컴파일러 버전을 출력하는 명령. 네이티브 백엔드의 컴파일 캐시 키에 쓰인다.
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+fn default_time_multiplier() -> f64 {
+    1.0
+}
+
+fn default_limit_address_space() -> bool {
+    true
+}
+
+impl Language {
+    pub fn compile_command(&self, memory_limit_mb: u32) -> Option<String> {
+        self.compile.as_deref().map(|command| expand(command, memory_limit_mb))
+    }
+
+    pub fn run_command(&self, memory_limit_mb: u32) -> String {
+        expand(&self.run, memory_limit_mb)
+    }
+
+    /// 언어 배수를 적용한 시간 제한 (ms)
+    pub fn time_limit(&self, base_ms: u32) -> u32 {
+        (base_ms as f64 * self.time_multiplier).round() as u32
+    }
+}
+
+fn expand(command: &str, memory_limit_mb: u32) -> String {
+    command.replace("{memory_limit_mb}", &memory_limit_mb.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageConfig {
+    #[serde(default, rename = "language")]
+    languages: Vec<Language>,
+}
+
+/// 설정 파일에 적힌 순서대로 언어를 보관한다.
+#[derive(Debug, Clone)]
+pub struct LanguageRegistry {
+    languages: Vec<Language>,
+}
+
+impl LanguageRegistry {
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let config: LanguageConfig = toml::from_str(text)?;
+
+        for (index, language) in config.languages.iter().enumerate() {
+            if config.languages[..index].iter().any(|l| l.id == language.id) {
+                return Err(anyhow::anyhow!("Duplicate language id: {}", language.id));
+            }
+            if language.time_multiplier <= 0.0 {
+                return Err(anyhow::anyhow!("Invalid time multiplier for {}", language.id));
+            }
+        }
+
+        Ok(Self { languages: config.languages })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_toml(&text)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Language> {
+        self.languages.iter().find(|l| l.id == id)
+    }
+
+    pub fn all(&self) -> &[Language] {
+        &self.languages
+    }
+}
+
+/// `LANGUAGES_CONFIG`(기본 `languages.toml`)에서 레지스트리를 읽어 전역으로 등록한다.
+/// 이미 등록되어 있으면 아무것도 하지 않는다.
+pub fn init_from_env() -> anyhow::Result<()> {
+    if REGISTRY.get().is_some() {
+        return Ok(());
+    }
+
+    let path = std::env::var("LANGUAGES_CONFIG").unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+    let registry = LanguageRegistry::load(Path::new(&path))?;
+    let _ = REGISTRY.set(registry);
+    Ok(())
+}
+
+/// 전역 언어 레지스트리. `init_from_env` 이후에 사용해야 한다.
+pub fn registry() -> &'static LanguageRegistry {
+    REGISTRY.get().expect("language registry is not initialized")
+}
+
+/// 등록된 언어 조회
+pub fn lookup(id: &str) -> anyhow::Result<&'static Language> {
+    registry()
+        .get(id)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported language: {}", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[[language]]
+id = "cpp"
+name = "C++17"
+source_file = "main.cpp"
+compile = "g++ -O2 -o build/main main.cpp"
+run = "./build/main"
+image = "gcc:13"
+
+[[language]]
+id = "java"
+name = "Java 17"
+source_file = "Main.java"
+compile = "javac -d build Main.java"
+run = "java -Xmx{memory_limit_mb}m -cp build Main"
+image = "eclipse-temurin:17"
+time_multiplier = 2.5
+limit_address_space = false
+"#;
+
+    #[test]
+    fn registry_keeps_file_order_and_defaults() {
+        let registry = LanguageRegistry::from_toml(SAMPLE).unwrap();
+        let ids: Vec<&str> = registry.all().iter().map(|l| l.id.as_str()).collect();
+        assert_eq!(ids, vec!["cpp", "java"]);
+        assert!(registry.get("python").is_none());
+
+        let cpp = registry.get("cpp").unwrap();
+        assert_eq!((cpp.time_multiplier, cpp.limit_address_space), (1.0, true));
+        assert!(cpp.diagnostics.is_none() && cpp.version.is_none());
+        assert_eq!(cpp.time_limit(1000), 1000);
+
+        let java = registry.get("java").unwrap();
+        assert_eq!(java.run_command(256), "java -Xmx256m -cp build Main");
+        assert_eq!(java.compile_command(256).as_deref(), Some("javac -d build Main.java"));
+        assert_eq!(java.time_limit(1001), 2503);
+        assert!(!java.limit_address_space);
+    }
+
+    #[test]
+    fn registry_rejects_invalid_configs() {
+        let duplicated = format!("{}\n{}", SAMPLE, &SAMPLE[..SAMPLE.find("\n[[language]]\nid = \"java\"").unwrap()]);
+        let error = LanguageRegistry::from_toml(&duplicated).unwrap_err();
+        assert!(error.to_string().contains("Duplicate language id: cpp"), "{}", error);
+
+        for multiplier in ["0", "-1.5"] {
+            let config = SAMPLE.replace("time_multiplier = 2.5", &format!("time_multiplier = {}", multiplier));
+            let error = LanguageRegistry::from_toml(&config).unwrap_err();
+            assert!(error.to_string().contains("Invalid time multiplier for java"), "{}", error);
+        }
+
+        // 필수 항목(run)이 빠진 언어
+        assert!(LanguageRegistry::from_toml(&SAMPLE.replace("run = \"./build/main\"", "")).is_err());
+    }
+
+    #[test]
+    fn bundled_config_loads() {
+        let registry = LanguageRegistry::load(Path::new(DEFAULT_CONFIG_PATH)).unwrap();
+        for id in ["cpp", "c", "python", "java"] {
+            assert!(registry.get(id).is_some(), "{} is missing", id);
+        }
+        assert!(LanguageRegistry::load(Path::new("missing-languages.toml")).is_err());
+    }
+}
@@ -0,0 +1,666 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::problem_repository;
+use checker::CheckerSpec;
+use language::Language;
+use progress::{JudgeProgress, ProgressSink};
+use scoring::{ScoringMode, SubtaskSpec};
+
+pub mod bundle;
+pub mod checker;
+pub mod compile;
+pub mod diagnostics;
+pub mod docker;
+pub mod fake;
+pub mod interactor;
+pub mod language;
+pub mod native;
+pub mod output_diff;
+pub mod progress;
+pub mod run;
+pub mod runtime_error;
+pub mod scoring;
+
+pub use docker::DockerBackend;
+pub use fake::FakeBackend;
+pub use native::NativeBackend;
+
+/// 문제에 제한이 지정되지 않았을 때 쓰는 기본값
+pub const DEFAULT_TIME_LIMIT_MS: u32 = 5000;
+pub const DEFAULT_MEMORY_LIMIT_MB: u32 = 512;
+pub const DEFAULT_OUTPUT_LIMIT_MB: u32 = 64;
+
+/// 채점 로그(judge.sh stdout/stderr)를 수집하는 최대 크기
+pub(crate) const LOG_LIMIT_BYTES: usize = 1024 * 1024;
+
+/// 컴파일에 허용하는 시간
+pub(crate) const COMPILE_TIME_BUDGET_SECS: u64 = 30;
+/// 컴파일러에 허용하는 메모리. 제출 프로그램의 메모리 제한과 별개로 적용한다.
+pub(crate) const COMPILE_MEMORY_LIMIT_MB: u32 = 1024;
+
+/// 작업 디렉토리 안에서 컴파일 결과물(실행 파일, 클래스 파일 등)을 두는 디렉토리
+pub(crate) const BUILD_DIR: &str = "build";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JudgeRequest {
+    pub submission_id: i64,
+    pub language: String,
+    pub source_code: String,
+    pub problem_id: i64,
+    pub time_limit: u32,      // ms 단위
+    pub memory_limit: u32,    // MB 단위
+    /// 테스트케이스 하나의 stdout/stderr 크기 제한 (MB)
+    #[serde(default = "default_output_limit")]
+    pub output_limit: u32,
+    pub checker: CheckerSpec,
+    /// 인터랙티브 문제의 인터랙터 (문제 폴더 기준 경로)
+    pub interactor: Option<String>,
+    pub subtasks: Vec<SubtaskSpec>,
+    pub scoring: ScoringMode,
+    /// 예상 출력과 실행 결과를 저장하는 테스트케이스 이름
+    #[serde(default)]
+    pub public_testcases: Vec<String>,
+    /// 문제 테스트케이스 대신 사용할 입력 디렉토리 (실행 모드)
+    #[serde(default)]
+    pub custom_testcases: Option<PathBuf>,
+    /// 문제 폴더 대신 사용할 디렉토리 (원격 워커가 내려받은 문제 번들)
+    #[serde(default)]
+    pub problem_root: Option<PathBuf>,
+}
+
+impl JudgeRequest {
+    /// 기본 제한과 채점 설정으로 만든 요청. 문제 메타데이터가 있으면 필드를 덮어쓴다.
+    pub fn new(submission_id: i64, language: String, source_code: String, problem_id: i64) -> Self {
+        Self {
+            submission_id,
+            language,
+            source_code,
+            problem_id,
+            time_limit: DEFAULT_TIME_LIMIT_MS,
+            memory_limit: DEFAULT_MEMORY_LIMIT_MB,
+            output_limit: DEFAULT_OUTPUT_LIMIT_MB,
+            checker: CheckerSpec::default(),
+            interactor: None,
+            subtasks: Vec::new(),
+            scoring: ScoringMode::default(),
+            public_testcases: Vec::new(),
+            custom_testcases: None,
+            problem_root: None,
+        }
+    }
+
+    pub(crate) fn output_limit_bytes(&self) -> u64 {
+        self.output_limit as u64 * 1024 * 1024
+    }
+
+    /// 체커, 인터랙터, 테스트케이스가 있는 문제 폴더
+    pub(crate) fn problem_path(&self) -> PathBuf {
+        self.problem_root
+            .clone()
+            .unwrap_or_else(|| problem_repository::repository().problem_dir(self.problem_id))
+    }
+
+    /// 채점에 사용할 테스트케이스 디렉토리
+    pub(crate) fn testcase_path(&self) -> PathBuf {
+        self.custom_testcases
+            .clone()
+            .unwrap_or_else(|| self.problem_path().join("testcases"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JudgeResult {
+    pub status: String,
+    pub score: i32,
+    #[serde(default = "default_max_score")]
+    pub max_score: i32,
+    pub execution_time: Option<i32>,
+    pub memory_usage: Option<i32>,
+    pub compile_message: Option<String>,
+    pub runtime_error_type: Option<String>,
+    pub runtime_error_message: Option<String>,
+    pub total_testcases: i32,
+    pub passed_testcases: i32,
+    pub testcase_results: Vec<TestcaseResultData>,
+    pub compile_errors: Option<Vec<CompileErrorData>>,
+    #[serde(default)]
+    pub subtask_results: Vec<SubtaskResultData>,
+}
+
+fn default_output_limit() -> u32 {
+    DEFAULT_OUTPUT_LIMIT_MB
+}
+
+/// 상한까지만 이어 붙인다 (UTF-8 경계가 잘리면 손실 변환)
+pub(crate) fn push_bounded(buffer: &mut String, chunk: &[u8], limit: usize) {
+    let end = chunk.len().min(limit.saturating_sub(buffer.len()));
+    buffer.push_str(&String::from_utf8_lossy(&chunk[..end]));
+}
+
+fn default_max_score() -> i32 {
+    scoring::DEFAULT_MAX_SCORE
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestcaseResultData {
+    pub testcase_number: i32,
+    /// 테스트케이스 파일 이름 (확장자 제외). 서브태스크 구분에 쓰인다.
+    #[serde(default)]
+    pub testcase_name: String,
+    pub status: String,
+    pub execution_time: Option<i32>,
+    pub memory_usage: Option<i32>,
+    pub error_message: Option<String>,
+    /// 공개 테스트케이스만 출력 일부를 저장한다
+    pub expected_output: Option<String>,
+    pub actual_output: Option<String>,
+    /// `expected_output`/`actual_output` 첫 줄의 줄 번호
+    #[serde(default)]
+    pub output_start_line: Option<i32>,
+    /// 예상 출력과 처음으로 다른 위치
+    #[serde(default)]
+    pub diff_line: Option<i32>,
+    #[serde(default)]
+    pub diff_column: Option<i32>,
+    #[serde(default)]
+    pub exit_code: i32,
+    #[serde(default)]
+    pub signal: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubtaskResultData {
+    pub subtask_number: i32,
+    pub name: Option<String>,
+    pub status: String,
+    pub score: i32,
+    pub max_score: i32,
+    pub total_testcases: i32,
+    pub passed_testcases: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompileErrorData {
+    #[serde(default)]
+    pub file_name: Option<String>,
+    pub line_number: Option<i32>,
+    pub column_number: Option<i32>,
+    /// `error` 또는 `warning`
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    pub error_type: Option<String>,
+    pub error_message: String,
+}
+
+fn default_severity() -> String {
+    "error".to_string()
+}
+
+/// 컴파일 단계의 결과. 성공하면 결과물은 작업 디렉토리의 `build/`에 남는다.
+#[derive(Debug, Clone)]
+pub struct CompileOutcome {
+    pub success: bool,
+    /// 컴파일러 출력 (경고 포함)
+    pub message: String,
+    /// 컴파일러가 스스로 끝나며 낸 결과인지. 시간 초과, 강제 종료, 실행 환경 문제로 실패한 결과는
+    /// 다시 컴파일하면 달라질 수 있으므로 캐시하지 않는다.
+    pub cacheable: bool,
+}
+
+/// 채점 실행 환경. 작업 디렉토리에는 언어별 소스 파일이 미리 저장되어 있다.
+///
+/// 컴파일과 실행은 따로 호출된다. `judge`가 불릴 때는 `build/`에 실행할 파일이 준비되어 있다.
+#[async_trait::async_trait]
+pub trait JudgeBackend: Send + Sync {
+    /// 컴파일 캐시 키에 들어가는 컴파일러 버전. 컴파일러가 바뀌면 값도 바뀌어야 한다.
+    async fn toolchain_version(&self, language: &Language) -> anyhow::Result<String>;
+
+    /// 작업 디렉토리의 소스를 컴파일 제한(시간/메모리)으로 컴파일해 `build/`에 결과물을 남긴다.
+    async fn compile(&self, request: &JudgeRequest, workspace: &Path) -> anyhow::Result<CompileOutcome>;
+
+    /// `build/`의 프로그램으로 테스트케이스를 실행한다.
+    /// 테스트케이스를 실행할 때마다 `progress`에 `Running`을 알린다.
+    async fn judge(
+        &self,
+        request: &JudgeRequest,
+        workspace: &Path,
+        progress: &dyn ProgressSink,
+    ) -> anyhow::Result<JudgeResult>;
+
+    /// 문제에 포함된 체커/인터랙터 C++ 소스(작업 디렉토리의 `<name>.cpp`)를 제출 프로그램과 같은 샌드박스에서
+    /// 컴파일해 `build/<name>`을 남긴다. 출제자가 올린 코드도 호스트에서 직접 실행하지 않기 위해서다.
+    async fn compile_problem_program(
+        &self,
+        workspace: &Path,
+        name: &str,
+        extra_flags: &[&str],
+    ) -> anyhow::Result<CompileOutcome>;
+}
+
+/// `JUDGE_BACKEND` 환경 변수로 채점 백엔드 선택 (docker | native | fake, 기본값 docker)
+pub fn backend_from_env() -> anyhow::Result<Arc<dyn JudgeBackend>> {
+    let name = std::env::var("JUDGE_BACKEND").unwrap_or_else(|_| "docker".to_string());
+    match name.as_str() {
+        "docker" => Ok(Arc::new(DockerBackend)),
+        "native" => Ok(Arc::new(NativeBackend::new()?)),
+        "fake" => Ok(Arc::new(FakeBackend::new())),
+        other => Err(anyhow::anyhow!("Unknown judge backend: {}", other)),
+    }
+}
+
+pub async fn judge_submission(
+    backend: &dyn JudgeBackend,
+    mut request: JudgeRequest,
+    progress: &dyn ProgressSink,
+) -> anyhow::Result<JudgeResult> {
+    let temp_dir = prepare_workspace(&mut request).await?;
+
+    progress.report(JudgeProgress::Compiling);
+    let result = match compile::prepare_build(backend, &request, &temp_dir).await {
+        Ok(Some(failed)) => Ok(failed),
+        Ok(None) => backend.judge(&request, &temp_dir, progress).await,
+        Err(e) => Err(e),
+    };
+
+    // 임시 파일 정리
+    let _ = fs::remove_dir_all(&temp_dir).await;
+
+    let mut result = result?;
+    if result.status == "COMPILATION_ERROR" {
+        // 컴파일러 출력을 에러/경고 단위로 나눈다
+        let language = language::lookup(&request.language)?;
+        let output = result.compile_message.as_deref().unwrap_or_default();
+        result.compile_errors = Some(diagnostics::parse(language.diagnostics, output));
+    }
+    scoring::apply(&mut result, &request)?;
+    Ok(result)
+}
+
+/// 언어별 시간 배수를 적용하고, 소스 파일을 저장한 임시 작업 디렉토리를 만든다.
+pub(crate) async fn prepare_workspace(request: &mut JudgeRequest) -> anyhow::Result<PathBuf> {
+    let language = language::lookup(&request.language)?;
+    request.time_limit = language.time_limit(request.time_limit);
+
+    let session_id = Uuid::new_v4().to_string();
+    let temp_dir = PathBuf::from(format!("/tmp/judge_{}", session_id));
+
+    // 임시 디렉토리 생성
+    fs::create_dir_all(&temp_dir).await?;
+
+    // 소스 코드 파일 생성
+    fs::write(temp_dir.join(&language.source_file), &request.source_code).await?;
+
+    Ok(temp_dir)
+}
+
+/// 테스트케이스 디렉토리의 입력 파일 수
+pub(crate) async fn count_testcases(testcase_path: &Path) -> usize {
+    let mut count = 0;
+    if let Ok(mut entries) = fs::read_dir(testcase_path).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.path().extension().is_some_and(|ext| ext == "in") {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// judge.sh가 stdout에 출력하는 한 줄짜리 JSON 이벤트
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JudgeEvent {
+    Compile {
+        verdict: String,
+        /// 컴파일 명령의 종료 코드 (compile 단계에서 실패했을 때만)
+        #[serde(default)]
+        exit_code: Option<i32>,
+    },
+    Testcase(TestcaseReport),
+}
+
+/// judge.sh 출력 한 줄에 해당하는 진행 상황. 컴파일이 끝나면 첫 테스트케이스, 테스트케이스가 끝나면 다음 테스트케이스를 실행한다.
+pub(crate) fn progress_from_line(line: &str, total: i32) -> Option<JudgeProgress> {
+    match serde_json::from_str(line.trim()).ok()? {
+        JudgeEvent::Compile { verdict, .. } if verdict == "OK" && total > 0 => {
+            Some(JudgeProgress::Running { testcase: 1, total })
+        }
+        JudgeEvent::Testcase(report) if report.number < total => Some(JudgeProgress::Running {
+            testcase: report.number + 1,
+            total,
+        }),
+        _ => None,
+    }
+}
+
+/// 테스트케이스 하나의 실행 결과 (judge.sh 출력 또는 네이티브 백엔드 측정값)
+///
+/// 샌드박스는 정상 종료한 테스트케이스를 `OK`로 보고하고, 출력 검사는 체커가 맡는다.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TestcaseReport {
+    pub number: i32,
+    #[serde(default)]
+    pub name: String,
+    pub verdict: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    pub wall_ms: i32,
+    pub cpu_ms: i32,
+    pub rss_kb: i32,
+    pub exit_code: i32,
+    pub signal: i32,
+    /// 런타임 에러로 끝난 테스트케이스의 분류 (`runtime_error::attach`)
+    #[serde(skip)]
+    pub runtime_error: Option<runtime_error::RuntimeError>,
+    /// 공개 테스트케이스의 출력 비교 (`output_diff::attach`)
+    #[serde(skip)]
+    pub output_diff: Option<output_diff::OutputDiff>,
+}
+
+pub(crate) async fn parse_judge_result(
+    backend: &dyn JudgeBackend,
+    stdout: &str,
+    stderr: &str,
+    completed_normally: bool,
+    request: &JudgeRequest,
+    workspace: &Path,
+) -> anyhow::Result<JudgeResult> {
+    if !completed_normally {
+        return Ok(JudgeResult {
+            status: "TIME_LIMIT_EXCEEDED".to_string(),
+            score: 0,
+            max_score: scoring::DEFAULT_MAX_SCORE,
+            execution_time: None,
+            memory_usage: None,
+            compile_message: None,
+            runtime_error_type: Some("TIME_LIMIT_EXCEEDED".to_string()),
+            runtime_error_message: Some("채점 시간이 초과되었습니다.".to_string()),
+            total_testcases: 1,
+            passed_testcases: 0,
+            testcase_results: vec![],
+            compile_errors: None,
+            subtask_results: vec![],
+        });
+    }
+
+    // JSON이 아닌 줄은 무시
+    let events: Vec<JudgeEvent> = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str(line.trim()).ok())
+        .collect();
+
+    let mut compiled = None;
+    let mut reports = Vec::new();
+    for event in events {
+        match event {
+            JudgeEvent::Compile { verdict, .. } => compiled = Some(verdict == "OK"),
+            JudgeEvent::Testcase(report) => reports.push(report),
+        }
+    }
+
+    match compiled {
+        None => Err(anyhow::anyhow!("채점 결과를 해석할 수 없습니다: {}", stderr.trim())),
+        Some(false) => Ok(compilation_error_result(stderr)),
+        Some(true) => {
+            if request.interactor.is_some() {
+                interactor::attach_messages(&mut reports, workspace).await;
+            } else if matches!(request.checker, CheckerSpec::Custom { .. }) {
+                checker::attach_messages(&mut reports, workspace).await;
+            }
+            runtime_error::attach(&mut reports, workspace).await;
+            checker::check_reports(backend, &mut reports, request, workspace).await?;
+            output_diff::attach(&mut reports, request, workspace).await;
+            Ok(aggregate_reports(reports))
+        }
+    }
+}
+
+/// judge.sh 컴파일 단계의 출력 해석. 컴파일러 출력은 stderr로 나온다.
+pub(crate) fn parse_compile_output(
+    stdout: &str,
+    stderr: &str,
+    completed_normally: bool,
+) -> anyhow::Result<CompileOutcome> {
+    if !completed_normally {
+        return Ok(CompileOutcome {
+            success: false,
+            message: "컴파일 시간이 초과되었습니다.".to_string(),
+            cacheable: false,
+        });
+    }
+
+    let verdict = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JudgeEvent>(line.trim()).ok())
+        .find_map(|event| match event {
+            JudgeEvent::Compile { verdict, exit_code } => Some((verdict, exit_code)),
+            _ => None,
+        });
+
+    match verdict {
+        Some((verdict, exit_code)) => Ok(CompileOutcome {
+            success: verdict == "OK",
+            message: stderr.to_string(),
+            cacheable: verdict == "OK" || exit_code.is_some_and(compile::is_compiler_exit),
+        }),
+        None => Err(anyhow::anyhow!("컴파일 결과를 해석할 수 없습니다: {}", stderr.trim())),
+    }
+}
+
+/// 컴파일 실패 결과
+pub(crate) fn compilation_error_result(message: &str) -> JudgeResult {
+    JudgeResult {
+        status: "COMPILATION_ERROR".to_string(),
+        score: 0,
+        max_score: scoring::DEFAULT_MAX_SCORE,
+        execution_time: None,
+        memory_usage: None,
+        compile_message: Some(message.to_string()),
+        runtime_error_type: None,
+        runtime_error_message: None,
+        total_testcases: 0,
+        passed_testcases: 0,
+        testcase_results: vec![],
+        compile_errors: Some(vec![CompileErrorData {
+            file_name: None,
+            line_number: None,
+            column_number: None,
+            severity: default_severity(),
+            error_type: Some("COMPILATION_ERROR".to_string()),
+            error_message: message.to_string(),
+        }]),
+        subtask_results: vec![],
+    }
+}
+
+/// 테스트케이스별 실행 결과를 제출 결과로 합친다.
+/// 점수는 전부 통과 여부로만 채우고, 서브태스크 부분 점수는 `scoring::apply`가 다시 계산한다.
+pub(crate) fn aggregate_reports(mut reports: Vec<TestcaseReport>) -> JudgeResult {
+    reports.sort_by_key(|r| r.number);
+
+    let total_testcases = reports.len() as i32;
+    let passed_testcases = reports.iter().filter(|r| r.verdict == "ACCEPTED").count() as i32;
+    // 첫 번째로 실패한 테스트케이스의 결과가 제출 결과가 된다
+    let first_failure = reports.iter().find(|r| r.verdict != "ACCEPTED");
+    let status = first_failure
+        .map(|r| r.verdict.clone())
+        .unwrap_or_else(|| "ACCEPTED".to_string());
+
+    let (runtime_error_type, runtime_error_message) = match first_failure {
+        Some(r) if r.verdict == "RUNTIME_ERROR" => {
+            let error = runtime_error_of(r);
+            (Some(error.error_type), Some(error.message))
+        }
+        _ => (None, None),
+    };
+
+    let testcase_results = reports
+        .iter()
+        .map(|r| TestcaseResultData {
+            testcase_number: r.number,
+            testcase_name: r.name.clone(),
+            status: r.verdict.clone(),
+            execution_time: Some(r.cpu_ms),
+            memory_usage: Some(r.rss_kb),
+            error_message: match r.verdict.as_str() {
+                _ if r.message.is_some() => r.message.clone(),
+                "WRONG_ANSWER" => Some("출력이 예상과 다릅니다.".to_string()),
+                "RUNTIME_ERROR" => Some(runtime_error_of(r).message),
+                "TIME_LIMIT_EXCEEDED" => Some(format!("{}ms 후 중단되었습니다.", r.wall_ms)),
+                _ => None,
+            },
+            expected_output: r.output_diff.as_ref().map(|d| d.expected.clone()),
+            actual_output: r.output_diff.as_ref().map(|d| d.actual.clone()),
+            output_start_line: r.output_diff.as_ref().map(|d| d.start_line),
+            diff_line: r.output_diff.as_ref().and_then(|d| d.diff_line),
+            diff_column: r.output_diff.as_ref().and_then(|d| d.diff_column),
+            exit_code: r.exit_code,
+            signal: r.signal,
+        })
+        .collect();
+
+    JudgeResult {
+        score: if status == "ACCEPTED" { scoring::DEFAULT_MAX_SCORE } else { 0 },
+        max_score: scoring::DEFAULT_MAX_SCORE,
+        status,
+        execution_time: reports.iter().map(|r| r.cpu_ms).max(),
+        memory_usage: reports.iter().map(|r| r.rss_kb).max(),
+        compile_message: None,
+        runtime_error_type,
+        runtime_error_message,
+        total_testcases,
+        passed_testcases,
+        testcase_results,
+        compile_errors: None,
+        subtask_results: vec![],
+    }
+}
+
+/// stderr를 읽지 못한 테스트케이스는 시그널과 종료 코드로만 분류한다
+fn runtime_error_of(report: &TestcaseReport) -> runtime_error::RuntimeError {
+    report
+        .runtime_error
+        .clone()
+        .unwrap_or_else(|| runtime_error::classify(report.signal, report.exit_code, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_events_decide_what_is_cacheable() {
+        let ok = parse_compile_output("{\"type\":\"compile\",\"verdict\":\"OK\"}\n", "", true).unwrap();
+        assert!(ok.success && ok.cacheable);
+
+        let error = parse_compile_output(
+            "{\"type\":\"compile\",\"verdict\":\"COMPILATION_ERROR\",\"exit_code\":1}\n",
+            "main.cpp:1:1: error: expected ';'",
+            true,
+        )
+        .unwrap();
+        assert!(!error.success && error.cacheable);
+        assert_eq!(error.message, "main.cpp:1:1: error: expected ';'");
+
+        // timeout(124)이나 SIGKILL(137)로 끝난 컴파일러는 캐시하지 않는다
+        for exit_code in [124, 137] {
+            let stdout = format!("{{\"type\":\"compile\",\"verdict\":\"COMPILATION_ERROR\",\"exit_code\":{}}}\n", exit_code);
+            let killed = parse_compile_output(&stdout, "", true).unwrap();
+            assert!(!killed.success && !killed.cacheable, "exit code {} was cached", exit_code);
+        }
+
+        // 컨테이너가 제한 시간 안에 끝나지 않은 경우
+        let timed_out = parse_compile_output("", "", false).unwrap();
+        assert!(!timed_out.success && !timed_out.cacheable);
+
+        assert!(parse_compile_output("garbage", "boom", true).is_err());
+    }
+
+    fn report(number: i32, verdict: &str, cpu_ms: i32, rss_kb: i32) -> TestcaseReport {
+        TestcaseReport {
+            number,
+            name: number.to_string(),
+            verdict: verdict.to_string(),
+            message: None,
+            wall_ms: cpu_ms,
+            cpu_ms,
+            rss_kb,
+            exit_code: 0,
+            signal: 0,
+            runtime_error: None,
+            output_diff: None,
+        }
+    }
+
+    #[test]
+    fn first_failing_testcase_decides_the_verdict() {
+        // 보고 순서와 관계없이 번호가 가장 작은 실패가 제출 결과가 된다
+        let result = aggregate_reports(vec![
+            report(3, "WRONG_ANSWER", 5, 900),
+            report(1, "ACCEPTED", 40, 1200),
+            report(2, "TIME_LIMIT_EXCEEDED", 1000, 800),
+        ]);
+        assert_eq!(result.status, "TIME_LIMIT_EXCEEDED");
+        assert_eq!((result.score, result.total_testcases, result.passed_testcases), (0, 3, 1));
+        assert_eq!((result.execution_time, result.memory_usage), (Some(1000), Some(1200)));
+        let numbers: Vec<i32> = result.testcase_results.iter().map(|t| t.testcase_number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(result.testcase_results[1].error_message.as_deref(), Some("1000ms 후 중단되었습니다."));
+        assert_eq!(result.testcase_results[2].error_message.as_deref(), Some("출력이 예상과 다릅니다."));
+        assert!(result.runtime_error_type.is_none());
+
+        // 런타임 에러는 stderr가 없으면 시그널로 분류한다
+        let mut crashed = report(1, "RUNTIME_ERROR", 3, 100);
+        crashed.signal = 11;
+        let result = aggregate_reports(vec![crashed, report(2, "WRONG_ANSWER", 3, 100)]);
+        assert_eq!(result.status, "RUNTIME_ERROR");
+        assert_eq!(result.runtime_error_type.as_deref(), Some("SIGSEGV"));
+
+        let result = aggregate_reports(vec![report(1, "ACCEPTED", 1, 1), report(2, "ACCEPTED", 2, 2)]);
+        assert_eq!((result.status.as_str(), result.score), ("ACCEPTED", scoring::DEFAULT_MAX_SCORE));
+    }
+
+    #[tokio::test]
+    async fn truncated_judge_output_keeps_complete_events() {
+        let workspace = std::env::temp_dir().join(format!("nekonic_parse_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&workspace).await.unwrap();
+        let backend = FakeBackend::new();
+        let request = JudgeRequest::new(1, "cpp".to_string(), String::new(), 1001);
+
+        // 로그 상한에서 잘린 마지막 줄은 버리고 끝까지 출력된 테스트케이스만 쓴다
+        let stdout = concat!(
+            "{\"type\":\"compile\",\"verdict\":\"OK\"}\n",
+            "progress: 1\n",
+            "{\"type\":\"testcase\",\"number\":1,\"name\":\"1\",\"verdict\":\"TIME_LIMIT_EXCEEDED\",\"wall_ms\":2100,\"cpu_ms\":2000,\"rss_kb\":512,\"exit_code\":124,\"signal\":0}\n",
+            "{\"type\":\"testcase\",\"number\":2,\"name\":\"2\",\"verd",
+        );
+        let result = parse_judge_result(&backend, stdout, "", true, &request, &workspace).await.unwrap();
+        assert_eq!(result.status, "TIME_LIMIT_EXCEEDED");
+        assert_eq!(result.total_testcases, 1);
+        assert_eq!(result.testcase_results[0].exit_code, 124);
+
+        let compile_error = "{\"type\":\"compile\",\"verdict\":\"COMPILATION_ERROR\"}\n";
+        let failed = parse_judge_result(&backend, compile_error, "main.cpp:1: error", true, &request, &workspace)
+            .await
+            .unwrap();
+        assert_eq!(failed.status, "COMPILATION_ERROR");
+        assert_eq!(failed.compile_message.as_deref(), Some("main.cpp:1: error"));
+
+        // 컴파일 이벤트조차 없으면 결과를 만들지 않는다
+        let error = parse_judge_result(&backend, "{\"type\":\"comp", "killed", true, &request, &workspace)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("killed"));
+
+        // 컨테이너가 제한 시간 안에 끝나지 않은 경우
+        let timed_out = parse_judge_result(&backend, stdout, "", false, &request, &workspace).await.unwrap();
+        assert_eq!(timed_out.status, "TIME_LIMIT_EXCEEDED");
+        assert!(timed_out.testcase_results.is_empty());
+
+        let _ = fs::remove_dir_all(&workspace).await;
+    }
+}
@@ -0,0 +1,519 @@
+// 로컬 프로세스 채점 백엔드 (Docker가 없는 호스트 / CI 용)
+use std::os::fd::FromRawFd;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use tokio::fs;
+
+use super::language::{self, Language};
+use super::progress::{JudgeProgress, ProgressSink};
+use super::{
+    aggregate_reports, checker, compile, interactor, output_diff, push_bounded, runtime_error, CompileOutcome, JudgeBackend, JudgeRequest, JudgeResult, TestcaseReport, BUILD_DIR, COMPILE_MEMORY_LIMIT_MB, COMPILE_TIME_BUDGET_SECS, LOG_LIMIT_BYTES,
+};
+
+/// 컴파일러와 인터랙터가 생성할 수 있는 파일 크기 상한
+const MAX_OUTPUT_FILE_BYTES: u64 = 256 * 1024 * 1024;
+/// 주소 공간 제한에 더해주는 여유분 (런타임/스택 예약용)
+const ADDRESS_SPACE_HEADROOM_MB: u64 = 64;
+
+/// 채점 프로세스를 호스트에서 자식 프로세스로 실행한다.
+///
+/// 실행마다 새 임시 디렉토리를 쓰고 rlimit으로 CPU 시간, 주소 공간, 파일 크기를 제한한다.
+/// Docker 백엔드의 `NetworkMode none`처럼 모든 프로세스를 새 네트워크 네임스페이스에서 실행하며,
+/// 네임스페이스를 만들 수 없으면 실행하지 않는다. 파일시스템은 격리하지 않는다.
+pub struct NativeBackend;
+
+impl NativeBackend {
+    /// 네트워크 격리를 쓸 수 있는 환경인지 확인하고 백엔드를 만든다.
+    pub fn new() -> anyhow::Result<Self> {
+        let mut command = Command::new("true");
+        // SAFETY: fork 이후 exec 이전에는 async-signal-safe 한 시스템 콜만 호출한다.
+        unsafe {
+            command.pre_exec(isolate_network);
+        }
+        let status = command.status().map_err(|e| {
+            anyhow::anyhow!(
+                "native 채점 백엔드는 네트워크 네임스페이스가 필요합니다 (root 권한 또는 unprivileged user namespace): {}",
+                e
+            )
+        })?;
+        if !status.success() {
+            anyhow::bail!("네트워크 격리 확인에 실패했습니다: {}", status);
+        }
+        Ok(Self)
+    }
+}
+
+/// 언어 레지스트리의 셸 명령을 실행 인자로 변환 (측정 대상이 셸이 아닌 프로그램이 되도록 exec)
+fn shell_command(command: &str) -> Vec<String> {
+    vec!["sh".to_string(), "-c".to_string(), format!("exec {}", command)]
+}
+
+#[derive(Clone, Copy)]
+pub(super) struct RunLimits {
+    pub cpu_secs: u64,
+    pub address_space_bytes: Option<u64>,
+    pub file_size_bytes: u64,
+}
+
+pub(super) struct RunOutcome {
+    pub exit_code: i32,
+    pub signal: i32,
+    pub wall_ms: i32,
+    pub cpu_ms: i32,
+    pub rss_kb: i32,
+    pub timed_out: bool,
+}
+
+#[async_trait::async_trait]
+impl JudgeBackend for NativeBackend {
+    /// languages.toml의 `version` 명령 출력. 명령이 없으면 컴파일 명령만으로 캐시를 구분한다.
+    async fn toolchain_version(&self, language: &Language) -> anyhow::Result<String> {
+        let Some(command) = &language.version else {
+            return Ok(String::new());
+        };
+        let output = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .output()
+            .await?;
+        // javac처럼 버전을 stderr로 출력하는 컴파일러도 있다
+        let mut version = String::from_utf8_lossy(&output.stdout).to_string();
+        version.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(version)
+    }
+
+    async fn compile(&self, request: &JudgeRequest, workspace: &Path) -> anyhow::Result<CompileOutcome> {
+        let language = language::lookup(&request.language)?;
+        let Some(compile) = language.compile_command(request.memory_limit) else {
+            return Ok(CompileOutcome { success: true, message: String::new(), cacheable: true });
+        };
+        compile_in_build_dir(workspace, &language.source_file, &compile, language.limit_address_space).await
+    }
+
+    async fn compile_problem_program(
+        &self,
+        workspace: &Path,
+        name: &str,
+        extra_flags: &[&str],
+    ) -> anyhow::Result<CompileOutcome> {
+        let command = checker::program_compile_command(name, extra_flags);
+        compile_in_build_dir(workspace, &format!("{}.cpp", name), &command, false).await
+    }
+
+    async fn judge(
+        &self,
+        request: &JudgeRequest,
+        workspace: &Path,
+        progress: &dyn ProgressSink,
+    ) -> anyhow::Result<JudgeResult> {
+        let language = language::lookup(&request.language)?;
+        let run_command = shell_command(&language.run_command(request.memory_limit));
+        // 제출 프로그램은 컴파일 결과물이 있는 build/에서 실행한다
+        let build_dir = workspace.join(BUILD_DIR);
+
+        // 테스트케이스 실행 (judge.sh와 같이 파일 이름 순서)
+        let mut inputs = Vec::new();
+        if let Ok(mut entries) = fs::read_dir(request.testcase_path()).await {
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "in") {
+                    inputs.push(path);
+                }
+            }
+        }
+        inputs.sort();
+
+        let limits = RunLimits {
+            cpu_secs: (request.time_limit as u64).div_ceil(1000) + 1,
+            address_space_bytes: language
+                .limit_address_space
+                .then_some((request.memory_limit as u64 + ADDRESS_SPACE_HEADROOM_MB) * 1024 * 1024),
+            // 출력 제한을 넘으면 SIGXFSZ로 종료된다
+            file_size_bytes: request.output_limit_bytes(),
+        };
+        let wall_limit = Duration::from_millis(request.time_limit as u64 * 2);
+        let memory_limit_kb = request.memory_limit as i32 * 1024;
+        let interactor_binary = interactor::prepare(self, request).await?;
+
+        let output_dir = workspace.join("output");
+        fs::create_dir_all(&output_dir).await?;
+
+        let mut reports = Vec::new();
+
+        for (index, input_path) in inputs.iter().enumerate() {
+            progress.report(JudgeProgress::Running {
+                testcase: index as i32 + 1,
+                total: inputs.len() as i32,
+            });
+            let name = input_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let result_path = output_dir.join(format!("{}.out", name));
+            let error_path = output_dir.join(format!("{}.err", name));
+
+            let (outcome, interactor_exit) = match &interactor_binary {
+                Some(binary) => {
+                    // 인터랙터 -> 제출 프로그램, 제출 프로그램 -> 인터랙터 방향의 파이프
+                    let (program_in, interactor_out) = pipe()?;
+                    let (interactor_in, program_out) = pipe()?;
+
+                    let expected_path = input_path.with_extension("out");
+                    let answer = if expected_path.exists() {
+                        expected_path.canonicalize()?
+                    } else {
+                        "/dev/null".into()
+                    };
+                    let interactor_args = vec![
+                        binary.to_string_lossy().to_string(),
+                        input_path.canonicalize()?.to_string_lossy().to_string(),
+                        answer.to_string_lossy().to_string(),
+                    ];
+                    let interactor_log = output_dir.join(format!("{}.interactor", name));
+
+                    let (program, judge) = tokio::join!(
+                        run_process(
+                            &run_command,
+                            &build_dir,
+                            Stdio::from(program_in),
+                            Stdio::from(program_out),
+                            Stdio::from(std::fs::File::create(&error_path)?),
+                            limits,
+                            wall_limit,
+                        ),
+                        run_process(
+                            &interactor_args,
+                            workspace,
+                            Stdio::from(interactor_in),
+                            Stdio::from(interactor_out),
+                            Stdio::from(std::fs::File::create(&interactor_log)?),
+                            RunLimits {
+                                cpu_secs: limits.cpu_secs * 2,
+                                address_space_bytes: None,
+                                file_size_bytes: MAX_OUTPUT_FILE_BYTES,
+                            },
+                            wall_limit,
+                        ),
+                    );
+                    (program?, Some(judge?.exit_code))
+                }
+                None => {
+                    let outcome = run_process(
+                        &run_command,
+                        &build_dir,
+                        Stdio::from(std::fs::File::open(input_path)?),
+                        Stdio::from(std::fs::File::create(&result_path)?),
+                        Stdio::from(std::fs::File::create(&error_path)?),
+                        limits,
+                        wall_limit,
+                    )
+                    .await?;
+                    (outcome, None)
+                }
+            };
+
+            let program_failed = outcome.exit_code != 0 || outcome.signal != 0;
+            let verdict = if outcome.signal == libc::SIGSYS {
+                // 네이티브 백엔드는 seccomp를 걸지 않지만 judge.sh와 같은 판정을 따른다
+                "SECURITY_VIOLATION"
+            } else if outcome.timed_out || outcome.cpu_ms > request.time_limit as i32 {
+                "TIME_LIMIT_EXCEEDED"
+            } else if outcome.rss_kb > memory_limit_kb {
+                "MEMORY_LIMIT_EXCEEDED"
+            } else if outcome.signal == libc::SIGXFSZ || output_exceeded(&result_path, request).await {
+                "OUTPUT_LIMIT_EXCEEDED"
+            } else {
+                match interactor_exit {
+                    Some(exit_code) => interactor::verdict(exit_code, program_failed),
+                    None if program_failed => "RUNTIME_ERROR",
+                    // 출력 검사는 체커가 담당
+                    None => "OK",
+                }
+            };
+
+            reports.push(TestcaseReport {
+                number: index as i32 + 1,
+                name,
+                verdict: verdict.to_string(),
+                message: None,
+                wall_ms: outcome.wall_ms,
+                cpu_ms: outcome.cpu_ms,
+                rss_kb: outcome.rss_kb,
+                exit_code: outcome.exit_code,
+                signal: outcome.signal,
+                runtime_error: None,
+                output_diff: None,
+            });
+        }
+
+        if interactor_binary.is_some() {
+            interactor::attach_messages(&mut reports, workspace).await;
+        }
+        runtime_error::attach(&mut reports, workspace).await;
+        checker::check_reports(self, &mut reports, request, workspace).await?;
+        output_diff::attach(&mut reports, request, workspace).await;
+        Ok(aggregate_reports(reports))
+    }
+}
+
+/// 작업 디렉토리의 `source_file`을 컴파일 제한으로 컴파일한다.
+/// 결과물이 소스와 함께 build/에 남도록 build/에서 컴파일한다.
+async fn compile_in_build_dir(
+    workspace: &Path,
+    source_file: &str,
+    command: &str,
+    limit_address_space: bool,
+) -> anyhow::Result<CompileOutcome> {
+    let build_dir = workspace.join(BUILD_DIR);
+    fs::create_dir_all(&build_dir).await?;
+    fs::copy(workspace.join(source_file), build_dir.join(source_file)).await?;
+
+    let log_path = workspace.join("compile_error.txt");
+    let outcome = run_process(
+        &shell_command(command),
+        &build_dir,
+        Stdio::null(),
+        Stdio::null(),
+        Stdio::from(std::fs::File::create(&log_path)?),
+        RunLimits {
+            cpu_secs: COMPILE_TIME_BUDGET_SECS,
+            address_space_bytes: limit_address_space.then_some(COMPILE_MEMORY_LIMIT_MB as u64 * 1024 * 1024),
+            file_size_bytes: MAX_OUTPUT_FILE_BYTES,
+        },
+        Duration::from_secs(COMPILE_TIME_BUDGET_SECS),
+    )
+    .await?;
+
+    let mut message = String::new();
+    push_bounded(&mut message, &fs::read(&log_path).await?, LOG_LIMIT_BYTES);
+    if outcome.timed_out {
+        message.push_str(&format!("컴파일 시간이 초과되었습니다. ({}초)\n", COMPILE_TIME_BUDGET_SECS));
+    }
+    Ok(CompileOutcome {
+        success: !outcome.timed_out && outcome.exit_code == 0 && outcome.signal == 0,
+        message,
+        cacheable: !outcome.timed_out && outcome.signal == 0 && compile::is_compiler_exit(outcome.exit_code),
+    })
+}
+
+/// SIGXFSZ를 무시하고 쓰기 실패(EFBIG)만 받은 프로그램도 잡아내기 위해 출력 파일이 제한 크기에 도달했는지 확인한다.
+async fn output_exceeded(result_path: &Path, request: &JudgeRequest) -> bool {
+    fs::metadata(result_path)
+        .await
+        .is_ok_and(|metadata| metadata.len() >= request.output_limit_bytes())
+}
+
+/// 자식 프로세스를 제한을 걸어 실행하고, 종료 상태와 자원 사용량을 측정한다.
+pub(super) async fn run_process(
+    args: &[String],
+    workspace: &Path,
+    stdin: Stdio,
+    stdout: Stdio,
+    stderr: Stdio,
+    limits: RunLimits,
+    wall_limit: Duration,
+) -> anyhow::Result<RunOutcome> {
+    let (program, rest) = args.split_first().ok_or_else(|| anyhow::anyhow!("Empty command"))?;
+
+    let mut command = Command::new(program);
+    command
+        .args(rest)
+        .current_dir(workspace)
+        .stdin(stdin)
+        .stdout(stdout)
+        .stderr(stderr)
+        .env_clear()
+        .env("PATH", std::env::var("PATH").unwrap_or_default())
+        .env("HOME", workspace);
+
+    // SAFETY: fork 이후 exec 이전에는 async-signal-safe 한 시스템 콜만 호출한다.
+    unsafe {
+        command.pre_exec(move || apply_limits(&limits));
+    }
+
+    let child = command.spawn()?;
+    let pid = child.id() as libc::pid_t;
+    // 부모가 들고 있는 stdio 사본을 닫아야 파이프 반대편에서 EOF를 받을 수 있다
+    drop(command);
+    let started = Instant::now();
+
+    let mut waiter = tokio::task::spawn_blocking(move || wait_with_rusage(pid));
+    let (wait_result, timed_out) = match tokio::time::timeout(wall_limit, &mut waiter).await {
+        Ok(result) => (result, false),
+        Err(_) => {
+            // 프로세스 그룹 전체 종료
+            unsafe {
+                libc::kill(-pid, libc::SIGKILL);
+            }
+            (waiter.await, true)
+        }
+    };
+    let (status, usage) = wait_result??;
+    let wall_ms = started.elapsed().as_millis() as i32;
+
+    let status = ExitStatus::from_raw(status);
+    let signal = status.signal().unwrap_or(0);
+    let cpu_micros = (usage.ru_utime.tv_sec + usage.ru_stime.tv_sec) * 1_000_000
+        + (usage.ru_utime.tv_usec + usage.ru_stime.tv_usec);
+
+    Ok(RunOutcome {
+        exit_code: status.code().unwrap_or(128 + signal),
+        signal,
+        wall_ms,
+        cpu_ms: (cpu_micros / 1000) as i32,
+        rss_kb: usage.ru_maxrss as i32, // Linux에서는 KB 단위
+        timed_out,
+    })
+}
+
+/// 읽기/쓰기 양 끝 (자식 프로세스에 새지 않도록 close-on-exec)
+fn pipe() -> std::io::Result<(std::fs::File, std::fs::File)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: pipe2가 새로 만든 fd이며 여기서만 소유한다.
+    unsafe {
+        Ok((
+            std::fs::File::from_raw_fd(fds[0]),
+            std::fs::File::from_raw_fd(fds[1]),
+        ))
+    }
+}
+
+/// 루프백만 있는(그마저 내려가 있는) 새 네트워크 네임스페이스로 옮긴다.
+/// root가 아니면 사용자 네임스페이스를 함께 만들어야 네트워크 네임스페이스를 만들 수 있다.
+fn isolate_network() -> std::io::Result<()> {
+    if unsafe { libc::unshare(libc::CLONE_NEWNET) } == 0
+        || unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } == 0
+    {
+        return Ok(());
+    }
+    Err(std::io::Error::last_os_error())
+}
+
+fn apply_limits(limits: &RunLimits) -> std::io::Result<()> {
+    // 시간 초과 시 자손 프로세스까지 한 번에 종료할 수 있도록 새 프로세스 그룹 생성
+    if unsafe { libc::setpgid(0, 0) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    isolate_network()?;
+
+    set_rlimit(libc::RLIMIT_CPU, limits.cpu_secs)?;
+    set_rlimit(libc::RLIMIT_FSIZE, limits.file_size_bytes)?;
+    set_rlimit(libc::RLIMIT_CORE, 0)?;
+    if let Some(bytes) = limits.address_space_bytes {
+        set_rlimit(libc::RLIMIT_AS, bytes)?;
+    }
+    Ok(())
+}
+
+fn set_rlimit(resource: libc::__rlimit_resource_t, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// wait4로 자식 프로세스를 회수하고 자원 사용량을 함께 가져온다.
+fn wait_with_rusage(pid: libc::pid_t) -> std::io::Result<(i32, libc::rusage)> {
+    let mut status = 0;
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        if unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } != -1 {
+            return Ok((status, usage));
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge::judge_submission;
+    use crate::judge::progress::NoProgress;
+
+    #[tokio::test]
+    async fn processes_run_without_the_host_network() {
+        NativeBackend::new().unwrap();
+        let workspace = std::env::temp_dir().join(format!("nekonic_native_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&workspace).unwrap();
+        let output = std::fs::File::create(workspace.join("ns")).unwrap();
+
+        let args: Vec<String> = ["readlink", "/proc/self/ns/net"].iter().map(|s| s.to_string()).collect();
+        let limits = RunLimits { cpu_secs: 5, address_space_bytes: None, file_size_bytes: 1024 * 1024 };
+        let outcome = run_process(&args, &workspace, Stdio::null(), output.into(), Stdio::null(), limits, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(outcome.exit_code, 0);
+
+        let child_namespace = std::fs::read_to_string(workspace.join("ns")).unwrap();
+        let host_namespace = std::fs::read_link("/proc/self/ns/net").unwrap();
+        assert!(child_namespace.starts_with("net:"));
+        assert_ne!(child_namespace.trim(), host_namespace.to_string_lossy());
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    const LIMITS_PROGRAM: &str = r#"
+#include <cstdio>
+#include <cstring>
+#include <vector>
+int main() {
+    char mode[16] = {0};
+    scanf("%15s", mode);
+    if (!strcmp(mode, "tle")) { volatile unsigned long x = 0; for (;;) x++; }
+    if (!strcmp(mode, "mle")) {
+        std::vector<char> memory(64 << 20);
+        memset(memory.data(), 1, memory.size());
+        printf("%d\n", memory[12345]);
+        return 0;
+    }
+    if (!strcmp(mode, "re")) return 3;
+    printf("%s\n", mode);
+}
+"#;
+
+    #[tokio::test]
+    async fn limits_from_the_request_apply_to_each_testcase() {
+        language::init_from_env().unwrap();
+        let backend = NativeBackend::new().unwrap();
+        let testcases = std::env::temp_dir().join(format!("nekonic_native_limits_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&testcases).unwrap();
+        let cases = [("1", "ok", Some("ok")), ("2", "tle", None), ("3", "mle", None), ("4", "re", None), ("5", "wa", Some("ok"))];
+        for (name, input, output) in cases {
+            std::fs::write(testcases.join(format!("{}.in", name)), input).unwrap();
+            if let Some(output) = output {
+                std::fs::write(testcases.join(format!("{}.out", name)), output).unwrap();
+            }
+        }
+
+        let mut request = JudgeRequest::new(1, "cpp".to_string(), LIMITS_PROGRAM.to_string(), 0);
+        request.time_limit = 300;
+        request.memory_limit = 32;
+        request.custom_testcases = Some(testcases.clone());
+        let result = judge_submission(&backend, request, &NoProgress).await.unwrap();
+
+        // 한 테스트케이스의 초과가 채점 전체를 멈추지 않는다
+        let verdicts: Vec<&str> = result.testcase_results.iter().map(|t| t.status.as_str()).collect();
+        assert_eq!(
+            verdicts,
+            vec!["ACCEPTED", "TIME_LIMIT_EXCEEDED", "MEMORY_LIMIT_EXCEEDED", "RUNTIME_ERROR", "WRONG_ANSWER"]
+        );
+        assert_eq!(result.status, "TIME_LIMIT_EXCEEDED");
+        assert!(result.testcase_results[2].memory_usage.unwrap() > 32 * 1024);
+
+        std::fs::remove_dir_all(&testcases).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+// 공개 테스트케이스(예제 등)의 예상 출력과 실행 결과 비교
+use std::path::Path;
+
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+
+use super::checker::lines_of;
+use super::{JudgeRequest, TestcaseReport};
+
+/// 저장하는 출력 줄 수
+const PREVIEW_LINES: usize = 30;
+/// 첫 번째 차이 앞에 함께 보여주는 줄 수
+const CONTEXT_LINES: usize = 5;
+/// 줄 하나에 저장하는 최대 문자 수
+const LINE_CHAR_LIMIT: usize = 200;
+/// 비교를 위해 읽는 출력 파일 최대 크기
+const READ_LIMIT_BYTES: u64 = 1024 * 1024;
+
+/// 예상 출력과 실행 결과의 일부, 그리고 첫 번째로 다른 위치
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputDiff {
+    pub expected: String,
+    pub actual: String,
+    /// `expected`/`actual` 첫 줄의 줄 번호 (1부터)
+    pub start_line: i32,
+    /// 첫 번째로 다른 줄과 열 (1부터). 출력이 같으면 `None`
+    pub diff_line: Option<i32>,
+    pub diff_column: Option<i32>,
+}
+
+/// 줄 단위로 비교한다. 줄 끝 공백과 마지막 빈 줄은 `exact` 체커처럼 무시한다.
+///
+/// 저장하는 출력은 첫 번째 차이 조금 앞부터 `PREVIEW_LINES`줄이며, 긴 줄은 잘라낸다.
+pub fn compare(expected: &str, actual: &str) -> OutputDiff {
+    let expected_lines = lines_of(expected);
+    let actual_lines = lines_of(actual);
+
+    let line_count = expected_lines.len().max(actual_lines.len());
+    let first_difference = (0..line_count).find(|&i| expected_lines.get(i) != actual_lines.get(i));
+
+    let diff_column = first_difference.map(|i| match (expected_lines.get(i), actual_lines.get(i)) {
+        (Some(e), Some(a)) => {
+            let same = e.chars().zip(a.chars()).take_while(|(x, y)| x == y).count();
+            same as i32 + 1
+        }
+        // 한쪽 출력이 먼저 끝난 경우
+        _ => 1,
+    });
+
+    let start = first_difference.map_or(0, |i| i.saturating_sub(CONTEXT_LINES));
+    OutputDiff {
+        expected: preview(&expected_lines, start),
+        actual: preview(&actual_lines, start),
+        start_line: start as i32 + 1,
+        diff_line: first_difference.map(|i| i as i32 + 1),
+        diff_column,
+    }
+}
+
+fn preview(lines: &[&str], start: usize) -> String {
+    lines
+        .iter()
+        .skip(start)
+        .take(PREVIEW_LINES)
+        .map(|line| {
+            if line.chars().count() <= LINE_CHAR_LIMIT {
+                line.to_string()
+            } else {
+                let mut trimmed: String = line.chars().take(LINE_CHAR_LIMIT).collect();
+                trimmed.push_str("...");
+                trimmed
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 문제에서 공개로 지정한 테스트케이스의 출력 비교 결과를 채운다.
+/// 인터랙티브 문제는 비교할 출력 파일이 없으므로 건너뛴다.
+pub(super) async fn attach(reports: &mut [TestcaseReport], request: &JudgeRequest, workspace: &Path) {
+    if request.interactor.is_some() {
+        return;
+    }
+
+    let testcases = request.testcase_path();
+    for report in reports.iter_mut() {
+        if !request.public_testcases.contains(&report.name) {
+            continue;
+        }
+        let Some(expected) = read_limited(&testcases.join(format!("{}.out", report.name))).await else {
+            continue;
+        };
+        let actual = read_limited(&workspace.join("output").join(format!("{}.out", report.name)))
+            .await
+            .unwrap_or_default();
+        report.output_diff = Some(compare(&expected, &actual));
+    }
+}
+
+async fn read_limited(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).await.ok()?;
+    let mut bytes = Vec::new();
+    file.take(READ_LIMIT_BYTES).read_to_end(&mut bytes).await.ok()?;
+    Some(String::from_utf8_lossy(&bytes).to_string())
+}
@@ -0,0 +1,111 @@
+// 채점 진행 상황 전달 (제출 상세 페이지의 실시간 상태 표시용)
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// 구독자가 따라오지 못할 때 버퍼에 쌓아두는 이벤트 수
+const CHANNEL_CAPACITY: usize = 256;
+
+/// 채점 진행 단계
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JudgeProgress {
+    /// 채점 대기열에 들어감
+    Queued,
+    Compiling,
+    /// `testcase`번째 테스트케이스 실행 중 (1부터)
+    Running { testcase: i32, total: i32 },
+    /// 채점 결과가 저장됨
+    Finished { status: String, score: i32, max_score: i32 },
+}
+
+impl JudgeProgress {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JudgeProgress::Finished { .. })
+    }
+}
+
+/// 채점 백엔드가 진행 상황을 알리는 곳
+pub trait ProgressSink: Send + Sync {
+    fn report(&self, progress: JudgeProgress);
+}
+
+/// 진행 상황을 버린다 (실행 모드 등)
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&self, _progress: JudgeProgress) {}
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmissionProgress {
+    pub submission_id: i64,
+    pub progress: JudgeProgress,
+}
+
+/// 제출별 진행 상황을 구독자에게 전달한다. 늦게 구독한 쪽을 위해 제출별 마지막 상태를 보관한다.
+#[derive(Clone)]
+pub struct ProgressHub {
+    sender: broadcast::Sender<SubmissionProgress>,
+    latest: Arc<Mutex<HashMap<i64, JudgeProgress>>>,
+}
+
+impl Default for ProgressHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            latest: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn publish(&self, submission_id: i64, progress: JudgeProgress) {
+        {
+            let mut latest = self.latest.lock().unwrap();
+            if progress.is_finished() {
+                // 끝난 제출은 DB에서 결과를 읽으면 된다
+                latest.remove(&submission_id);
+            } else {
+                latest.insert(submission_id, progress.clone());
+            }
+        }
+        // 구독자가 없으면 실패하지만 무시해도 된다
+        let _ = self.sender.send(SubmissionProgress { submission_id, progress });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SubmissionProgress> {
+        self.sender.subscribe()
+    }
+
+    /// 진행 중인 제출의 마지막 상태
+    pub fn latest(&self, submission_id: i64) -> Option<JudgeProgress> {
+        self.latest.lock().unwrap().get(&submission_id).cloned()
+    }
+
+    /// 제출 하나의 진행 상황을 이 허브로 보내는 sink
+    pub fn sink(&self, submission_id: i64) -> SubmissionSink {
+        SubmissionSink {
+            hub: self.clone(),
+            submission_id,
+        }
+    }
+}
+
+pub struct SubmissionSink {
+    hub: ProgressHub,
+    submission_id: i64,
+}
+
+impl ProgressSink for SubmissionSink {
+    fn report(&self, progress: JudgeProgress) {
+        self.hub.publish(self.submission_id, progress);
+    }
+}
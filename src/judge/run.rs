@@ -0,0 +1,143 @@
+// 제출 없이 코드를 실행해보는 실행 모드
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tokio::fs;
+use uuid::Uuid;
+
+use super::progress::NoProgress;
+use super::{compile, prepare_workspace, JudgeBackend, JudgeRequest, JudgeResult};
+
+/// 한 번에 실행할 수 있는 입력 수
+pub const MAX_RUN_INPUTS: usize = 10;
+/// 입력 하나의 최대 크기
+pub const MAX_RUN_INPUT_BYTES: usize = 1024 * 1024;
+/// 응답에 담는 stdout/stderr 최대 길이
+const RUN_OUTPUT_LIMIT: usize = 64 * 1024;
+
+/// 실행할 입력 하나. 예제처럼 예상 출력이 있으면 체커로 비교한다.
+#[derive(Debug, Clone)]
+pub struct RunInput {
+    pub input: String,
+    pub expected_output: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunResult {
+    /// `OK`, `COMPILATION_ERROR` 또는 첫 번째로 실패한 입력의 결과
+    pub status: String,
+    pub compile_message: Option<String>,
+    pub cases: Vec<RunCaseResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunCaseResult {
+    pub number: i32,
+    /// 예상 출력이 없는 입력은 정상 종료 시 `OK`
+    pub verdict: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub expected_output: Option<String>,
+    pub exit_code: i32,
+    pub signal: i32,
+    pub execution_time: Option<i32>,
+    pub memory_usage: Option<i32>,
+}
+
+/// 입력마다 프로그램을 실행하고 출력을 돌려준다. 결과는 DB에 저장하지 않는다.
+pub async fn run_code(
+    backend: &dyn JudgeBackend,
+    mut request: JudgeRequest,
+    inputs: Vec<RunInput>,
+) -> anyhow::Result<RunResult> {
+    if inputs.is_empty() || inputs.len() > MAX_RUN_INPUTS {
+        return Err(anyhow::anyhow!("입력은 1개 이상 {}개 이하여야 합니다.", MAX_RUN_INPUTS));
+    }
+    if inputs.iter().any(|i| i.input.len() > MAX_RUN_INPUT_BYTES) {
+        return Err(anyhow::anyhow!("입력이 너무 큽니다."));
+    }
+
+    // 입력 파일은 문제 테스트케이스와 같은 형식(<번호>.in / <번호>.out)으로 저장
+    let input_dir = PathBuf::from(format!("/tmp/judge_run_{}", Uuid::new_v4()));
+    fs::create_dir_all(&input_dir).await?;
+    for (index, input) in inputs.iter().enumerate() {
+        let name = index + 1;
+        fs::write(input_dir.join(format!("{}.in", name)), &input.input).await?;
+        if let Some(expected) = &input.expected_output {
+            fs::write(input_dir.join(format!("{}.out", name)), expected).await?;
+        }
+    }
+    request.custom_testcases = Some(input_dir.canonicalize()?);
+
+    let workspace = match prepare_workspace(&mut request).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&input_dir).await;
+            return Err(e);
+        }
+    };
+
+    let result = match compile::prepare_build(backend, &request, &workspace).await {
+        Ok(Some(failed)) => Ok(failed),
+        Ok(None) => backend.judge(&request, &workspace, &NoProgress).await,
+        Err(e) => Err(e),
+    };
+    let result = match result {
+        Ok(result) => Ok(collect_result(result, &inputs, &workspace).await),
+        Err(e) => Err(e),
+    };
+
+    // 임시 파일 정리
+    let _ = fs::remove_dir_all(&workspace).await;
+    let _ = fs::remove_dir_all(&input_dir).await;
+
+    result
+}
+
+async fn collect_result(result: JudgeResult, inputs: &[RunInput], workspace: &Path) -> RunResult {
+    let output_dir = workspace.join("output");
+    let mut cases = Vec::new();
+    for testcase in &result.testcase_results {
+        let expected_output = inputs
+            .get(testcase.testcase_number as usize - 1)
+            .and_then(|i| i.expected_output.clone());
+        let verdict = match testcase.status.as_str() {
+            // 비교할 출력이 없으면 체커는 통과로 처리한다
+            "ACCEPTED" if expected_output.is_none() => "OK".to_string(),
+            status => status.to_string(),
+        };
+
+        cases.push(RunCaseResult {
+            number: testcase.testcase_number,
+            verdict,
+            stdout: read_truncated(&output_dir.join(format!("{}.out", testcase.testcase_name))).await,
+            stderr: read_truncated(&output_dir.join(format!("{}.err", testcase.testcase_name))).await,
+            expected_output,
+            exit_code: testcase.exit_code,
+            signal: testcase.signal,
+            execution_time: testcase.execution_time,
+            memory_usage: testcase.memory_usage,
+        });
+    }
+
+    let status = match result.status.as_str() {
+        "COMPILATION_ERROR" => result.status.clone(),
+        _ => cases
+            .iter()
+            .find(|c| c.verdict != "OK" && c.verdict != "ACCEPTED")
+            .map(|c| c.verdict.clone())
+            .unwrap_or_else(|| "OK".to_string()),
+    };
+
+    RunResult {
+        status,
+        compile_message: result.compile_message,
+        cases,
+    }
+}
+
+async fn read_truncated(path: &Path) -> String {
+    let bytes = fs::read(path).await.unwrap_or_default();
+    let end = bytes.len().min(RUN_OUTPUT_LIMIT);
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
@@ -0,0 +1,143 @@
+// 런타임 에러 분류 (종료 시그널, 종료 코드, 예외 이름)
+//
+// 숨겨진 테스트케이스의 내용이 드러나지 않도록 프로그램 stderr 전체가 아니라
+// 에러 종류와 예외 메시지 한 줄만 결과에 남긴다.
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+
+use super::TestcaseReport;
+
+/// 저장하는 에러 메시지 최대 길이 (문자 수)
+const MESSAGE_LIMIT: usize = 200;
+/// 예외를 찾기 위해 읽는 stderr 앞/뒤 크기. Java는 앞부분, Python은 뒷부분에 예외가 출력된다.
+const STDERR_SCAN_BYTES: u64 = 32 * 1024;
+
+static JAVA_EXCEPTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^Exception in thread "[^"]*" (?P<class>[\w.$]+)(?::\s?(?P<message>.*))?$"#).unwrap()
+});
+static PYTHON_EXCEPTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<kind>[A-Za-z_][\w.]*)(?::\s?(?P<message>.*))?$").unwrap());
+static CPP_TERMINATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^terminate called after throwing an instance of '(?P<class>[^']+)'(?:\s*\n\s*what\(\):\s*(?P<message>.*))?")
+        .unwrap()
+});
+
+/// 분류된 런타임 에러
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeError {
+    /// `SIGSEGV`, `NON_ZERO_EXIT`, `ArithmeticException`, `ZeroDivisionError` 등
+    pub error_type: String,
+    pub message: String,
+}
+
+/// 종료 시그널, 종료 코드, stderr로 런타임 에러를 분류한다.
+///
+/// 시그널로 종료되었으면 시그널 이름을, 아니면 stderr의 Java/Python 예외 이름을 쓰고,
+/// 둘 다 아니면 `NON_ZERO_EXIT`로 분류한다.
+pub fn classify(signal: i32, exit_code: i32, stderr: &str) -> RuntimeError {
+    if signal != 0 {
+        let (name, description) = signal_name(signal);
+        let mut message = description.to_string();
+        // C++의 잡히지 않은 예외는 SIGABRT로 종료된다
+        if let Some(caps) = CPP_TERMINATE.captures(stderr) {
+            message = format!("{}: {}", message, &caps["class"]);
+            if let Some(what) = caps.name("message") {
+                message = format!("{} ({})", message, what.as_str().trim());
+            }
+        }
+        return RuntimeError {
+            error_type: name,
+            message: trim_message(&message),
+        };
+    }
+
+    if let Some(caps) = JAVA_EXCEPTION.captures(stderr) {
+        let class = &caps["class"];
+        let simple_name = class.rsplit('.').next().unwrap_or(class);
+        return exception(simple_name, caps.name("message").map(|m| m.as_str()));
+    }
+
+    if let Some((kind, message)) = python_exception(stderr) {
+        return exception(kind, message);
+    }
+
+    RuntimeError {
+        error_type: "NON_ZERO_EXIT".to_string(),
+        message: format!("종료 코드 {} 로 종료되었습니다.", exit_code),
+    }
+}
+
+fn signal_name(signal: i32) -> (String, &'static str) {
+    match signal {
+        libc::SIGSEGV => ("SIGSEGV".to_string(), "잘못된 메모리 접근 (Segmentation fault)"),
+        libc::SIGFPE => ("SIGFPE".to_string(), "산술 연산 오류 (0으로 나누기 등)"),
+        libc::SIGABRT => ("SIGABRT".to_string(), "프로그램이 비정상 종료됨 (abort)"),
+        libc::SIGBUS => ("SIGBUS".to_string(), "잘못된 메모리 정렬 또는 접근 (Bus error)"),
+        libc::SIGILL => ("SIGILL".to_string(), "잘못된 명령어 실행"),
+        libc::SIGPIPE => ("SIGPIPE".to_string(), "닫힌 파이프에 쓰기"),
+        libc::SIGKILL => ("SIGKILL".to_string(), "강제 종료됨"),
+        _ => (format!("SIGNAL_{}", signal), "시그널로 종료됨"),
+    }
+}
+
+/// traceback 다음에 나오는 마지막 예외 줄 (`ZeroDivisionError: division by zero`)
+fn python_exception(stderr: &str) -> Option<(&str, Option<&str>)> {
+    let (_, after) = stderr.rsplit_once("Traceback (most recent call last):")?;
+    let line = after
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty() && !line.starts_with(char::is_whitespace))?;
+    let caps = PYTHON_EXCEPTION.captures(line)?;
+    Some((caps.name("kind")?.as_str(), caps.name("message").map(|m| m.as_str())))
+}
+
+fn exception(kind: &str, message: Option<&str>) -> RuntimeError {
+    let message = match message.map(str::trim) {
+        Some(message) if !message.is_empty() => format!("{}: {}", kind, message),
+        _ => kind.to_string(),
+    };
+    RuntimeError {
+        error_type: kind.to_string(),
+        message: trim_message(&message),
+    }
+}
+
+fn trim_message(message: &str) -> String {
+    let message = message.trim();
+    if message.chars().count() <= MESSAGE_LIMIT {
+        return message.to_string();
+    }
+    let mut trimmed: String = message.chars().take(MESSAGE_LIMIT).collect();
+    trimmed.push_str("...");
+    trimmed
+}
+
+/// 런타임 에러로 끝난 테스트케이스의 stderr(`output/<이름>.err`)를 읽어 에러를 분류한다.
+pub(super) async fn attach(reports: &mut [TestcaseReport], workspace: &Path) {
+    for report in reports.iter_mut().filter(|r| r.verdict == "RUNTIME_ERROR") {
+        let path = workspace.join("output").join(format!("{}.err", report.name));
+        let stderr = read_head_and_tail(&path).await.unwrap_or_default();
+        report.runtime_error = Some(classify(report.signal, report.exit_code, &stderr));
+    }
+}
+
+async fn read_head_and_tail(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path).await?;
+    let length = file.metadata().await?.len();
+    if length <= STDERR_SCAN_BYTES * 2 {
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await?;
+        return Ok(String::from_utf8_lossy(&bytes).to_string());
+    }
+
+    let mut head = vec![0; STDERR_SCAN_BYTES as usize];
+    file.read_exact(&mut head).await?;
+    file.seek(SeekFrom::End(-(STDERR_SCAN_BYTES as i64))).await?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).await?;
+    Ok(format!("{}\n{}", String::from_utf8_lossy(&head), String::from_utf8_lossy(&tail)))
+}
@@ -0,0 +1,109 @@
+// 서브태스크 부분 점수 계산 (IOI 스타일)
+use serde::{Deserialize, Serialize};
+
+use super::{JudgeRequest, JudgeResult, SubtaskResultData};
+
+/// 서브태스크를 지정하지 않은 문제의 만점
+pub const DEFAULT_MAX_SCORE: i32 = 100;
+
+/// 문제 front matter의 `subtasks` 항목.
+///
+/// 테스트케이스는 파일 이름 접두어로 묶는다. (`prefix: "sub1_"` 이면 `sub1_01.in`, `sub1_02.in` ...)
+/// ```yaml
+/// scoring: min
+/// subtasks:
+///   - name: "N ≤ 100"
+///     score: 30
+///     prefix: "sub1_"
+///   - score: 70
+///     prefix: "sub2_"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtaskSpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub score: i32,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// 서브태스크 점수 계산 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringMode {
+    /// 서브태스크의 모든 테스트케이스를 통과해야 점수를 받는다
+    #[default]
+    Min,
+    /// 통과한 테스트케이스 비율만큼 점수를 받는다
+    Sum,
+}
+
+/// 테스트케이스 결과로 서브태스크별 점수와 제출 점수를 채운다.
+///
+/// 서브태스크가 없으면 전체 테스트케이스를 만점 100점짜리 서브태스크 하나로 본다.
+/// 테스트케이스 결과가 없는 경우(컴파일 에러 등)는 0점으로 둔다.
+pub(super) fn apply(result: &mut JudgeResult, request: &JudgeRequest) -> anyhow::Result<()> {
+    let implicit = [SubtaskSpec {
+        name: None,
+        score: DEFAULT_MAX_SCORE,
+        prefix: String::new(),
+    }];
+    let subtasks: &[SubtaskSpec] = if request.subtasks.is_empty() {
+        &implicit
+    } else {
+        &request.subtasks
+    };
+
+    result.max_score = subtasks.iter().map(|s| s.score).sum();
+    if result.testcase_results.is_empty() {
+        result.score = 0;
+        return Ok(());
+    }
+
+    let mut subtask_results = Vec::new();
+    for (index, subtask) in subtasks.iter().enumerate() {
+        let testcases: Vec<_> = result
+            .testcase_results
+            .iter()
+            .filter(|t| t.testcase_name.starts_with(&subtask.prefix))
+            .collect();
+        if testcases.is_empty() {
+            return Err(anyhow::anyhow!(
+                "서브태스크 {}에 해당하는 테스트케이스가 없습니다 (prefix: {:?})",
+                index + 1,
+                subtask.prefix
+            ));
+        }
+
+        let total = testcases.len() as i32;
+        let passed = testcases.iter().filter(|t| t.status == "ACCEPTED").count() as i32;
+        let score = match request.scoring {
+            ScoringMode::Min if passed == total => subtask.score,
+            ScoringMode::Min => 0,
+            ScoringMode::Sum => subtask.score * passed / total,
+        };
+        // 첫 번째로 실패한 테스트케이스의 결과가 서브태스크 결과가 된다
+        let status = testcases
+            .iter()
+            .find(|t| t.status != "ACCEPTED")
+            .map(|t| t.status.clone())
+            .unwrap_or_else(|| "ACCEPTED".to_string());
+
+        subtask_results.push(SubtaskResultData {
+            subtask_number: index as i32 + 1,
+            name: subtask.name.clone(),
+            status,
+            score,
+            max_score: subtask.score,
+            total_testcases: total,
+            passed_testcases: passed,
+        });
+    }
+
+    result.score = subtask_results.iter().map(|s| s.score).sum();
+    // 서브태스크를 정의하지 않은 문제는 기존처럼 테스트케이스 결과만 보여준다
+    if !request.subtasks.is_empty() {
+        result.subtask_results = subtask_results;
+    }
+    Ok(())
+}
@@ -0,0 +1,497 @@
+// 제출 채점 파이프라인 (일반 제출 / 대회 제출 공용)
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+
+use crate::judge::progress::{JudgeProgress, ProgressSink};
+use crate::judge::{self, JudgeRequest, JudgeResult};
+use crate::judge_queue::JudgeQueue;
+use crate::problem_repository;
+
+/// 소스 코드 최대 크기 기본값 (`MAX_SOURCE_BYTES`로 변경 가능)
+pub const DEFAULT_MAX_SOURCE_BYTES: usize = 64 * 1024;
+
+static MAX_SOURCE_BYTES: Lazy<usize> = Lazy::new(|| {
+    std::env::var("MAX_SOURCE_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_SOURCE_BYTES)
+});
+
+/// 제출/실행할 수 있는 소스 코드 최대 크기 (바이트)
+pub fn max_source_bytes() -> usize {
+    *MAX_SOURCE_BYTES
+}
+
+/// 새 제출 정보
+#[derive(Debug)]
+pub struct NewSubmission {
+    pub user_id: i64,
+    pub problem_id: i64,
+    pub contest_id: Option<i64>,
+    pub language: String,
+    pub source_code: String,
+}
+
+/// 제출을 저장하고 채점 대기열에 넣는다. 생성된 제출 ID를 반환한다.
+pub async fn create_submission(
+    pool: &SqlitePool,
+    queue: &JudgeQueue,
+    submission: NewSubmission,
+) -> Result<i64, sqlx::Error> {
+    let submission_id: i64 = sqlx::query_scalar(
+        "INSERT INTO submissions (user_id, problem_id, contest_id, language, source_code, status)
+         VALUES (?, ?, ?, ?, ?, 'PENDING') RETURNING id",
+    )
+    .bind(submission.user_id)
+    .bind(submission.problem_id)
+    .bind(submission.contest_id)
+    .bind(&submission.language)
+    .bind(&submission.source_code)
+    .fetch_one(pool)
+    .await?;
+
+    queue.enqueue(submission_id).await?;
+
+    Ok(submission_id)
+}
+
+/// 문제별 제한과 채점 설정을 담은 채점 요청 (메타데이터가 없으면 기본값)
+pub async fn build_request(
+    submission_id: i64,
+    language: String,
+    source_code: String,
+    problem_id: i64,
+) -> JudgeRequest {
+    let mut request = JudgeRequest::new(submission_id, language, source_code, problem_id);
+    if let Ok(problem) = problem_repository::repository().load(problem_id).await {
+        let meta = problem.meta.clone();
+        request.time_limit = meta.time_limit;
+        request.memory_limit = meta.memory_limit;
+        request.output_limit = meta.output_limit;
+        request.checker = meta.checker;
+        request.interactor = meta.interactor;
+        request.subtasks = meta.subtasks;
+        request.scoring = meta.scoring;
+        request.public_testcases = meta.public_testcases;
+
+        // 예제와 같은 테스트케이스도 결과에서 출력을 비교해 보여준다
+        if let Ok(samples) = problem_repository::repository().samples(problem_id).await {
+            for name in problem_repository::repository().sample_testcases(problem_id, &samples).await {
+                if !request.public_testcases.contains(&name) {
+                    request.public_testcases.push(name);
+                }
+            }
+        }
+    }
+    request
+}
+
+/// 채점 결과(로컬 채점 또는 원격 워커가 보낸 결과)를 저장한다.
+/// 채점기 오류는 `SYSTEM_ERROR`로 기록하고 그 오류를 그대로 돌려준다.
+pub async fn record_result(
+    pool: &SqlitePool,
+    submission_id: i64,
+    result: anyhow::Result<JudgeResult>,
+    progress: &dyn ProgressSink,
+) -> anyhow::Result<()> {
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            // 채점기 자체 오류: PENDING으로 남지 않도록 기록
+            sqlx::query(
+                "UPDATE submissions SET status = 'SYSTEM_ERROR', runtime_error_message = ?,
+                 judged_at = CURRENT_TIMESTAMP WHERE id = ?",
+            )
+            .bind(e.to_string())
+            .bind(submission_id)
+            .execute(pool)
+            .await?;
+            progress.report(JudgeProgress::Finished {
+                status: "SYSTEM_ERROR".to_string(),
+                score: 0,
+                max_score: judge::scoring::DEFAULT_MAX_SCORE,
+            });
+            return Err(e);
+        }
+    };
+
+    store_result(pool, submission_id, &result).await?;
+    refresh_user_stats(pool, submission_id).await?;
+    progress.report(JudgeProgress::Finished {
+        status: result.status.clone(),
+        score: result.score,
+        max_score: result.max_score,
+    });
+
+    // 대회 제출인 경우 순위 업데이트
+    let contest_entry: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT contest_id, user_id FROM submissions WHERE id = ? AND contest_id IS NOT NULL",
+    )
+    .bind(submission_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some((contest_id, user_id)) = contest_entry {
+        crate::contest_scoring::update_standings(pool, contest_id, user_id).await?;
+    }
+
+    Ok(())
+}
+
+/// 선택한 제출의 이전 결과를 지우고 다시 채점 대기열에 넣는다.
+pub async fn rejudge_submissions(
+    pool: &SqlitePool,
+    queue: &JudgeQueue,
+    submission_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for &submission_id in submission_ids {
+        for table in ["testcase_results", "compile_errors", "subtask_results"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE submission_id = ?", table))
+                .bind(submission_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query(
+            "UPDATE submissions SET status = 'PENDING', score = 0, execution_time = NULL,
+             memory_usage = NULL, compile_message = NULL, runtime_error_type = NULL,
+             runtime_error_message = NULL, total_testcases = 0, passed_testcases = 0,
+             judged_at = NULL WHERE id = ?",
+        )
+        .bind(submission_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    for &submission_id in submission_ids {
+        queue.enqueue(submission_id).await?;
+    }
+    Ok(())
+}
+
+/// 제출한 사용자의 맞은 문제 수, 제출 수와 연속 해결 일수를 제출 기록에서 다시 계산한다.
+/// (재채점으로 결과가 바뀌거나 정답 트리거가 다시 실행되어도 통계가 맞도록)
+async fn refresh_user_stats(pool: &SqlitePool, submission_id: i64) -> Result<(), sqlx::Error> {
+    let user_id: i64 = sqlx::query_scalar("SELECT user_id FROM submissions WHERE id = ?")
+        .bind(submission_id)
+        .fetch_one(pool)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO user_stats (user_id, total_solved, total_submissions)
+         SELECT user_id,
+                COUNT(DISTINCT CASE WHEN status = 'ACCEPTED' THEN problem_id END),
+                COUNT(*)
+         FROM submissions
+         WHERE user_id = ?
+         GROUP BY user_id
+         ON CONFLICT(user_id) DO UPDATE SET
+             total_solved = excluded.total_solved,
+             total_submissions = excluded.total_submissions",
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    // 연속 해결 일수는 정답 제출을 낸 날짜로 센다 (재채점한 날이 아니라)
+    let solved_dates: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT DATE(created_at) FROM submissions
+         WHERE user_id = ? AND status = 'ACCEPTED'
+         ORDER BY 1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    let solved_dates: Vec<NaiveDate> = solved_dates
+        .iter()
+        .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .collect();
+    let (current_streak, max_streak) = streaks(&solved_dates);
+
+    sqlx::query(
+        "UPDATE user_stats SET current_streak = ?, max_streak = ?, last_solved_date = ?
+         WHERE user_id = ?",
+    )
+    .bind(current_streak)
+    .bind(max_streak)
+    .bind(solved_dates.last().map(|date| date.format("%Y-%m-%d").to_string()))
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 정렬된 해결 날짜에서 (마지막 해결일로 끝나는 연속 일수, 가장 긴 연속 일수)
+fn streaks(dates: &[NaiveDate]) -> (i64, i64) {
+    let mut current = 0;
+    let mut max = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in dates {
+        current = if previous.and_then(|p| p.succ_opt()) == Some(date) { current + 1 } else { 1 };
+        max = max.max(current);
+        previous = Some(date);
+    }
+    (current, max)
+}
+
+/// 채점 결과를 submissions / testcase_results / subtask_results / compile_errors 에 기록
+async fn store_result(
+    pool: &SqlitePool,
+    submission_id: i64,
+    result: &JudgeResult,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // 재채점되는 경우 이전 결과 제거
+    sqlx::query("DELETE FROM testcase_results WHERE submission_id = ?")
+        .bind(submission_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM compile_errors WHERE submission_id = ?")
+        .bind(submission_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM subtask_results WHERE submission_id = ?")
+        .bind(submission_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "UPDATE submissions SET status = ?, score = ?, max_score = ?, execution_time = ?,
+         memory_usage = ?, compile_message = ?, runtime_error_type = ?,
+         runtime_error_message = ?, total_testcases = ?, passed_testcases = ?,
+         judged_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(&result.status)
+    .bind(result.score)
+    .bind(result.max_score)
+    .bind(result.execution_time)
+    .bind(result.memory_usage)
+    .bind(&result.compile_message)
+    .bind(&result.runtime_error_type)
+    .bind(&result.runtime_error_message)
+    .bind(result.total_testcases)
+    .bind(result.passed_testcases)
+    .bind(submission_id)
+    .execute(&mut *tx)
+    .await?;
+
+    for testcase in &result.testcase_results {
+        sqlx::query(
+            "INSERT INTO testcase_results
+             (submission_id, testcase_number, status, execution_time, memory_usage, error_message,
+              expected_output, actual_output, output_start_line, diff_line, diff_column)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(submission_id)
+        .bind(testcase.testcase_number)
+        .bind(&testcase.status)
+        .bind(testcase.execution_time)
+        .bind(testcase.memory_usage)
+        .bind(&testcase.error_message)
+        .bind(&testcase.expected_output)
+        .bind(&testcase.actual_output)
+        .bind(testcase.output_start_line)
+        .bind(testcase.diff_line)
+        .bind(testcase.diff_column)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for subtask in &result.subtask_results {
+        sqlx::query(
+            "INSERT INTO subtask_results
+             (submission_id, subtask_number, name, status, score, max_score, total_testcases, passed_testcases)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(submission_id)
+        .bind(subtask.subtask_number)
+        .bind(&subtask.name)
+        .bind(&subtask.status)
+        .bind(subtask.score)
+        .bind(subtask.max_score)
+        .bind(subtask.total_testcases)
+        .bind(subtask.passed_testcases)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(compile_errors) = &result.compile_errors {
+        for error in compile_errors {
+            sqlx::query(
+                "INSERT INTO compile_errors
+                 (submission_id, file_name, line_number, column_number, severity, error_type, error_message)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(submission_id)
+            .bind(&error.file_name)
+            .bind(error.line_number)
+            .bind(error.column_number)
+            .bind(&error.severity)
+            .bind(&error.error_type)
+            .bind(&error.error_message)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge::progress::NoProgress;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        sqlx::query("INSERT INTO users (username, password_hash) VALUES ('erin', '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn insert_submission(pool: &SqlitePool, contest_id: Option<i64>) -> i64 {
+        sqlx::query_scalar(
+            "INSERT INTO submissions (user_id, problem_id, contest_id, language, source_code)
+             VALUES (1, 1001, ?, 'cpp', 'int main() {}') RETURNING id",
+        )
+        .bind(contest_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    fn result(status: &str, testcases: &[&str]) -> JudgeResult {
+        let passed = testcases.iter().filter(|&&t| t == "ACCEPTED").count();
+        serde_json::from_value(serde_json::json!({
+            "status": status,
+            "score": if status == "ACCEPTED" { 100 } else { 0 },
+            "execution_time": 12,
+            "memory_usage": 2048,
+            "compile_message": null,
+            "runtime_error_type": null,
+            "runtime_error_message": null,
+            "total_testcases": testcases.len(),
+            "passed_testcases": passed,
+            "testcase_results": testcases.iter().enumerate().map(|(i, t)| serde_json::json!({
+                "testcase_number": i + 1,
+                "status": t,
+                "execution_time": 12,
+                "memory_usage": 2048,
+                "error_message": null,
+                "expected_output": null,
+                "actual_output": null,
+            })).collect::<Vec<_>>(),
+            "compile_errors": null,
+        }))
+        .unwrap()
+    }
+
+    async fn testcase_statuses(pool: &SqlitePool, submission_id: i64) -> Vec<String> {
+        sqlx::query_scalar("SELECT status FROM testcase_results WHERE submission_id = ? ORDER BY testcase_number")
+            .bind(submission_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn results_replace_previous_rows_and_update_stats() {
+        let pool = pool().await;
+        let submission_id = insert_submission(&pool, None).await;
+
+        record_result(&pool, submission_id, Ok(result("WRONG_ANSWER", &["ACCEPTED", "WRONG_ANSWER"])), &NoProgress)
+            .await
+            .unwrap();
+        assert_eq!(testcase_statuses(&pool, submission_id).await, vec!["ACCEPTED", "WRONG_ANSWER"]);
+
+        // 재채점 결과는 이전 테스트케이스 결과를 대신한다
+        record_result(&pool, submission_id, Ok(result("ACCEPTED", &["ACCEPTED"])), &NoProgress)
+            .await
+            .unwrap();
+        assert_eq!(testcase_statuses(&pool, submission_id).await, vec!["ACCEPTED"]);
+        let (status, passed): (String, i64) =
+            sqlx::query_as("SELECT status, passed_testcases FROM submissions WHERE id = ?")
+                .bind(submission_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((status.as_str(), passed), ("ACCEPTED", 1));
+        let (solved, submissions): (i64, i64) =
+            sqlx::query_as("SELECT total_solved, total_submissions FROM user_stats WHERE user_id = 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((solved, submissions), (1, 1));
+
+        let failed = judge::compilation_error_result("main.cpp:1:1: error: expected ';'");
+        record_result(&pool, submission_id, Ok(failed), &NoProgress).await.unwrap();
+        assert!(testcase_statuses(&pool, submission_id).await.is_empty());
+        let messages: Vec<String> = sqlx::query_scalar("SELECT error_message FROM compile_errors WHERE submission_id = ?")
+            .bind(submission_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(messages, vec!["main.cpp:1:1: error: expected ';'"]);
+    }
+
+    #[tokio::test]
+    async fn contest_results_update_standings() {
+        let pool = pool().await;
+        let contest_id: i64 = sqlx::query_scalar(
+            "INSERT INTO contests (title, start_time, end_time, contest_type, status, created_by)
+             VALUES ('대회', datetime('now', '-10 minutes'), datetime('now', '+1 hour'), 'ICPC', 'approved', 1)
+             RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO contest_problems (contest_id, problem_id, problem_order) VALUES (?, 1001, 1)")
+            .bind(contest_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO contest_participants (contest_id, user_id) VALUES (?, 1)")
+            .bind(contest_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let submission_id = insert_submission(&pool, Some(contest_id)).await;
+        record_result(&pool, submission_id, Ok(result("ACCEPTED", &["ACCEPTED"])), &NoProgress)
+            .await
+            .unwrap();
+        let solved: i64 = sqlx::query_scalar("SELECT total_score FROM contest_participants WHERE contest_id = ?")
+            .bind(contest_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(solved, 1);
+
+        // 채점기 오류도 제출에 남는다
+        let other = insert_submission(&pool, Some(contest_id)).await;
+        let error = record_result(&pool, other, Err(anyhow::anyhow!("docker is down")), &NoProgress).await;
+        assert!(error.is_err());
+        let (status, message): (String, Option<String>) =
+            sqlx::query_as("SELECT status, runtime_error_message FROM submissions WHERE id = ?")
+                .bind(other)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((status.as_str(), message.as_deref()), ("SYSTEM_ERROR", Some("docker is down")));
+    }
+}
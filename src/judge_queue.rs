@@ -0,0 +1,557 @@
+// DB 기반 채점 대기열과 고정 크기 워커 풀
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+use tokio::sync::{Notify, Semaphore};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::judge::progress::{JudgeProgress, NoProgress, ProgressHub};
+use crate::judge::run::{self, RunInput, RunResult};
+use crate::judge::{self, bundle, JudgeBackend, JudgeRequest, JudgeResult};
+use crate::judge_pipeline;
+use crate::judge_worker::LeasedJob;
+
+/// 새 작업이 없을 때 대기열을 다시 확인하는 주기
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 실행 모드(제출 없이 실행)를 동시에 처리하는 최대 개수
+const MAX_CONCURRENT_RUNS: usize = 2;
+/// 원격 워커가 요청할 수 있는 임대 시간 범위 (초)
+const MIN_LEASE_SECS: u64 = 1;
+const MAX_LEASE_SECS: u64 = 300;
+/// 만료된 임대를 확인하는 주기
+const LEASE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// 워커가 응답 없이 사라진 작업을 다시 시도하는 최대 횟수. 넘으면 SYSTEM_ERROR로 기록한다.
+const MAX_LEASE_ATTEMPTS: i64 = 3;
+
+#[derive(Clone)]
+pub struct JudgeQueue {
+    pool: SqlitePool,
+    backend: Arc<dyn JudgeBackend>,
+    notify: Arc<Notify>,
+    runs: Arc<Semaphore>,
+    progress: ProgressHub,
+    /// 원격 워커 API 토큰 (없으면 원격 워커를 받지 않는다)
+    worker_token: Option<Arc<str>>,
+}
+
+impl JudgeQueue {
+    pub fn new(pool: SqlitePool, backend: Arc<dyn JudgeBackend>, worker_token: Option<String>) -> Self {
+        Self {
+            pool,
+            backend,
+            notify: Arc::new(Notify::new()),
+            runs: Arc::new(Semaphore::new(MAX_CONCURRENT_RUNS)),
+            progress: ProgressHub::new(),
+            worker_token: worker_token.map(Arc::from),
+        }
+    }
+
+    /// 제출별 채점 진행 상황
+    pub fn progress(&self) -> &ProgressHub {
+        &self.progress
+    }
+
+    pub fn worker_token(&self) -> Option<&str> {
+        self.worker_token.as_deref()
+    }
+
+    /// 제출을 만들지 않고 같은 채점 백엔드로 코드를 실행한다. 채점 워커와 별도로 동시 실행 수를 제한한다.
+    pub async fn run_code(&self, request: JudgeRequest, inputs: Vec<RunInput>) -> anyhow::Result<RunResult> {
+        let _permit = self.runs.acquire().await?;
+        run::run_code(self.backend.as_ref(), request, inputs).await
+    }
+
+    /// 제출을 만들지 않고 문제의 모든 테스트케이스로 채점한다 (문제 편집기의 모범 답안 검증).
+    /// 실행 모드와 같은 동시 실행 제한을 쓴다.
+    pub async fn judge_without_submission(&self, request: JudgeRequest) -> anyhow::Result<JudgeResult> {
+        let _permit = self.runs.acquire().await?;
+        judge::judge_submission(self.backend.as_ref(), request, &NoProgress).await
+    }
+
+    /// 제출을 대기열에 넣는다. 이미 들어있으면 다시 대기 상태로 돌린다.
+    /// 채점 중인 작업은 다른 워커가 또 가져가지 않도록 재채점 요청만 표시하고, 지금 채점이 끝나면 다시 넣는다.
+    pub async fn enqueue(&self, submission_id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO judge_queue (submission_id) VALUES (?)
+             ON CONFLICT(submission_id) DO UPDATE SET
+                 status = 'queued', started_at = NULL, enqueued_at = CURRENT_TIMESTAMP
+             WHERE status != 'running'",
+        )
+        .bind(submission_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE judge_queue SET requeue_requested = 1 WHERE submission_id = ? AND status = 'running'")
+            .bind(submission_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.progress.publish(submission_id, JudgeProgress::Queued);
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// 서버 재시작 시 채점이 끝나지 않은 제출을 다시 대기열에 넣는다.
+    pub async fn recover(&self) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE judge_queue SET status = 'queued', started_at = NULL, requeue_requested = 0,
+             worker_id = NULL, lease_token = NULL, lease_expires_at = NULL
+             WHERE status = 'running'",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO judge_queue (submission_id)
+             SELECT id FROM submissions
+             WHERE status IN ('PENDING', 'JUDGING')
+               AND id NOT IN (SELECT submission_id FROM judge_queue)
+             ORDER BY id",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE submissions SET status = 'PENDING' WHERE status = 'JUDGING'")
+            .execute(&mut *tx)
+            .await?;
+
+        let (queued,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM judge_queue")
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(queued as u64)
+    }
+
+    /// 워커 `count`개를 띄운다.
+    pub fn spawn_workers(&self, count: usize) {
+        for worker_id in 0..count {
+            let queue = self.clone();
+            tokio::spawn(async move { queue.run_worker(worker_id).await });
+        }
+    }
+
+    async fn run_worker(self, worker_id: usize) {
+        loop {
+            match self.claim_next().await {
+                Ok(Some((queue_id, submission_id))) => {
+                    if let Err(e) = self.process(queue_id, submission_id).await {
+                        eprintln!("[judge worker {}] submission {} failed: {:?}", worker_id, submission_id, e);
+                    }
+                }
+                Ok(None) => {
+                    let _ = tokio::time::timeout(IDLE_POLL_INTERVAL, self.notify.notified()).await;
+                }
+                Err(e) => {
+                    eprintln!("[judge worker {}] queue error: {:?}", worker_id, e);
+                    tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// 가장 오래된 대기 작업을 하나 가져와 실행 중으로 표시
+    async fn claim_next(&self) -> Result<Option<(i64, i64)>, sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE judge_queue
+             SET status = 'running', started_at = CURRENT_TIMESTAMP, attempts = attempts + 1
+             WHERE id = (SELECT id FROM judge_queue WHERE status = 'queued' ORDER BY id LIMIT 1)
+             RETURNING id, submission_id",
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 채점하고 작업을 대기열에서 뺀 뒤 결과를 저장한다. 결과가 저장되면 `Finished`를 알린다.
+    async fn process(&self, queue_id: i64, submission_id: i64) -> anyhow::Result<()> {
+        let request = match self.start_judging(submission_id).await {
+            Ok(Some(request)) => request,
+            other => {
+                self.finish(queue_id, None).await?;
+                return other.map(|_| ());
+            }
+        };
+
+        let progress = self.progress.sink(submission_id);
+        let result = judge::judge_submission(self.backend.as_ref(), request, &progress).await;
+        if self.finish(queue_id, None).await?.is_none() {
+            return Ok(());
+        }
+        judge_pipeline::record_result(&self.pool, submission_id, result, &progress).await
+    }
+
+    /// 채점이 끝난 작업을 대기열에서 빼고 제출 번호를 돌려준다. (로컬 워커의 작업은 `lease_token`이 없다)
+    ///
+    /// 임대가 만료되어 다른 워커에게 넘어갔거나, 채점 중에 재채점 요청이 들어와 다시 대기열에 넣었으면
+    /// `None`이고 이 채점 결과는 버려야 한다.
+    async fn finish(&self, queue_id: i64, lease_token: Option<&str>) -> Result<Option<i64>, sqlx::Error> {
+        // 임대 확인과 대기열 제거를 한 번에 해서 만료 처리나 재채점 요청과 겹치지 않게 한다
+        let finished: Option<i64> = sqlx::query_scalar(
+            "DELETE FROM judge_queue
+             WHERE id = ? AND lease_token IS ? AND status = 'running' AND requeue_requested = 0
+             RETURNING submission_id",
+        )
+        .bind(queue_id)
+        .bind(lease_token)
+        .fetch_optional(&self.pool)
+        .await?;
+        if finished.is_some() {
+            return Ok(finished);
+        }
+
+        let requeued: Option<i64> = sqlx::query_scalar(
+            "UPDATE judge_queue SET status = 'queued', started_at = NULL, enqueued_at = CURRENT_TIMESTAMP,
+             attempts = 0, requeue_requested = 0, worker_id = NULL, lease_token = NULL, lease_expires_at = NULL
+             WHERE id = ? AND lease_token IS ? AND status = 'running' AND requeue_requested = 1
+             RETURNING submission_id",
+        )
+        .bind(queue_id)
+        .bind(lease_token)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(submission_id) = requeued {
+            sqlx::query("UPDATE submissions SET status = 'PENDING' WHERE id = ?")
+                .bind(submission_id)
+                .execute(&self.pool)
+                .await?;
+            self.progress.publish(submission_id, JudgeProgress::Queued);
+            self.notify.notify_one();
+        }
+        Ok(None)
+    }
+
+    /// 제출을 채점 중으로 표시하고 채점 요청을 만든다. 제출이 삭제되었으면 `None`.
+    async fn start_judging(&self, submission_id: i64) -> anyhow::Result<Option<JudgeRequest>> {
+        let submission: Option<(String, String, i64)> = sqlx::query_as(
+            "SELECT language, source_code, problem_id FROM submissions WHERE id = ?",
+        )
+        .bind(submission_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some((language, source_code, problem_id)) = submission else {
+            return Ok(None);
+        };
+
+        sqlx::query("UPDATE submissions SET status = 'JUDGING' WHERE id = ?")
+            .bind(submission_id)
+            .execute(&self.pool)
+            .await?;
+
+        let request = judge_pipeline::build_request(submission_id, language, source_code, problem_id).await;
+        Ok(Some(request))
+    }
+
+    /// 원격 워커에게 가장 오래된 대기 작업을 `lease_secs`초 동안 빌려준다.
+    /// 작업이 없으면 새 작업이 들어올 때까지 최대 `wait`만큼 기다린다.
+    pub async fn lease(&self, worker_id: &str, lease_secs: u64, wait: Duration) -> anyhow::Result<Option<LeasedJob>> {
+        let lease_secs = lease_secs.clamp(MIN_LEASE_SECS, MAX_LEASE_SECS);
+        let deadline = Instant::now() + wait;
+
+        loop {
+            let lease_token = Uuid::new_v4().to_string();
+            let claimed: Option<(i64, i64)> = sqlx::query_as(
+                "UPDATE judge_queue
+                 SET status = 'running', started_at = CURRENT_TIMESTAMP, attempts = attempts + 1,
+                     worker_id = ?, lease_token = ?, lease_expires_at = datetime('now', ?)
+                 WHERE id = (SELECT id FROM judge_queue WHERE status = 'queued' ORDER BY id LIMIT 1)
+                 RETURNING id, submission_id",
+            )
+            .bind(worker_id)
+            .bind(&lease_token)
+            .bind(format!("+{} seconds", lease_secs))
+            .fetch_optional(&self.pool)
+            .await?;
+
+            let Some((queue_id, submission_id)) = claimed else {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(None);
+                }
+                let _ = tokio::time::timeout(deadline - now, self.notify.notified()).await;
+                continue;
+            };
+
+            let Some(request) = self.start_judging(submission_id).await? else {
+                sqlx::query("DELETE FROM judge_queue WHERE id = ?")
+                    .bind(queue_id)
+                    .execute(&self.pool)
+                    .await?;
+                continue;
+            };
+
+            // 문제 폴더가 없으면 로컬 채점처럼 테스트케이스 없이 채점된다
+            let bundle_hash = bundle::manifest(request.problem_id).await.ok().map(|m| m.hash);
+            return Ok(Some(LeasedJob {
+                queue_id,
+                submission_id,
+                lease_token,
+                bundle_hash,
+                request,
+            }));
+        }
+    }
+
+    /// 임대를 연장하고, 워커가 보낸 진행 상황을 구독자에게 전달한다.
+    /// 임대가 만료되어 다른 워커에게 넘어갔으면 `false`.
+    pub async fn heartbeat(
+        &self,
+        queue_id: i64,
+        lease_token: &str,
+        lease_secs: u64,
+        progress: Option<JudgeProgress>,
+    ) -> Result<bool, sqlx::Error> {
+        let lease_secs = lease_secs.clamp(MIN_LEASE_SECS, MAX_LEASE_SECS);
+        let submission_id: Option<i64> = sqlx::query_scalar(
+            "UPDATE judge_queue SET lease_expires_at = datetime('now', ?)
+             WHERE id = ? AND lease_token = ? AND status = 'running'
+             RETURNING submission_id",
+        )
+        .bind(format!("+{} seconds", lease_secs))
+        .bind(queue_id)
+        .bind(lease_token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(submission_id) = submission_id else {
+            return Ok(false);
+        };
+        // 완료 알림은 결과를 저장한 뒤 서버가 보낸다
+        if let Some(progress) = progress.filter(|p| !p.is_finished()) {
+            self.progress.publish(submission_id, progress);
+        }
+        Ok(true)
+    }
+
+    /// 원격 워커의 채점 결과를 저장한다.
+    /// 임대가 만료되어 다른 워커에게 넘어갔거나 채점 중에 재채점 요청이 들어왔으면 저장하지 않고 `false`.
+    pub async fn complete(
+        &self,
+        queue_id: i64,
+        lease_token: &str,
+        result: anyhow::Result<JudgeResult>,
+    ) -> anyhow::Result<bool> {
+        let Some(submission_id) = self.finish(queue_id, Some(lease_token)).await? else {
+            return Ok(false);
+        };
+
+        let judged = result.is_ok();
+        let progress = self.progress.sink(submission_id);
+        match judge_pipeline::record_result(&self.pool, submission_id, result, &progress).await {
+            Ok(()) => {}
+            // 워커의 채점 오류는 SYSTEM_ERROR로 기록되었다
+            Err(e) if !judged => eprintln!("[judge worker] submission {} failed: {:?}", submission_id, e),
+            Err(e) => return Err(e),
+        }
+        Ok(true)
+    }
+
+    /// 만료된 임대를 주기적으로 대기열에 되돌리는 작업을 띄운다.
+    pub fn spawn_lease_reaper(&self) {
+        let queue = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(LEASE_CHECK_INTERVAL).await;
+                if let Err(e) = queue.requeue_expired().await {
+                    eprintln!("[judge queue] failed to requeue expired leases: {:?}", e);
+                }
+            }
+        });
+    }
+
+    /// 하트비트가 끊긴 원격 작업을 다시 대기열에 넣는다. 되돌린 작업 수를 반환한다.
+    pub async fn requeue_expired(&self) -> anyhow::Result<u64> {
+        let expired: Vec<(i64, i64, i64)> = sqlx::query_as(
+            "SELECT id, submission_id, attempts FROM judge_queue
+             WHERE status = 'running' AND lease_expires_at < datetime('now')",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut requeued = 0;
+        for (queue_id, submission_id, attempts) in expired {
+            if attempts >= MAX_LEASE_ATTEMPTS {
+                let removed = sqlx::query(
+                    "DELETE FROM judge_queue WHERE id = ? AND lease_expires_at < datetime('now')",
+                )
+                .bind(queue_id)
+                .execute(&self.pool)
+                .await?;
+                if removed.rows_affected() == 1 {
+                    let error = anyhow::anyhow!("채점 워커가 {}번 응답하지 않았습니다.", attempts);
+                    let progress = self.progress.sink(submission_id);
+                    let _ = judge_pipeline::record_result(&self.pool, submission_id, Err(error), &progress).await;
+                }
+                continue;
+            }
+
+            let updated = sqlx::query(
+                "UPDATE judge_queue SET status = 'queued', started_at = NULL, requeue_requested = 0,
+                 worker_id = NULL, lease_token = NULL, lease_expires_at = NULL
+                 WHERE id = ? AND lease_expires_at < datetime('now')",
+            )
+            .bind(queue_id)
+            .execute(&self.pool)
+            .await?;
+            if updated.rows_affected() == 1 {
+                sqlx::query("UPDATE submissions SET status = 'PENDING' WHERE id = ?")
+                    .bind(submission_id)
+                    .execute(&self.pool)
+                    .await?;
+                self.progress.publish(submission_id, JudgeProgress::Queued);
+                requeued += 1;
+            }
+        }
+
+        if requeued > 0 {
+            self.notify.notify_one();
+        }
+        Ok(requeued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge::FakeBackend;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// 마이그레이션한 인메모리 DB에 `submissions`개의 제출을 넣은 대기열 (워커는 띄우지 않는다)
+    async fn queue_with_submissions(submissions: usize) -> (JudgeQueue, Vec<i64>) {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        sqlx::query("INSERT INTO users (username, password_hash) VALUES ('queue', '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for _ in 0..submissions {
+            let id: i64 = sqlx::query_scalar(
+                "INSERT INTO submissions (user_id, problem_id, language, source_code)
+                 VALUES (1, 1001, 'cpp', 'int main() {}') RETURNING id",
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+        (JudgeQueue::new(pool, Arc::new(FakeBackend::new()), None), ids)
+    }
+
+    async fn queue_row(queue: &JudgeQueue, submission_id: i64) -> Option<(String, i64, i64)> {
+        sqlx::query_as("SELECT status, attempts, requeue_requested FROM judge_queue WHERE submission_id = ?")
+            .bind(submission_id)
+            .fetch_optional(&queue.pool)
+            .await
+            .unwrap()
+    }
+
+    async fn submission_status(queue: &JudgeQueue, submission_id: i64) -> String {
+        sqlx::query_scalar("SELECT status FROM submissions WHERE id = ?")
+            .bind(submission_id)
+            .fetch_one(&queue.pool)
+            .await
+            .unwrap()
+    }
+
+    /// 임대가 이미 끝난 것처럼 만료 시각을 과거로 돌린다
+    async fn expire_leases(queue: &JudgeQueue) {
+        sqlx::query("UPDATE judge_queue SET lease_expires_at = datetime('now', '-1 seconds') WHERE status = 'running'")
+            .execute(&queue.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn claims_jobs_in_order_and_requeues_rejudged_running_jobs() {
+        let (queue, ids) = queue_with_submissions(2).await;
+        for &id in &ids {
+            queue.enqueue(id).await.unwrap();
+        }
+
+        let (first_queue_id, first) = queue.claim_next().await.unwrap().unwrap();
+        assert_eq!(first, ids[0]);
+        assert_eq!(queue_row(&queue, first).await, Some(("running".to_string(), 1, 0)));
+        let (second_queue_id, second) = queue.claim_next().await.unwrap().unwrap();
+        assert_eq!(second, ids[1]);
+        assert!(queue.claim_next().await.unwrap().is_none());
+
+        // 채점 중에 들어온 재채점 요청은 작업을 다시 내주지 않고 표시만 한다
+        queue.enqueue(first).await.unwrap();
+        assert_eq!(queue_row(&queue, first).await, Some(("running".to_string(), 1, 1)));
+        assert!(queue.claim_next().await.unwrap().is_none());
+
+        // 재채점 전에 시작한 채점은 버리고 다시 대기열에 넣는다
+        assert_eq!(queue.finish(first_queue_id, None).await.unwrap(), None);
+        assert_eq!(queue_row(&queue, first).await, Some(("queued".to_string(), 0, 0)));
+        assert_eq!(submission_status(&queue, first).await, "PENDING");
+
+        assert_eq!(queue.finish(second_queue_id, None).await.unwrap(), Some(second));
+        assert_eq!(queue_row(&queue, second).await, None);
+
+        let (requeued_id, requeued) = queue.claim_next().await.unwrap().unwrap();
+        assert_eq!(requeued, first);
+        assert_eq!(queue.finish(requeued_id, None).await.unwrap(), Some(first));
+        assert!(queue.claim_next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn leases_need_their_token_and_expired_ones_are_requeued() {
+        let (queue, ids) = queue_with_submissions(1).await;
+        queue.enqueue(ids[0]).await.unwrap();
+
+        let job = queue.lease("w1", 30, Duration::ZERO).await.unwrap().unwrap();
+        assert_eq!(job.submission_id, ids[0]);
+        assert_eq!(submission_status(&queue, ids[0]).await, "JUDGING");
+        assert!(queue.lease("w2", 30, Duration::ZERO).await.unwrap().is_none());
+        assert!(queue.heartbeat(job.queue_id, &job.lease_token, 30, None).await.unwrap());
+        assert!(!queue.heartbeat(job.queue_id, "other", 30, None).await.unwrap());
+
+        // 살아 있는 임대는 건드리지 않는다
+        assert_eq!(queue.requeue_expired().await.unwrap(), 0);
+
+        expire_leases(&queue).await;
+        assert_eq!(queue.requeue_expired().await.unwrap(), 1);
+        assert_eq!(queue_row(&queue, ids[0]).await, Some(("queued".to_string(), 1, 0)));
+        assert_eq!(submission_status(&queue, ids[0]).await, "PENDING");
+
+        // 만료된 임대의 하트비트와 결과는 받지 않는다
+        assert!(!queue.heartbeat(job.queue_id, &job.lease_token, 30, None).await.unwrap());
+        assert_eq!(queue.finish(job.queue_id, Some(&job.lease_token)).await.unwrap(), None);
+
+        let retry = queue.lease("w2", 30, Duration::ZERO).await.unwrap().unwrap();
+        assert_eq!(queue.finish(retry.queue_id, None).await.unwrap(), None);
+        assert_eq!(queue.finish(retry.queue_id, Some(&retry.lease_token)).await.unwrap(), Some(ids[0]));
+    }
+
+    #[tokio::test]
+    async fn jobs_abandoned_too_often_become_system_errors() {
+        let (queue, ids) = queue_with_submissions(1).await;
+        queue.enqueue(ids[0]).await.unwrap();
+
+        for attempt in 1..MAX_LEASE_ATTEMPTS {
+            queue.lease("ghost", 30, Duration::ZERO).await.unwrap().unwrap();
+            expire_leases(&queue).await;
+            assert_eq!(queue.requeue_expired().await.unwrap(), 1);
+            assert_eq!(queue_row(&queue, ids[0]).await, Some(("queued".to_string(), attempt, 0)));
+        }
+
+        queue.lease("ghost", 30, Duration::ZERO).await.unwrap().unwrap();
+        expire_leases(&queue).await;
+        assert_eq!(queue.requeue_expired().await.unwrap(), 0);
+        assert_eq!(queue_row(&queue, ids[0]).await, None);
+        assert_eq!(submission_status(&queue, ids[0]).await, "SYSTEM_ERROR");
+    }
+}
@@ -0,0 +1,347 @@
+// 원격 채점 워커: 웹 서버의 채점 대기열에서 작업을 빌려 채점하고 결과를 돌려보낸다.
+//
+// 서버와는 HTTP(JSON)로 통신한다.
+//   POST /api/judge/lease                       작업 임대 (없으면 204)
+//   POST /api/judge/jobs/:id/heartbeat          임대 연장 + 진행 상황 전달 (임대를 잃었으면 409)
+//   POST /api/judge/jobs/:id/result             결과 보고 (임대를 잃었으면 409)
+//   GET  /api/judge/problems/:id/bundle         문제 번들 목록
+//   GET  /api/judge/problems/:id/files/*path    번들 파일
+// 워커가 하트비트 없이 임대 시간을 넘기면 서버가 작업을 다시 대기열에 넣는다.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::judge::bundle::{self, BundleManifest};
+use crate::judge::progress::{JudgeProgress, ProgressSink};
+use crate::judge::{self, JudgeBackend, JudgeRequest, JudgeResult};
+
+/// 기본 임대 시간 (초). 하트비트는 이 시간의 1/3마다 보낸다.
+const DEFAULT_LEASE_SECS: u64 = 30;
+/// 작업이 없을 때 서버에서 기다리는 시간 (롱 폴링)
+const LEASE_WAIT_SECS: u64 = 10;
+/// 서버에 연결하지 못했을 때 다시 시도하기 전 대기 시간
+const RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+// --- 프로토콜 ---
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeaseRequest {
+    pub worker_id: String,
+    pub lease_secs: u64,
+    /// 작업이 없을 때 기다릴 시간 (초)
+    #[serde(default)]
+    pub wait_secs: u64,
+}
+
+/// 워커에게 빌려준 채점 작업
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeasedJob {
+    pub queue_id: i64,
+    pub submission_id: i64,
+    pub lease_token: String,
+    /// 문제 번들 해시 (문제 폴더가 없으면 `None`)
+    pub bundle_hash: Option<String>,
+    pub request: JudgeRequest,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeartbeatRequest {
+    pub lease_token: String,
+    pub lease_secs: u64,
+    #[serde(default)]
+    pub progress: Option<JudgeProgress>,
+}
+
+/// 채점 결과. 워커에서 채점이 실패했으면 `error`에 이유를 담는다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResultRequest {
+    pub lease_token: String,
+    #[serde(default)]
+    pub result: Option<JudgeResult>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// --- 워커 ---
+
+pub struct WorkerConfig {
+    /// 웹 서버 주소 (예: `http://127.0.0.1:3000`)
+    pub server_url: String,
+    pub token: String,
+    pub worker_id: String,
+    /// 문제 번들 캐시 위치
+    pub cache_dir: PathBuf,
+    pub lease_secs: u64,
+}
+
+impl WorkerConfig {
+    /// `JUDGE_SERVER_URL`, `JUDGE_WORKER_TOKEN`(필수), `JUDGE_WORKER_ID`, `WORKER_CACHE_DIR`, `JUDGE_LEASE_SECS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        let token = std::env::var("JUDGE_WORKER_TOKEN")
+            .map_err(|_| anyhow::anyhow!("JUDGE_WORKER_TOKEN must be set"))?;
+        Ok(Self {
+            server_url: std::env::var("JUDGE_SERVER_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string()),
+            token,
+            worker_id: std::env::var("JUDGE_WORKER_ID").unwrap_or_else(|_| format!("worker-{}", Uuid::new_v4())),
+            cache_dir: std::env::var("WORKER_CACHE_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("/tmp/nekonic_worker_cache")),
+            lease_secs: std::env::var("JUDGE_LEASE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_LEASE_SECS),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoteWorker {
+    config: Arc<WorkerConfig>,
+    client: Client,
+    backend: Arc<dyn JudgeBackend>,
+}
+
+impl RemoteWorker {
+    pub fn new(config: WorkerConfig, backend: Arc<dyn JudgeBackend>) -> Self {
+        Self {
+            config: Arc::new(config),
+            client: Client::new(),
+            backend,
+        }
+    }
+
+    /// 작업을 계속 받아 채점한다.
+    pub async fn run(self) {
+        loop {
+            if let Err(e) = self.run_once().await {
+                eprintln!("[judge worker {}] {:?}", self.config.worker_id, e);
+                tokio::time::sleep(RETRY_INTERVAL).await;
+            }
+        }
+    }
+
+    /// 작업 하나를 빌려 채점하고 결과를 보낸다. 빌릴 작업이 없었으면 `false`.
+    pub async fn run_once(&self) -> anyhow::Result<bool> {
+        let Some(job) = self.lease().await? else {
+            return Ok(false);
+        };
+
+        let LeasedJob { queue_id, lease_token, bundle_hash, request, .. } = job;
+        let result = self.judge(queue_id, &lease_token, bundle_hash.is_some(), request).await;
+        self.report(queue_id, &lease_token, result).await?;
+        Ok(true)
+    }
+
+    async fn lease(&self) -> anyhow::Result<Option<LeasedJob>> {
+        let response = self
+            .client
+            .post(self.url(&["api", "judge", "lease"])?)
+            .bearer_auth(&self.config.token)
+            .json(&LeaseRequest {
+                worker_id: self.config.worker_id.clone(),
+                lease_secs: self.config.lease_secs,
+                wait_secs: LEASE_WAIT_SECS,
+            })
+            .send()
+            .await?
+            .error_for_status()?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        Ok(Some(response.json().await?))
+    }
+
+    async fn judge(
+        &self,
+        queue_id: i64,
+        lease_token: &str,
+        has_bundle: bool,
+        mut request: JudgeRequest,
+    ) -> anyhow::Result<JudgeResult> {
+        request.problem_root = Some(if has_bundle {
+            self.ensure_bundle(request.problem_id).await?
+        } else {
+            // 서버에도 문제 폴더가 없으면 테스트케이스 없이 채점된다
+            self.config.cache_dir.join("missing")
+        });
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let heartbeat = tokio::spawn(self.clone().heartbeat_loop(queue_id, lease_token.to_string(), receiver));
+        let result = judge::judge_submission(self.backend.as_ref(), request, &ChannelProgress(sender)).await;
+        heartbeat.abort();
+        result
+    }
+
+    /// 채점 중에는 임대를 주기적으로 연장하고, 진행 상황이 바뀌면 바로 알린다.
+    async fn heartbeat_loop(self, queue_id: i64, lease_token: String, mut receiver: mpsc::UnboundedReceiver<JudgeProgress>) {
+        let mut interval = tokio::time::interval(Duration::from_secs((self.config.lease_secs / 3).max(1)));
+        // 첫 틱은 바로 끝난다
+        interval.tick().await;
+
+        loop {
+            let progress = tokio::select! {
+                _ = interval.tick() => None,
+                progress = receiver.recv() => match progress {
+                    Some(progress) => Some(progress),
+                    None => return,
+                },
+            };
+
+            match self.heartbeat(queue_id, &lease_token, progress).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("[judge worker {}] lease for job {} was lost", self.config.worker_id, queue_id);
+                    return;
+                }
+                // 일시적인 오류는 다음 주기에 다시 시도한다
+                Err(e) => eprintln!("[judge worker {}] heartbeat failed: {:?}", self.config.worker_id, e),
+            }
+        }
+    }
+
+    async fn heartbeat(&self, queue_id: i64, lease_token: &str, progress: Option<JudgeProgress>) -> anyhow::Result<bool> {
+        let response = self
+            .client
+            .post(self.url(&["api", "judge", "jobs", &queue_id.to_string(), "heartbeat"])?)
+            .bearer_auth(&self.config.token)
+            .json(&HeartbeatRequest {
+                lease_token: lease_token.to_string(),
+                lease_secs: self.config.lease_secs,
+                progress,
+            })
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::CONFLICT {
+            return Ok(false);
+        }
+        response.error_for_status()?;
+        Ok(true)
+    }
+
+    async fn report(&self, queue_id: i64, lease_token: &str, result: anyhow::Result<JudgeResult>) -> anyhow::Result<()> {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+
+        let response = self
+            .client
+            .post(self.url(&["api", "judge", "jobs", &queue_id.to_string(), "result"])?)
+            .bearer_auth(&self.config.token)
+            .json(&ResultRequest {
+                lease_token: lease_token.to_string(),
+                result,
+                error,
+            })
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::CONFLICT {
+            // 임대가 만료되어 다른 워커가 채점한다
+            eprintln!("[judge worker {}] result for job {} was rejected", self.config.worker_id, queue_id);
+            return Ok(());
+        }
+        response.error_for_status()?;
+        Ok(())
+    }
+
+    /// 문제 번들을 `<cache>/problems/<id>/<hash>`에 받아 둔다. 이미 있으면 그대로 쓴다.
+    async fn ensure_bundle(&self, problem_id: i64) -> anyhow::Result<PathBuf> {
+        // 임대 이후 문제가 바뀌었을 수 있으므로 최신 목록을 받는다
+        let manifest: BundleManifest = self
+            .client
+            .get(self.url(&["api", "judge", "problems", &problem_id.to_string(), "bundle"])?)
+            .bearer_auth(&self.config.token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if manifest.problem_id != problem_id || !manifest.is_consistent() {
+            return Err(anyhow::anyhow!("Bundle manifest for problem {} is inconsistent", problem_id));
+        }
+
+        let bundle_dir = self
+            .config
+            .cache_dir
+            .join("problems")
+            .join(problem_id.to_string())
+            .join(&manifest.hash);
+        if bundle_dir.is_dir() {
+            return Ok(bundle_dir);
+        }
+
+        // 다 받은 뒤에 옮겨서 받다 만 번들이 캐시에 남지 않게 한다
+        let staging = self.config.cache_dir.join(format!(".staging_{}", Uuid::new_v4()));
+        let downloaded = self.download_bundle(&manifest, &staging).await;
+        let result = match downloaded {
+            Ok(()) => {
+                fs::create_dir_all(bundle_dir.parent().unwrap()).await?;
+                match fs::rename(&staging, &bundle_dir).await {
+                    Ok(()) => Ok(bundle_dir),
+                    // 다른 작업이 먼저 받아 둔 경우
+                    Err(_) if bundle_dir.is_dir() => Ok(bundle_dir),
+                    Err(e) => Err(e.into()),
+                }
+            }
+            Err(e) => Err(e),
+        };
+        let _ = fs::remove_dir_all(&staging).await;
+        result
+    }
+
+    async fn download_bundle(&self, manifest: &BundleManifest, staging: &Path) -> anyhow::Result<()> {
+        let problem_id = manifest.problem_id.to_string();
+        for file in &manifest.files {
+            let mut segments = vec!["api", "judge", "problems", problem_id.as_str(), "files"];
+            segments.extend(file.path.split('/'));
+
+            let bytes = self
+                .client
+                .get(self.url(&segments)?)
+                .bearer_auth(&self.config.token)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+            if bytes.len() as u64 != file.size || bundle::content_hash(&bytes) != file.sha256 {
+                return Err(anyhow::anyhow!("Bundle file {} failed hash verification", file.path));
+            }
+
+            let target = staging.join(&file.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&target, &bytes).await?;
+        }
+        Ok(())
+    }
+
+    /// 경로 조각은 각각 퍼센트 인코딩된다.
+    fn url(&self, segments: &[&str]) -> anyhow::Result<Url> {
+        let mut url = Url::parse(&self.config.server_url)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid JUDGE_SERVER_URL: {}", self.config.server_url))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+}
+
+/// 진행 상황을 하트비트 작업으로 넘긴다.
+struct ChannelProgress(mpsc::UnboundedSender<JudgeProgress>);
+
+impl ProgressSink for ChannelProgress {
+    fn report(&self, progress: JudgeProgress) {
+        let _ = self.0.send(progress);
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use axum::Router;
+use axum_login::AuthManagerLayerBuilder;
+use sqlx::SqlitePool;
+use tera::Tera;
+use time::Duration;
+use tower_sessions::{Expiry, SessionManagerLayer};
+use tower_sessions_sqlx_store::SqliteStore;
+use crate::auth::Backend;
+use crate::judge::{language, JudgeBackend};
+use crate::judge_queue::JudgeQueue;
+
+pub mod router;
+pub mod auth;
+pub mod judge;
+pub mod judge_pipeline;
+pub mod judge_queue;
+pub mod judge_worker;
+pub mod error;
+pub mod models;
+pub mod handlers;
+pub mod middleware;
+pub mod contest_scoring;
+pub mod problem_repository;
+pub mod problem_access;
+
+#[derive(Clone, axum::extract::FromRef)]
+pub struct AppState {
+    pub tera: Tera,
+    pub db_pool: SqlitePool,
+    pub judge_queue: JudgeQueue,
+}
+
+/// 마이그레이션, 채점 워커, 세션/인증 레이어까지 포함한 애플리케이션 라우터 생성
+///
+/// `worker_token`이 있으면 원격 채점 워커 API를 연다.
+pub async fn build_app(
+    db_pool: SqlitePool,
+    judge_backend: Arc<dyn JudgeBackend>,
+    judge_workers: usize,
+    worker_token: Option<String>,
+) -> anyhow::Result<Router> {
+    // 애플리케이션 DB 마이그레이션 실행
+    sqlx::migrate!().run(&db_pool).await?;
+    println!("✅ Application migrations complete.");
+
+    // 채점 언어 레지스트리 로드
+    language::init_from_env()?;
+    println!("✅ Language registry loaded ({} languages).", language::registry().all().len());
+
+    // 문제 인덱스를 디스크와 맞춤
+    let synced = problem_repository::repository().sync_index(&db_pool).await?;
+    println!("✅ Problem index synced ({} problems, {} updated, {} removed).", synced.total, synced.updated, synced.removed);
+
+    // 채점 대기열 복구 및 워커 실행
+    let remote_workers = worker_token.is_some();
+    let judge_queue = JudgeQueue::new(db_pool.clone(), judge_backend, worker_token);
+    let recovered = judge_queue.recover().await?;
+    judge_queue.spawn_workers(judge_workers);
+    judge_queue.spawn_lease_reaper();
+    println!(
+        "✅ Judge queue started ({} workers, {} queued, remote workers {}).",
+        judge_workers,
+        recovered,
+        if remote_workers { "enabled" } else { "disabled" }
+    );
+
+    // 세션 저장소 설정 및 마이그레이션
+    let session_store = SqliteStore::new(db_pool.clone());
+    session_store.migrate().await?;
+    println!("✅ Session table migrations complete.");
+
+    // 세션 레이어 생성
+    let session_layer = SessionManagerLayer::new(session_store)
+        .with_expiry(Expiry::OnInactivity(Duration::days(7)));
+
+    // 인증 백엔드 및 레이어 설정
+    let auth_backend = Backend::new(db_pool.clone());
+    let auth_layer = AuthManagerLayerBuilder::new(auth_backend, session_layer).build();
+
+    // Tera 템플릿 엔진 설정
+    let tera = Tera::new("templates/**/*")?;
+
+    // 애플리케이션 상태(State) 생성
+    let app_state = AppState { tera, db_pool, judge_queue };
+
+    // 라우터 빌드
+    Ok(router::create_router().with_state(app_state).layer(auth_layer))
+}
@@ -1,29 +1,8 @@
 use std::net::SocketAddr;
 use std::str::FromStr;
-use axum_login::AuthManagerLayerBuilder;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::SqlitePool;
-use tera::Tera;
-use time::Duration;
-use tower_sessions::{Expiry, SessionManagerLayer};
-use tower_sessions_sqlx_store::SqliteStore;
 use dotenvy::dotenv;
-use crate::auth::Backend;
-
-mod router;
-mod auth;
-mod judge;
-mod error;
-mod models;
-mod handlers;
-mod middleware;
-mod contest_scoring;
-
-#[derive(Clone, axum::extract::FromRef)]
-pub struct AppState {
-    pub tera: Tera,
-    pub db_pool: SqlitePool,
-}
+use nekonic_oj::{build_app, judge};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -42,31 +21,17 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     println!("✅ Database connected successfully.");
 
-    // 애플리케이션 DB 마이그레이션 실행
-    sqlx::migrate!().run(&db_pool).await?;
-    println!("✅ Application migrations complete.");
-
-    // 세션 저장소 설정 및 마이그레이션
-    let session_store = SqliteStore::new(db_pool.clone());
-    session_store.migrate().await?;
-    println!("✅ Session table migrations complete.");
-
-    // 세션 레이어 생성
-    let session_layer = SessionManagerLayer::new(session_store)
-        .with_expiry(Expiry::OnInactivity(Duration::days(7)));
-
-    // 인증 백엔드 및 레이어 설정
-    let auth_backend = Backend::new(db_pool.clone());
-    let auth_layer = AuthManagerLayerBuilder::new(auth_backend, session_layer).build();
-
-    // Tera 템플릿 엔진 설정
-    let tera = Tera::new("templates/**/*")?;
-
-    // 애플리케이션 상태(State) 생성
-    let app_state = AppState { tera, db_pool };
-
-    // 라우터 빌드
-    let app = router::create_router().with_state(app_state).layer(auth_layer);
+    // 채점 설정
+    let judge_workers = std::env::var("JUDGE_WORKERS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(2);
+    let worker_token = std::env::var("JUDGE_WORKER_TOKEN").ok().filter(|t| !t.is_empty());
+    // 원격 워커 없이 로컬 워커까지 0이면 아무도 채점하지 않는다
+    let judge_workers = if worker_token.is_some() { judge_workers } else { judge_workers.max(1) };
+    let judge_backend = judge::backend_from_env()?;
+
+    let app = build_app(db_pool, judge_backend, judge_workers, worker_token).await?;
 
     // 서버 실행
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -20,6 +20,19 @@ pub async fn require_admin(
     }
 }
 
+/// 문제 편집 권한 체크 미들웨어
+pub async fn require_problem_editor(
+    auth_session: AuthSession<Backend>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match auth_session.user {
+        Some(user) if user.can_edit_problems() => next.run(request).await,
+        Some(_) => (StatusCode::FORBIDDEN, "문제 편집 권한이 필요합니다").into_response(),
+        None => (StatusCode::UNAUTHORIZED, "로그인이 필요합니다").into_response(),
+    }
+}
+
 /// 로그인 확인 미들웨어
 pub async fn require_auth(
     auth_session: AuthSession<Backend>,
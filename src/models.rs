@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::judge::checker::{deserialize_checker, CheckerSpec};
+use crate::judge::scoring::{ScoringMode, SubtaskSpec};
+use crate::problem_repository::samples::Sample;
+use crate::problem_repository::testcases::TestcaseFile;
+
 // Helper function for deserializing empty string as None
 fn deserialize_optional_i32<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
 where
@@ -13,15 +18,42 @@ where
     }
 }
 
+// Helper function for deserializing empty string as None
+fn deserialize_optional_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) if s.trim().is_empty() => Ok(None),
+        Some(s) => s.trim().parse::<i64>().map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
 // --- Problem Models ---
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct ProblemListItem {
     pub id: i64,
     pub title: String,
     pub accuracy: f64,
 }
 
+/// 문제 목록에 나오지 않는(대회 전용, 작성 중) 문제. 편집할 수 있는 사람에게만 따로 보여준다.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct UnlistedProblem {
+    pub id: i64,
+    pub title: String,
+    pub visibility: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProblemListQuery {
+    /// 1부터 시작하는 페이지 번호
+    pub page: Option<i64>,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct ProblemStats {
     pub problem_id: i64,
@@ -45,14 +77,13 @@ pub struct ProblemDetail {
     pub id: i64,
     pub meta: ProblemMeta,
     pub content: String,
-    pub example_inputs: Vec<String>,
-    pub example_outputs: Vec<String>,
+    pub samples: Vec<Sample>,
     pub total_submits: i64,
     pub correct_submits: i64,
     pub accuracy: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FrontMatter {
     pub title: String,
     #[serde(deserialize_with = "deserialize_time_limit")]
@@ -60,6 +91,74 @@ pub struct FrontMatter {
     #[serde(deserialize_with = "deserialize_memory_limit")]
     pub memory_limit: u32,    // MB 단위
     pub tags: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_checker")]
+    pub checker: CheckerSpec,
+    /// 인터랙티브 문제의 인터랙터 소스 (예: `interactor.cpp`)
+    #[serde(default)]
+    pub interactor: Option<String>,
+    /// 테스트 입력 검증기 소스 (예: `validator.cpp`)
+    #[serde(default)]
+    pub validator: Option<String>,
+    /// 서브태스크 (없으면 전체 정답일 때만 100점)
+    #[serde(default)]
+    pub subtasks: Vec<SubtaskSpec>,
+    #[serde(default)]
+    pub scoring: ScoringMode,
+    /// 제출 결과에 예상 출력과 실행 결과를 보여줄 테스트케이스 이름 (예: `["1", "2"]`)
+    #[serde(default)]
+    pub public_testcases: Vec<String>,
+    /// 테스트케이스 하나의 출력 제한 (MB 단위, 메모리 제한과 같은 형식)
+    #[serde(default = "default_output_limit", deserialize_with = "deserialize_memory_limit")]
+    pub output_limit: u32,
+    /// 공개 범위. `public`이 아니면 문제 목록에 나오지 않는다
+    #[serde(default)]
+    pub visibility: ProblemVisibility,
+}
+
+impl FrontMatter {
+    /// 문제 편집기에서 새로 만드는 문제의 기본 설정 (1초, 256MB, 작성 중)
+    pub fn new(title: String) -> Self {
+        Self {
+            title,
+            time_limit: 1000,
+            memory_limit: 256,
+            tags: Vec::new(),
+            checker: CheckerSpec::default(),
+            interactor: None,
+            validator: None,
+            subtasks: Vec::new(),
+            scoring: ScoringMode::default(),
+            public_testcases: Vec::new(),
+            output_limit: default_output_limit(),
+            visibility: ProblemVisibility::Draft,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemVisibility {
+    /// 누구나 볼 수 있다
+    #[default]
+    Public,
+    /// 대회 전용: 진행 중인 대회의 참가자만 대회 문제 페이지로 볼 수 있다
+    Hidden,
+    /// 작성 중: 문제를 편집할 수 있는 사람만 볼 수 있다
+    Draft,
+}
+
+impl ProblemVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProblemVisibility::Public => "public",
+            ProblemVisibility::Hidden => "hidden",
+            ProblemVisibility::Draft => "draft",
+        }
+    }
+}
+
+fn default_output_limit() -> u32 {
+    crate::judge::DEFAULT_OUTPUT_LIMIT_MB
 }
 
 // 시간 제한 파싱 (문자열 또는 숫자 모두 허용)
@@ -123,6 +222,54 @@ where
     }
 }
 
+/// 문제 편집기의 front matter / 지문 저장 폼
+#[derive(Debug, Deserialize)]
+pub struct ProblemEditForm {
+    pub title: String,
+    pub time_limit: u32,      // ms 단위
+    pub memory_limit: u32,    // MB 단위
+    /// 쉼표로 구분한 태그
+    #[serde(default)]
+    pub tags: String,
+    pub statement: String,
+    /// 비어 있으면 공개 범위를 바꾸지 않는다
+    #[serde(default)]
+    pub visibility: Option<ProblemVisibility>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewProblemForm {
+    /// 비워두면 다음 번호
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub id: Option<i64>,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatementPreviewForm {
+    pub statement: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatementPreview {
+    pub html: String,
+    pub samples: Vec<Sample>,
+}
+
+/// 모범 답안 검증 요청
+#[derive(Debug, Deserialize)]
+pub struct ValidateSolutionForm {
+    pub language: String,
+    pub source_code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestcaseRow {
+    #[serde(flatten)]
+    pub file: TestcaseFile,
+    pub is_sample: bool,
+}
+
 // --- Submission Models ---
 
 #[derive(Debug, Deserialize)]
@@ -131,6 +278,15 @@ pub struct SubmitForm {
     pub source_code: String,
 }
 
+/// 실행 모드 요청. `input`이 없으면 문제의 예제 입력으로 실행한다.
+#[derive(Deserialize)]
+pub struct RunForm {
+    pub language: String,
+    pub source_code: String,
+    #[serde(default)]
+    pub input: Option<String>,
+}
+
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct SubmissionRow {
     pub id: i64,
@@ -138,6 +294,7 @@ pub struct SubmissionRow {
     pub language: String,
     pub status: String,
     pub score: Option<i32>,
+    pub max_score: Option<i32>,
     pub execution_time: Option<i32>,
     pub memory_usage: Option<i32>,
     pub submitted_at: String,
@@ -158,6 +315,7 @@ pub struct SubmissionDetailRow {
     pub language: String,
     pub status: String,
     pub score: Option<i32>,
+    pub max_score: Option<i32>,
     pub execution_time: Option<i32>,
     pub memory_usage: Option<i32>,
     pub compile_message: Option<String>,
@@ -176,12 +334,51 @@ pub struct TestcaseResultRow {
     pub execution_time: Option<i32>,
     pub memory_usage: Option<i32>,
     pub error_message: Option<String>,
+    pub expected_output: Option<String>,
+    pub actual_output: Option<String>,
+    pub output_start_line: Option<i32>,
+    pub diff_line: Option<i32>,
+    pub diff_column: Option<i32>,
+    /// 공개 테스트케이스의 줄 단위 비교 (`expected_output`/`actual_output`으로 채움)
+    #[sqlx(skip)]
+    pub diff_rows: Vec<DiffRow>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DiffRow {
+    pub line_number: i32,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub differs: bool,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CompileErrorRow {
+    pub file_name: Option<String>,
+    pub line_number: Option<i32>,
+    pub column_number: Option<i32>,
+    pub severity: String,
+    pub error_type: Option<String>,
+    pub error_message: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SubtaskResultRow {
+    pub subtask_number: i32,
+    pub name: Option<String>,
+    pub status: String,
+    pub score: i32,
+    pub max_score: i32,
+    pub total_testcases: i32,
+    pub passed_testcases: i32,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SubmissionDetailData {
     pub submission: SubmissionDetailRow,
     pub testcase_results: Vec<TestcaseResultRow>,
+    pub subtask_results: Vec<SubtaskResultRow>,
+    pub compile_errors: Vec<CompileErrorRow>,
 }
 
 // --- Auth Models ---
@@ -461,6 +658,23 @@ pub struct AdminAction {
     pub created_at: String,
 }
 
+/// 재채점 대상 조건. 비어 있는 항목은 조건에서 제외한다.
+#[derive(Debug, Default, Deserialize)]
+pub struct RejudgeForm {
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub problem_id: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub contest_id: Option<i64>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub id_from: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub id_to: Option<i64>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct InviteMemberForm {
     pub username: String,
@@ -580,3 +794,32 @@ pub struct BoardsQuery {
     #[serde(default)]
     pub search: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn front_matter(yaml: &str) -> Result<FrontMatter, serde_yaml::Error> {
+        serde_yaml::from_str(&format!("title: A+B\ntags: []\n{}", yaml))
+    }
+
+    #[test]
+    fn limits_accept_numbers_and_units() {
+        for (time_limit, expected) in [("1000", 1000), ("\"1500\"", 1500), ("\"250 ms\"", 250), ("\"2s\"", 2000)] {
+            let meta = front_matter(&format!("time_limit: {}\nmemory_limit: 128\n", time_limit)).unwrap();
+            assert_eq!(meta.time_limit, expected, "{}", time_limit);
+        }
+        for (memory_limit, expected) in [("64", 64), ("\"256\"", 256), ("\"512 MB\"", 512), ("\"32mb\"", 32)] {
+            let meta = front_matter(&format!("time_limit: 1000\nmemory_limit: {}\n", memory_limit)).unwrap();
+            assert_eq!(meta.memory_limit, expected, "{}", memory_limit);
+        }
+
+        let meta = front_matter("time_limit: 1000\nmemory_limit: 128\noutput_limit: \"8MB\"\n").unwrap();
+        assert_eq!(meta.output_limit, 8);
+        let meta = front_matter("time_limit: 1000\nmemory_limit: 128\n").unwrap();
+        assert_eq!(meta.output_limit, crate::judge::DEFAULT_OUTPUT_LIMIT_MB);
+
+        assert!(front_matter("time_limit: \"fast\"\nmemory_limit: 128\n").is_err());
+        assert!(front_matter("time_limit: 1000\nmemory_limit: \"1GB\"\n").is_err());
+    }
+}
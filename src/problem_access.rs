@@ -0,0 +1,111 @@
+// 문제 공개 범위와 출제자 권한
+//
+// - public: 누구나 문제 페이지, 채점 현황을 보고 제출할 수 있다.
+// - hidden: 대회 전용. 진행 중인 대회의 참가자만 대회 문제 페이지(`/contests/:id/problems/:id`)로 볼 수 있다.
+// - draft: 작성 중. 대회에서도 보이지 않는다.
+//
+// 문제를 편집할 수 있는 사람(관리자, 문제를 만든 출제자)은 공개 범위와 상관없이 볼 수 있다.
+// 볼 수 없는 문제는 있는지도 알리지 않도록 `ProblemNotFound`로 응답한다.
+// 제출 페이지도 문제와 같은 규칙을 따르되, 제출한 사용자는 자신의 제출을 항상 볼 수 있다.
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+
+use crate::auth::User;
+use crate::error::AppError;
+use crate::models::{ProblemVisibility, UnlistedProblem};
+use crate::problem_repository::{self, ProblemFile};
+
+/// 문제를 만든 사용자 (문제 편집기로 만들지 않은 문제는 `None`)
+pub async fn owner(pool: &SqlitePool, problem_id: i64) -> Result<Option<i64>, sqlx::Error> {
+    let owner: Option<Option<i64>> = sqlx::query_scalar("SELECT owner_id FROM problems WHERE id = ?")
+        .bind(problem_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(owner.flatten())
+}
+
+/// 인덱스에 문제를 만든 사용자를 기록한다. 인덱스를 먼저 맞춘 뒤 호출해야 한다.
+pub async fn set_owner(pool: &SqlitePool, problem_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE problems SET owner_id = ? WHERE id = ?")
+        .bind(user_id)
+        .bind(problem_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 관리자는 모든 문제를, 출제자는 자신이 만든 문제를 편집할 수 있다.
+pub async fn can_edit(pool: &SqlitePool, user: &User, problem_id: i64) -> Result<bool, sqlx::Error> {
+    if user.is_admin() {
+        return Ok(true);
+    }
+    if !user.is_setter() {
+        return Ok(false);
+    }
+    Ok(owner(pool, problem_id).await? == Some(user.id))
+}
+
+/// 편집 권한 확인. 문제가 없으면 `ProblemNotFound`, 권한이 없으면 `Forbidden`.
+pub async fn ensure_editable(pool: &SqlitePool, user: Option<&User>, problem_id: i64) -> Result<(), AppError> {
+    let user = user.ok_or(AppError::Unauthorized)?;
+    problem_repository::repository().load(problem_id).await?;
+    if can_edit(pool, user, problem_id).await? {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// 문제 페이지, 채점 현황, 일반 제출에서 문제를 볼 수 있는지 확인하고 문제를 돌려준다.
+pub async fn ensure_viewable(
+    pool: &SqlitePool,
+    user: Option<&User>,
+    problem_id: i64,
+) -> Result<Arc<ProblemFile>, AppError> {
+    let problem = problem_repository::repository().load(problem_id).await?;
+    if problem.meta.visibility == ProblemVisibility::Public {
+        return Ok(problem);
+    }
+    match user {
+        Some(user) if can_edit(pool, user, problem_id).await? => Ok(problem),
+        _ => Err(AppError::ProblemNotFound),
+    }
+}
+
+/// 제출 상세와 채점 진행 상황을 볼 수 있는지 확인한다.
+/// 대회에서 비공개 문제에 제출한 사용자는 대회가 끝난 뒤에도 자신의 제출을 볼 수 있다.
+pub async fn ensure_submission_viewable(
+    pool: &SqlitePool,
+    user: Option<&User>,
+    submission_id: i64,
+) -> Result<(), AppError> {
+    let (problem_id, submitter_id): (i64, i64) =
+        sqlx::query_as("SELECT problem_id, user_id FROM submissions WHERE id = ?")
+            .bind(submission_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    if user.is_some_and(|user| user.id == submitter_id) {
+        return Ok(());
+    }
+    ensure_viewable(pool, user, problem_id).await?;
+    Ok(())
+}
+
+/// 문제 목록에 나오지 않는 문제 중 `user`가 편집할 수 있는 것 (관리자는 전부, 출제자는 자신의 문제)
+pub async fn unlisted_problems(pool: &SqlitePool, user: &User) -> Result<Vec<UnlistedProblem>, sqlx::Error> {
+    if !user.can_edit_problems() {
+        return Ok(Vec::new());
+    }
+    let owner = if user.is_admin() { None } else { Some(user.id) };
+    sqlx::query_as(
+        "SELECT id, title, visibility FROM problems
+         WHERE visibility != 'public' AND (? IS NULL OR owner_id = ?)
+         ORDER BY id",
+    )
+    .bind(owner)
+    .bind(owner)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,444 @@
+// 문제 저장소: 문제 폴더 경로 계산, 마크다운 파싱/캐시, DB 문제 인덱스(`problems` 테이블)를 한 곳에서 관리한다.
+//
+// 원본은 `problems/<bucket>/<id>/<id>.md`이고 bucket은 문제 번호를 1000 단위로 내린 값이다 (1001 -> 001000).
+// 인덱스는 시작할 때와 관리자가 요청할 때 디스크에서 다시 맞추며, 문제 목록은 인덱스에서 페이지 단위로 읽는다.
+pub mod package;
+mod polygon;
+pub mod samples;
+pub mod testcases;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use gray_matter::{engine::YAML, Matter};
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+
+use crate::error::AppError;
+use crate::judge::bundle::content_hash;
+use crate::models::{FrontMatter, ProblemListItem};
+use package::ImportReport;
+use samples::Sample;
+use testcases::TestcaseFile;
+
+const PROBLEMS_ROOT: &str = "./problems";
+/// 문제 목록 한 페이지의 문제 수
+pub const PAGE_SIZE: i64 = 50;
+/// 예제와 비교하는 테스트케이스 파일의 최대 크기 (예제보다 이만큼 넘게 크면 비교하지 않는다)
+const SAMPLE_SIZE_SLACK: u64 = 1024;
+
+static REPOSITORY: Lazy<ProblemRepository> = Lazy::new(|| ProblemRepository::new(PROBLEMS_ROOT));
+
+/// 전역 문제 저장소
+pub fn repository() -> &'static ProblemRepository {
+    &REPOSITORY
+}
+
+/// 마크다운 파일 하나를 파싱한 결과
+#[derive(Debug)]
+pub struct ProblemFile {
+    pub id: i64,
+    pub meta: FrontMatter,
+    /// front matter와 예제 절을 뺀 마크다운 본문
+    pub content: String,
+    /// 마크다운에서 꺼낸 예제
+    pub samples: Vec<Sample>,
+    /// 마크다운 파일 전체의 SHA-256
+    pub checksum: String,
+}
+
+/// 인덱스 동기화 결과
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub total: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+/// 문제 번호 -> (수정 시각, 크기, 파싱 결과)
+type ProblemCache = HashMap<i64, (SystemTime, u64, Arc<ProblemFile>)>;
+
+pub struct ProblemRepository {
+    root: PathBuf,
+    /// 파일이 바뀌지 않았으면 다시 파싱하지 않는다.
+    cache: Mutex<ProblemCache>,
+}
+
+impl ProblemRepository {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 문제 폴더 (`problems/<bucket>/<id>/`)
+    pub fn problem_dir(&self, id: i64) -> PathBuf {
+        self.root.join(bucket_name(id)).join(id.to_string())
+    }
+
+    /// 문제 설명 마크다운 파일 (`problems/<bucket>/<id>/<id>.md`)
+    pub fn markdown_path(&self, id: i64) -> PathBuf {
+        self.problem_dir(id).join(format!("{}.md", id))
+    }
+
+    /// 문제를 읽어 front matter와 본문으로 나눈다. 파일이 바뀌지 않았으면 캐시를 돌려준다.
+    pub async fn load(&self, id: i64) -> Result<Arc<ProblemFile>, AppError> {
+        let path = self.markdown_path(id);
+        let metadata = tokio::fs::metadata(&path)
+            .await
+            .map_err(|_| AppError::ProblemNotFound)?;
+        let modified = metadata.modified()?;
+
+        if let Some((cached_modified, cached_len, problem)) = self.cache.lock().unwrap().get(&id)
+            && *cached_modified == modified
+            && *cached_len == metadata.len()
+        {
+            return Ok(problem.clone());
+        }
+
+        let text = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|_| AppError::ProblemNotFound)?;
+        let problem = Arc::new(parse_problem(id, &text)?);
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(id, (modified, metadata.len(), problem.clone()));
+        Ok(problem)
+    }
+
+    /// 문제의 예제. `samples/` 폴더가 있으면 마크다운보다 우선한다.
+    pub async fn samples(&self, id: i64) -> Result<Vec<Sample>, AppError> {
+        let problem = self.load(id).await?;
+        let from_dir = samples::read_from_dir(&self.problem_dir(id).join("samples")).await?;
+        Ok(if from_dir.is_empty() { problem.samples.clone() } else { from_dir })
+    }
+
+    /// 예제와 입출력이 같은 테스트케이스 이름. 채점 결과에서 공개 테스트케이스로 다룬다.
+    pub async fn sample_testcases(&self, id: i64, samples: &[Sample]) -> Vec<String> {
+        let Some(largest) = samples.iter().map(|s| s.input.len().max(s.output.len()) as u64).max() else {
+            return Vec::new();
+        };
+
+        let testcases = self.problem_dir(id).join("testcases");
+        let Ok(mut entries) = tokio::fs::read_dir(&testcases).await else {
+            return Vec::new();
+        };
+
+        let mut names = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "in") {
+                continue;
+            }
+            // 큰 테스트케이스는 예제일 수 없으므로 읽지 않는다
+            let Ok(metadata) = entry.metadata().await else { continue };
+            if metadata.len() > largest + SAMPLE_SIZE_SLACK {
+                continue;
+            }
+            let (Ok(input), Ok(output)) = (
+                tokio::fs::read_to_string(&path).await,
+                tokio::fs::read_to_string(path.with_extension("out")).await,
+            ) else {
+                continue;
+            };
+
+            let is_sample = samples
+                .iter()
+                .any(|s| samples::same_text(&s.input, &input) && samples::same_text(&s.output, &output));
+            if is_sample && let Some(name) = path.file_stem() {
+                names.push(name.to_string_lossy().to_string());
+            }
+        }
+
+        names.sort();
+        names
+    }
+
+    /// 편집기에서 쓰는 원본: front matter와 예제 절을 포함한 마크다운 본문
+    pub async fn source(&self, id: i64) -> Result<(FrontMatter, String), AppError> {
+        let problem = self.load(id).await?;
+        let text = tokio::fs::read_to_string(self.markdown_path(id))
+            .await
+            .map_err(|_| AppError::ProblemNotFound)?;
+        Ok((problem.meta.clone(), Matter::<YAML>::new().parse(&text).content))
+    }
+
+    /// 문제 마크다운을 저장한다. 다시 읽을 수 있는지 확인한 뒤 임시 파일을 거쳐 바꾼다.
+    pub async fn save(&self, id: i64, meta: &FrontMatter, body: &str) -> Result<(), AppError> {
+        let text = render_markdown(meta, body).map_err(|e| AppError::BadRequest(e.to_string()))?;
+        parse_problem(id, &text)?;
+
+        let path = self.markdown_path(id);
+        tokio::fs::create_dir_all(self.problem_dir(id)).await?;
+        let temp = path.with_extension("md.tmp");
+        tokio::fs::write(&temp, &text).await?;
+        tokio::fs::rename(&temp, &path).await?;
+        self.cache.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    /// 새 문제를 만든다. 문제 폴더가 이미 있으면 실패한다.
+    pub async fn create(&self, id: i64, meta: &FrontMatter, body: &str) -> Result<(), AppError> {
+        if self.problem_dir(id).exists() {
+            return Err(AppError::BadRequest(format!("{}번 문제가 이미 있습니다.", id)));
+        }
+        tokio::fs::create_dir_all(self.problem_dir(id).join("testcases")).await?;
+        self.save(id, meta, body).await
+    }
+
+    /// 새 문제에 쓸 번호 (가장 큰 번호 + 1)
+    pub async fn next_id(&self) -> std::io::Result<i64> {
+        Ok(self.problem_ids().await?.last().map_or(1000, |id| id + 1))
+    }
+
+    /// 채점 순서대로 정렬한 테스트케이스 목록
+    pub async fn testcases(&self, id: i64) -> std::io::Result<Vec<TestcaseFile>> {
+        testcases::list(&self.problem_dir(id).join("testcases")).await
+    }
+
+    pub async fn save_testcase(&self, id: i64, name: &str, input: &[u8], output: Option<&[u8]>) -> Result<(), AppError> {
+        if !testcases::is_valid_name(name) {
+            return Err(AppError::BadRequest(format!("잘못된 테스트케이스 이름입니다: {}", name)));
+        }
+        self.load(id).await?;
+        Ok(testcases::write(&self.problem_dir(id).join("testcases"), name, input, output).await?)
+    }
+
+    pub async fn delete_testcase(&self, id: i64, name: &str) -> Result<(), AppError> {
+        if !testcases::is_valid_name(name) {
+            return Err(AppError::NotFound);
+        }
+        Ok(testcases::remove(&self.problem_dir(id).join("testcases"), name).await?)
+    }
+
+    /// 테스트케이스를 `order` 순서로 다시 번호 매기고, `public_testcases`의 이름도 함께 바꾼다.
+    pub async fn reorder_testcases(&self, id: i64, order: &[String]) -> Result<(), AppError> {
+        let (mut meta, body) = self.source(id).await?;
+        let existing: HashSet<String> = self.testcases(id).await?.into_iter().map(|t| t.name).collect();
+        if order.len() != existing.len() || order.iter().any(|name| !existing.contains(name)) {
+            return Err(AppError::BadRequest("테스트케이스 목록이 바뀌었습니다. 다시 시도해주세요.".to_string()));
+        }
+
+        let prefixes: Vec<&str> = meta.subtasks.iter().map(|s| s.prefix.as_str()).collect();
+        let renames: Vec<(String, String)> = testcases::renumbered(order, &prefixes)
+            .into_iter()
+            .filter(|(from, to)| from != to)
+            .collect();
+        if renames.is_empty() {
+            return Ok(());
+        }
+        testcases::rename_all(&self.problem_dir(id).join("testcases"), &renames).await?;
+
+        let renamed: HashMap<&str, &str> = renames.iter().map(|(from, to)| (from.as_str(), to.as_str())).collect();
+        for name in &mut meta.public_testcases {
+            if let Some(to) = renamed.get(name.as_str()) {
+                *name = to.to_string();
+            }
+        }
+        self.save(id, &meta, &body).await
+    }
+
+    /// `names` 테스트케이스를 예제로 쓴다 (`samples/`). 비어 있으면 마크다운의 예제로 돌아간다.
+    pub async fn set_sample_testcases(&self, id: i64, names: &[String]) -> Result<(), AppError> {
+        if names.iter().any(|name| !testcases::is_valid_name(name)) {
+            return Err(AppError::BadRequest("잘못된 테스트케이스 이름입니다.".to_string()));
+        }
+        let dir = self.problem_dir(id);
+        Ok(testcases::write_samples(&dir.join("testcases"), &dir.join("samples"), names).await?)
+    }
+
+    /// 문제 패키지(zip)를 `id`번 문제로 가져온다. 목록에 보이려면 인덱스를 다시 맞춰야 한다.
+    pub async fn import_package(&self, id: i64, bytes: Vec<u8>, overwrite: bool) -> anyhow::Result<ImportReport> {
+        let target = self.problem_dir(id);
+        let report = tokio::task::spawn_blocking(move || package::import(&bytes, &target, id, overwrite)).await??;
+        self.cache.lock().unwrap().remove(&id);
+        Ok(report)
+    }
+
+    /// 문제 폴더를 문제 패키지(zip)로 묶는다.
+    pub async fn export_package(&self, id: i64) -> anyhow::Result<Vec<u8>> {
+        if !self.markdown_path(id).exists() {
+            return Err(anyhow::anyhow!("Problem {} not found", id));
+        }
+        let dir = self.problem_dir(id);
+        tokio::task::spawn_blocking(move || package::export(&dir, id)).await?
+    }
+
+    /// 디스크에 있는 문제 번호 목록. 번호와 맞지 않는 bucket에 있는 문제는 찾을 수 없으므로 경고 후 건너뛴다.
+    pub async fn problem_ids(&self) -> std::io::Result<Vec<i64>> {
+        let mut ids = Vec::new();
+        let mut buckets = match tokio::fs::read_dir(&self.root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ids),
+            Err(e) => return Err(e),
+        };
+
+        while let Some(bucket) = buckets.next_entry().await? {
+            if !bucket.file_type().await?.is_dir() {
+                continue;
+            }
+            let folder = bucket.file_name().to_string_lossy().to_string();
+
+            let mut entries = tokio::fs::read_dir(bucket.path()).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                if !entry.file_type().await?.is_dir() {
+                    continue;
+                }
+                let Ok(id) = entry.file_name().to_string_lossy().parse::<i64>() else {
+                    continue;
+                };
+                if !entry.path().join(format!("{}.md", id)).exists() {
+                    continue;
+                }
+                if bucket_name(id) != folder {
+                    eprintln!("[problems] problem {} is in {}/ but should be in {}/", id, folder, bucket_name(id));
+                    continue;
+                }
+                ids.push(id);
+            }
+        }
+
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// 디스크의 문제를 `problems` 인덱스에 반영한다. 체크섬이 바뀐 문제만 다시 쓰고, 없어진 문제는 지운다.
+    pub async fn sync_index(&self, pool: &SqlitePool) -> anyhow::Result<SyncReport> {
+        let indexed: HashMap<i64, String> = sqlx::query_as::<_, (i64, String)>("SELECT id, checksum FROM problems")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut report = SyncReport::default();
+        let mut present = HashSet::new();
+        for id in self.problem_ids().await? {
+            let problem = match self.load(id).await {
+                Ok(problem) => problem,
+                Err(_) => {
+                    // 형식이 잘못된 문제는 목록에서 뺀다
+                    eprintln!("[problems] failed to parse problem {}", id);
+                    continue;
+                }
+            };
+            present.insert(id);
+
+            if indexed.get(&id) == Some(&problem.checksum) {
+                continue;
+            }
+            upsert_index(pool, &problem).await?;
+            report.updated += 1;
+        }
+
+        for id in indexed.keys().filter(|id| !present.contains(id)) {
+            sqlx::query("DELETE FROM problems WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await?;
+            report.removed += 1;
+        }
+
+        report.total = present.len();
+        Ok(report)
+    }
+
+    /// 문제 하나의 인덱스를 디스크와 맞춘다 (편집기에서 저장한 뒤)
+    pub async fn refresh_index(&self, pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+        match self.load(id).await {
+            Ok(problem) => upsert_index(pool, &problem).await?,
+            Err(_) => {
+                sqlx::query("DELETE FROM problems WHERE id = ?")
+                    .bind(id)
+                    .execute(pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 공개 문제 목록의 `page`번째 페이지 (1부터)와 전체 페이지 수
+    pub async fn list_page(&self, pool: &SqlitePool, page: i64) -> Result<(Vec<ProblemListItem>, i64), sqlx::Error> {
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM problems WHERE visibility = 'public'")
+            .fetch_one(pool)
+            .await?;
+        let total_pages = ((total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+
+        let mut problems: Vec<ProblemListItem> = sqlx::query_as(
+            "SELECT p.id, p.title, COALESCE(s.acceptance_rate, 0.0) AS accuracy
+             FROM problems p
+             LEFT JOIN submission_stats s ON s.problem_id = p.id
+             WHERE p.visibility = 'public'
+             ORDER BY p.id
+             LIMIT ? OFFSET ?",
+        )
+        .bind(PAGE_SIZE)
+        .bind((page.clamp(1, total_pages) - 1) * PAGE_SIZE)
+        .fetch_all(pool)
+        .await?;
+
+        for problem in &mut problems {
+            problem.accuracy = (problem.accuracy * 10.0).round() / 10.0;
+        }
+        Ok((problems, total_pages))
+    }
+}
+
+/// front matter의 경로가 문제 폴더 밖을 가리키지 않는 상대 경로인지
+pub fn is_relative_inside(path: &str) -> bool {
+    Path::new(path).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// 1000 단위로 내린 bucket 폴더 이름 (1001 -> `001000`, 999 -> `000000`)
+fn bucket_name(id: i64) -> String {
+    format!("{:06}", id / 1000 * 1000)
+}
+
+async fn upsert_index(pool: &SqlitePool, problem: &ProblemFile) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO problems (id, title, time_limit, memory_limit, tags, visibility, checksum, synced_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(id) DO UPDATE SET
+             title = excluded.title,
+             time_limit = excluded.time_limit,
+             memory_limit = excluded.memory_limit,
+             tags = excluded.tags,
+             visibility = excluded.visibility,
+             checksum = excluded.checksum,
+             synced_at = excluded.synced_at",
+    )
+    .bind(problem.id)
+    .bind(&problem.meta.title)
+    .bind(problem.meta.time_limit as i64)
+    .bind(problem.meta.memory_limit as i64)
+    .bind(serde_json::to_string(&problem.meta.tags).unwrap_or_else(|_| "[]".to_string()))
+    .bind(problem.meta.visibility.as_str())
+    .bind(&problem.checksum)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// front matter와 본문으로 문제 마크다운 파일 내용을 만든다.
+pub fn render_markdown(meta: &FrontMatter, body: &str) -> anyhow::Result<String> {
+    Ok(format!("---\n{}---\n\n{}\n", serde_yaml::to_string(meta)?, body.trim()))
+}
+
+fn parse_problem(id: i64, text: &str) -> Result<ProblemFile, AppError> {
+    let parsed = Matter::<YAML>::new().parse(text);
+    let front_matter = parsed.data.ok_or(AppError::InvalidProblemFormat)?;
+    let meta: FrontMatter = front_matter.deserialize()?;
+    let (samples, content) = samples::extract_from_markdown(&parsed.content);
+
+    Ok(ProblemFile {
+        id,
+        meta,
+        content,
+        samples,
+        checksum: content_hash(text.as_bytes()),
+    })
+}
@@ -0,0 +1,201 @@
+// 문제 패키지 (zip) 가져오기/내보내기
+//
+// 기본 패키지는 문제 폴더를 그대로 담고, 번호와 상관없이 옮길 수 있도록 `<id>.md` 대신 `problem.md`를 쓴다.
+// `problem.xml`이 있으면 Codeforces Polygon 패키지로 보고 같은 폴더 구조로 바꾼다 (`polygon` 모듈).
+// 가져온 파일은 문제 폴더 옆의 임시 폴더에서 형식을 검사한 뒤 한 번에 옮긴다.
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{is_relative_inside, polygon};
+use crate::judge::checker::CheckerSpec;
+
+/// 업로드할 수 있는 패키지(zip) 최대 크기
+pub const MAX_PACKAGE_BYTES: usize = 256 * 1024 * 1024;
+/// 압축을 푼 파일 전체의 최대 크기 (압축 폭탄 방지)
+const MAX_UNPACKED_BYTES: u64 = 1024 * 1024 * 1024;
+/// 기본 패키지의 문제 설명 파일 이름
+pub const STATEMENT_FILE: &str = "problem.md";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageFormat {
+    Native,
+    Polygon,
+}
+
+/// 가져오기 결과
+#[derive(Debug)]
+pub struct ImportReport {
+    pub format: PackageFormat,
+    pub title: String,
+    pub testcases: usize,
+}
+
+/// 문제 폴더를 zip으로 묶는다. `<id>.md`는 `problem.md`로 넣는다.
+pub(super) fn export(dir: &Path, id: i64) -> anyhow::Result<Vec<u8>> {
+    let markdown = format!("{}.md", id);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let mut name = entry
+            .path()
+            .strip_prefix(dir)?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if name == markdown {
+            name = STATEMENT_FILE.to_string();
+        }
+
+        writer.start_file(name, options)?;
+        std::io::copy(&mut fs::File::open(entry.path())?, &mut writer)?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+/// 패키지를 `target` 문제 폴더로 가져온다. 폴더가 이미 있으면 `overwrite`일 때만 바꾼다.
+pub(super) fn import(bytes: &[u8], target: &Path, id: i64, overwrite: bool) -> anyhow::Result<ImportReport> {
+    if target.exists() && !overwrite {
+        anyhow::bail!("{}번 문제가 이미 있습니다. 덮어쓰려면 overwrite를 지정하세요.", id);
+    }
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid problem directory: {}", target.display()))?;
+    fs::create_dir_all(parent)?;
+
+    // 같은 파일시스템 안에서 rename으로 옮길 수 있도록 bucket 폴더 안에 만든다 (숫자가 아니므로 문제 목록에는 잡히지 않는다)
+    let staging = parent.join(format!(".import-{}-{}", id, uuid::Uuid::new_v4()));
+    let result = stage(bytes, &staging, id).and_then(|report| {
+        replace_dir(&staging.join("problem"), target, &staging.join("previous"))?;
+        Ok(report)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn stage(bytes: &[u8], staging: &Path, id: i64) -> anyhow::Result<ImportReport> {
+    let unpacked = staging.join("package");
+    unpack(bytes, &unpacked)?;
+    let root = package_root(&unpacked)?;
+    let problem = staging.join("problem");
+
+    let format = if root.join(STATEMENT_FILE).is_file() {
+        fs::rename(&root, &problem)?;
+        fs::rename(problem.join(STATEMENT_FILE), problem.join(format!("{}.md", id)))?;
+        PackageFormat::Native
+    } else if root.join("problem.xml").is_file() {
+        polygon::convert(&root, &problem, id)?;
+        PackageFormat::Polygon
+    } else {
+        anyhow::bail!("패키지에 {} 또는 problem.xml이 없습니다.", STATEMENT_FILE);
+    };
+
+    let (title, testcases) = validate(&problem, id)?;
+    Ok(ImportReport { format, title, testcases })
+}
+
+fn unpack(bytes: &[u8], dest: &Path) -> anyhow::Result<()> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| anyhow::anyhow!("zip 파일을 열 수 없습니다: {}", e))?;
+    let mut remaining = MAX_UNPACKED_BYTES;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        // 상위 디렉토리나 절대 경로로 벗어나는 항목은 받지 않는다
+        let Some(relative) = file.enclosed_name() else {
+            anyhow::bail!("패키지에 잘못된 경로가 있습니다: {}", file.name());
+        };
+        if relative.starts_with("__MACOSX") {
+            continue;
+        }
+
+        let path = dest.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let written = std::io::copy(&mut (&mut file).take(remaining + 1), &mut fs::File::create(&path)?)?;
+        if written > remaining {
+            anyhow::bail!("압축을 푼 크기가 {}MB를 넘습니다.", MAX_UNPACKED_BYTES / 1024 / 1024);
+        }
+        remaining -= written;
+    }
+
+    Ok(())
+}
+
+/// 문제 파일이 있는 폴더. zip 안에 폴더 하나로 감싸져 있으면 그 안을 본다.
+fn package_root(unpacked: &Path) -> anyhow::Result<PathBuf> {
+    if unpacked.join(STATEMENT_FILE).is_file() || unpacked.join("problem.xml").is_file() {
+        return Ok(unpacked.to_path_buf());
+    }
+
+    let entries: Vec<_> = fs::read_dir(unpacked)?.collect::<Result<_, _>>()?;
+    match entries.as_slice() {
+        [single] if single.file_type()?.is_dir() => Ok(single.path()),
+        _ => Ok(unpacked.to_path_buf()),
+    }
+}
+
+/// 문제 설명을 파싱하고 front matter가 가리키는 파일과 테스트케이스가 있는지 확인한다.
+fn validate(dir: &Path, id: i64) -> anyhow::Result<(String, usize)> {
+    let text = fs::read_to_string(dir.join(format!("{}.md", id)))?;
+    let problem = super::parse_problem(id, &text)
+        .map_err(|_| anyhow::anyhow!("문제 설명의 front matter 형식이 잘못되었습니다."))?;
+    let meta = &problem.meta;
+
+    let mut sources = Vec::new();
+    if let CheckerSpec::Custom { source, .. } = &meta.checker {
+        sources.push(source);
+    }
+    sources.extend(meta.interactor.iter());
+    sources.extend(meta.validator.iter());
+    for source in sources {
+        if !is_relative_inside(source) || !dir.join(source).is_file() {
+            anyhow::bail!("front matter가 가리키는 {} 파일이 패키지에 없습니다.", source);
+        }
+    }
+
+    let testcases = match fs::read_dir(dir.join("testcases")) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "in"))
+            .count(),
+        Err(_) => 0,
+    };
+    if testcases == 0 {
+        anyhow::bail!("테스트케이스(testcases/*.in)가 없습니다.");
+    }
+
+    Ok((meta.title.clone(), testcases))
+}
+
+/// `target`을 `new`로 바꾼다. 기존 폴더는 `backup`으로 옮겼다가 실패하면 되돌린다.
+fn replace_dir(new: &Path, target: &Path, backup: &Path) -> anyhow::Result<()> {
+    if !target.exists() {
+        fs::rename(new, target)?;
+        return Ok(());
+    }
+
+    fs::rename(target, backup)?;
+    if let Err(e) = fs::rename(new, target) {
+        fs::rename(backup, target)?;
+        return Err(e.into());
+    }
+    Ok(())
+}
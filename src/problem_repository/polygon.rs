@@ -0,0 +1,301 @@
+// Codeforces Polygon 패키지 변환
+//
+// `problem.xml`에서 제목, 제한, 태그, 테스트 목록(그룹), 체커, 검증기를 읽고
+// `statement-sections/<언어>/`의 지문으로 문제 마크다운을 만든다. 테스트는 생성된 파일이 들어 있는
+// 전체(full) 패키지여야 한다. 그룹이 있으면 그룹별 서브태스크(`g<그룹>_` 접두어)로 바꾼다.
+//
+// testlib 인터랙터는 출력 파일 인자 순서가 이 저지의 인터랙터와 달라 인터랙티브 문제는 받지 않는다.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use roxmltree::{Document, Node};
+
+use crate::judge::checker::CheckerSpec;
+use crate::judge::scoring::{ScoringMode, SubtaskSpec};
+use crate::judge::DEFAULT_OUTPUT_LIMIT_MB;
+use crate::models::{FrontMatter, ProblemVisibility};
+
+/// 제목과 지문을 찾는 언어 순서 (없으면 패키지의 첫 번째 언어)
+const LANGUAGES: [&str; 2] = ["korean", "english"];
+
+/// Polygon 패키지(`package`)를 문제 폴더(`dest`)로 바꾼다.
+pub(super) fn convert(package: &Path, dest: &Path, id: i64) -> anyhow::Result<()> {
+    let xml = fs::read_to_string(package.join("problem.xml"))?;
+    let document = Document::parse(&xml).map_err(|e| anyhow::anyhow!("problem.xml 파싱 실패: {}", e))?;
+    let problem = document.root_element();
+    let assets = child(problem, "assets");
+
+    if assets.and_then(|a| child(a, "interactor")).is_some() {
+        anyhow::bail!("Polygon 인터랙티브 문제는 가져올 수 없습니다.");
+    }
+
+    let testset = child(problem, "judging")
+        .and_then(|judging| {
+            let mut testsets = judging.children().filter(|n| n.has_tag_name("testset"));
+            testsets
+                .clone()
+                .find(|n| n.attribute("name") == Some("tests"))
+                .or_else(|| testsets.next())
+        })
+        .ok_or_else(|| anyhow::anyhow!("problem.xml에 testset이 없습니다."))?;
+
+    fs::create_dir_all(dest.join("testcases"))?;
+    let (subtasks, scoring) = copy_tests(package, dest, testset)?;
+
+    let checker = match assets.and_then(|a| child(a, "checker")) {
+        Some(checker) => convert_checker(package, dest, checker)?,
+        None => CheckerSpec::Exact,
+    };
+    let validator = match assets
+        .and_then(|a| child(a, "validators"))
+        .and_then(|v| child(v, "validator"))
+    {
+        Some(validator) => Some(copy_source(package, dest, validator, "validator")?),
+        None => None,
+    };
+
+    let time_limit = child_text(testset, "time-limit").and_then(|t| t.parse().ok()).unwrap_or(1000);
+    let memory_limit = child_text(testset, "memory-limit")
+        .and_then(|m| m.parse::<u64>().ok())
+        .map(|bytes| bytes.div_ceil(1024 * 1024) as u32)
+        .unwrap_or(256);
+    let tags = child(problem, "tags")
+        .map(|tags| {
+            tags.children()
+                .filter(|n| n.has_tag_name("tag"))
+                .filter_map(|n| n.attribute("value"))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let statement = statement_dir(package);
+    let read_section = |name: &str| {
+        statement
+            .as_ref()
+            .and_then(|dir| fs::read_to_string(dir.join(format!("{}.tex", name))).ok())
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let title = title(problem)
+        .or_else(|| Some(read_section("name")).filter(|name| !name.is_empty()))
+        .or_else(|| problem.attribute("short-name").map(str::to_string))
+        .unwrap_or_else(|| id.to_string());
+
+    let mut body = read_section("legend");
+    for (heading, section) in [("입력", "input"), ("출력", "output"), ("노트", "notes")] {
+        let text = read_section(section);
+        if !text.is_empty() {
+            body.push_str(&format!("\n\n### {}\n\n{}", heading, text));
+        }
+    }
+
+    let meta = FrontMatter {
+        title,
+        time_limit,
+        memory_limit,
+        tags,
+        checker,
+        interactor: None,
+        validator,
+        subtasks,
+        scoring,
+        public_testcases: Vec::new(),
+        output_limit: DEFAULT_OUTPUT_LIMIT_MB,
+        visibility: ProblemVisibility::default(),
+    };
+    fs::write(dest.join(format!("{}.md", id)), super::render_markdown(&meta, &body)?)?;
+    Ok(())
+}
+
+/// 테스트를 `testcases/`로, 예제(`sample="true"`)는 `samples/`에도 복사하고 그룹을 서브태스크로 바꾼다.
+fn copy_tests(package: &Path, dest: &Path, testset: Node) -> anyhow::Result<(Vec<SubtaskSpec>, ScoringMode)> {
+    let input_pattern = child_text(testset, "input-path-pattern").unwrap_or("tests/%02d");
+    let answer_pattern = child_text(testset, "answer-path-pattern").unwrap_or("tests/%02d.a");
+    let tests: Vec<Node> = child(testset, "tests")
+        .map(|tests| tests.children().filter(|n| n.has_tag_name("test")).collect())
+        .unwrap_or_default();
+
+    // 그룹 이름 -> (점수, 테스트마다 점수를 주는지)
+    let groups: BTreeMap<&str, (Option<f64>, bool)> = child(testset, "groups")
+        .map(|groups| {
+            groups
+                .children()
+                .filter(|n| n.has_tag_name("group"))
+                .filter_map(|n| {
+                    let points = n.attribute("points").and_then(|p| p.parse().ok());
+                    Some((n.attribute("name")?, (points, n.attribute("points-policy") == Some("each-test"))))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut group_scores: BTreeMap<&str, f64> = BTreeMap::new();
+
+    let mut samples = 0;
+    for (index, test) in tests.iter().enumerate() {
+        let number = index + 1;
+        let input = package.join(format_pattern(input_pattern, number));
+        let answer = package.join(format_pattern(answer_pattern, number));
+        if !input.is_file() || !answer.is_file() {
+            anyhow::bail!(
+                "테스트 {}의 입력 또는 정답 파일이 없습니다. 테스트가 생성된 전체(full) 패키지를 올려주세요.",
+                number
+            );
+        }
+
+        let group = test.attribute("group").filter(|_| !groups.is_empty());
+        let name = match group {
+            Some(group) => {
+                let points: f64 = test.attribute("points").and_then(|p| p.parse().ok()).unwrap_or(0.0);
+                *group_scores.entry(group).or_default() += points;
+                format!("{}{:02}", group_prefix(group), number)
+            }
+            None => number.to_string(),
+        };
+        fs::copy(&input, dest.join("testcases").join(format!("{}.in", name)))?;
+        fs::copy(&answer, dest.join("testcases").join(format!("{}.out", name)))?;
+
+        if test.attribute("sample") == Some("true") {
+            samples += 1;
+            fs::create_dir_all(dest.join("samples"))?;
+            fs::copy(&input, dest.join("samples").join(format!("{}.in", samples)))?;
+            fs::copy(&answer, dest.join("samples").join(format!("{}.out", samples)))?;
+        }
+    }
+
+    let subtasks = groups
+        .iter()
+        .map(|(group, (points, _))| SubtaskSpec {
+            name: Some(group.to_string()),
+            // 그룹 점수가 없으면 테스트 점수의 합
+            score: points.or_else(|| group_scores.get(group).copied()).unwrap_or(0.0).round() as i32,
+            prefix: group_prefix(group),
+        })
+        .collect();
+    let scoring = if !groups.is_empty() && groups.values().all(|(_, each_test)| *each_test) {
+        ScoringMode::Sum
+    } else {
+        ScoringMode::Min
+    };
+
+    Ok((subtasks, scoring))
+}
+
+/// Polygon 표준 체커 중 같은 동작의 내장 체커가 있으면 그것을 쓰고, 아니면 체커 소스를 testlib 체커로 쓴다.
+fn convert_checker(package: &Path, dest: &Path, checker: Node) -> anyhow::Result<CheckerSpec> {
+    let builtin = match checker.attribute("name").unwrap_or_default() {
+        "std::fcmp.cpp" => Some(CheckerSpec::Exact),
+        "std::wcmp.cpp" => Some(CheckerSpec::Token),
+        "std::rcmp4.cpp" => Some(float_checker(1e-4)),
+        "std::rcmp6.cpp" => Some(float_checker(1e-6)),
+        "std::rcmp9.cpp" => Some(float_checker(1e-9)),
+        _ => None,
+    };
+    if let Some(spec) = builtin {
+        return Ok(spec);
+    }
+
+    Ok(CheckerSpec::Custom {
+        source: copy_source(package, dest, checker, "checker")?,
+        testlib: checker.attribute("type").is_none_or(|t| t == "testlib"),
+    })
+}
+
+fn float_checker(eps: f64) -> CheckerSpec {
+    CheckerSpec::Float { abs_eps: eps, rel_eps: eps }
+}
+
+/// 자산의 `<source path="...">`를 `<name>.<확장자>`로 복사하고 문제 폴더 기준 경로를 돌려준다.
+/// 같은 폴더의 `testlib.h`도 함께 복사해 `#include "testlib.h"`로 컴파일되게 한다.
+fn copy_source(package: &Path, dest: &Path, asset: Node, name: &str) -> anyhow::Result<String> {
+    let path = child(asset, "source")
+        .and_then(|source| source.attribute("path"))
+        .ok_or_else(|| anyhow::anyhow!("problem.xml의 {} 소스 경로가 없습니다.", name))?;
+    let source = package.join(path);
+    if !path.split('/').all(|part| !part.is_empty() && part != "..") || !source.is_file() {
+        anyhow::bail!("{} 소스 파일({})이 패키지에 없습니다.", name, path);
+    }
+
+    let file_name = match source.extension() {
+        Some(ext) => format!("{}.{}", name, ext.to_string_lossy()),
+        None => name.to_string(),
+    };
+    fs::copy(&source, dest.join(&file_name))?;
+
+    let testlib = source.with_file_name("testlib.h");
+    if testlib.is_file() && !dest.join("testlib.h").exists() {
+        fs::copy(&testlib, dest.join("testlib.h"))?;
+    }
+    Ok(file_name)
+}
+
+/// 제목 (`<names><name language="..." value="..."/>`)
+fn title(problem: Node) -> Option<String> {
+    let names: Vec<Node> = child(problem, "names")?
+        .children()
+        .filter(|n| n.has_tag_name("name"))
+        .collect();
+    LANGUAGES
+        .iter()
+        .find_map(|language| names.iter().find(|n| n.attribute("language") == Some(*language)))
+        .or(names.first())
+        .and_then(|n| n.attribute("value"))
+        .map(str::to_string)
+}
+
+/// 지문 폴더 (`statement-sections/<언어>/`)
+fn statement_dir(package: &Path) -> Option<PathBuf> {
+    let sections = package.join("statement-sections");
+    LANGUAGES
+        .iter()
+        .map(|language| sections.join(language))
+        .find(|dir| dir.is_dir())
+        .or_else(|| {
+            let mut dirs: Vec<_> = fs::read_dir(&sections)
+                .ok()?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect();
+            dirs.sort();
+            dirs.into_iter().next()
+        })
+}
+
+/// 그룹 테스트케이스 이름 접두어. 파일 이름에 쓸 수 없는 글자는 `_`로 바꾼다.
+fn group_prefix(group: &str) -> String {
+    let group: String = group
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("g{}_", group)
+}
+
+/// `tests/%02d` 같은 printf 형식 경로에 테스트 번호를 넣는다.
+fn format_pattern(pattern: &str, number: usize) -> String {
+    let Some(start) = pattern.find('%') else {
+        return pattern.to_string();
+    };
+    let Some(end) = pattern[start..].find('d').map(|offset| start + offset) else {
+        return pattern.to_string();
+    };
+
+    let spec = &pattern[start + 1..end];
+    let width: usize = spec.trim_start_matches('0').parse().unwrap_or(0);
+    let number = if spec.starts_with('0') {
+        format!("{:0width$}", number, width = width)
+    } else {
+        format!("{:width$}", number, width = width)
+    };
+    format!("{}{}{}", &pattern[..start], number, &pattern[end + 1..])
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|n| n.text()).map(str::trim)
+}
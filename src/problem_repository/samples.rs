@@ -0,0 +1,129 @@
+// 예제 입출력 추출
+//
+// 문제 폴더에 `samples/<n>.in`, `samples/<n>.out`이 있으면 그것을 쓰고, 없으면 마크다운의
+// `### 예제 입력 N` / `### 예제 출력 N` 제목 바로 아래 코드 블록에서 읽는다.
+// 마크다운의 예제 절은 본문에서 빼고, 문제 페이지가 따로 보여준다.
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static SAMPLE_HEADING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#{1,6}\s*예제\s*(입력|출력)\s*(\d+)\s*$").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sample {
+    /// 1부터 시작하는 예제 번호
+    pub number: u32,
+    pub input: String,
+    pub output: String,
+}
+
+#[derive(Default)]
+struct SampleParts {
+    input: Option<String>,
+    output: Option<String>,
+}
+
+/// 마크다운에서 예제를 꺼내고, 예제 절을 뺀 본문을 돌려준다.
+/// 제목 아래에 코드 블록이 없으면 예제로 보지 않고 본문에 남긴다.
+pub fn extract_from_markdown(markdown: &str) -> (Vec<Sample>, String) {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut parts: BTreeMap<u32, SampleParts> = BTreeMap::new();
+    let mut body = Vec::with_capacity(lines.len());
+
+    let mut i = 0;
+    while i < lines.len() {
+        let heading = SAMPLE_HEADING.captures(lines[i].trim_end());
+        let block = heading.as_ref().and_then(|_| code_block(&lines, i + 1));
+
+        match (heading, block) {
+            (Some(caps), Some((text, next))) => {
+                let number: u32 = caps[2].parse().unwrap_or(0);
+                let entry = parts.entry(number).or_default();
+                if &caps[1] == "입력" {
+                    entry.input = Some(text);
+                } else {
+                    entry.output = Some(text);
+                }
+                i = next;
+            }
+            _ => {
+                body.push(lines[i]);
+                i += 1;
+            }
+        }
+    }
+
+    (into_samples(parts), body.join("\n"))
+}
+
+/// `start`부터 빈 줄을 건너뛰고 나오는 펜스 코드 블록의 내용과 블록 다음 줄 번호
+fn code_block(lines: &[&str], start: usize) -> Option<(String, usize)> {
+    let open = (start..lines.len()).find(|&i| !lines[i].trim().is_empty())?;
+    let fence = lines[open].trim_start();
+    let marker = if fence.starts_with("```") {
+        "```"
+    } else if fence.starts_with("~~~") {
+        "~~~"
+    } else {
+        return None;
+    };
+
+    let close = (open + 1..lines.len()).find(|&i| lines[i].trim() == marker)?;
+    let mut text = lines[open + 1..close].join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    Some((text, close + 1))
+}
+
+/// `samples/` 폴더의 `<n>.in` / `<n>.out` 파일. 폴더가 없으면 빈 목록.
+pub async fn read_from_dir(dir: &Path) -> std::io::Result<Vec<Sample>> {
+    let mut parts: BTreeMap<u32, SampleParts> = BTreeMap::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(number) = path.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse::<u32>().ok()) else {
+            continue;
+        };
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("in") => parts.entry(number).or_default().input = Some(tokio::fs::read_to_string(&path).await?),
+            Some("out") => parts.entry(number).or_default().output = Some(tokio::fs::read_to_string(&path).await?),
+            _ => {}
+        }
+    }
+
+    Ok(into_samples(parts))
+}
+
+/// 입력이나 출력 한쪽만 있는 예제는 나머지를 빈 문자열로 둔다.
+fn into_samples(parts: BTreeMap<u32, SampleParts>) -> Vec<Sample> {
+    parts
+        .into_iter()
+        .map(|(number, part)| Sample {
+            number,
+            input: part.input.unwrap_or_default(),
+            output: part.output.unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// 줄 끝 공백과 마지막 빈 줄을 무시하고 비교
+pub fn same_text(a: &str, b: &str) -> bool {
+    fn normalized(text: &str) -> Vec<&str> {
+        let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+    normalized(a) == normalized(b)
+}
@@ -0,0 +1,157 @@
+// 테스트케이스 파일 관리 (문제 편집기)
+//
+// 테스트케이스는 `testcases/<이름>.in` / `<이름>.out`이고 채점은 이름의 사전순으로 진행한다.
+// 순서를 바꾸면 서브태스크 접두어는 그대로 두고 접두어 안의 번호를 다시 매긴다.
+// 예제로 표시한 테스트케이스는 `samples/`에 복사되어 마크다운의 예제보다 우선한다.
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use tokio::fs;
+
+/// 테스트케이스 업로드 요청의 최대 크기
+pub const MAX_UPLOAD_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestcaseFile {
+    pub name: String,
+    pub input_size: u64,
+    /// 출력 파일이 없으면 `None` (체커가 통과로 처리한다)
+    pub output_size: Option<u64>,
+}
+
+/// 파일 이름으로 쓸 수 있는 테스트케이스 이름인지 (영문, 숫자, `_`, `-`)
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// 채점 순서(이름순)대로 정렬한 테스트케이스 목록
+pub async fn list(dir: &Path) -> io::Result<Vec<TestcaseFile>> {
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut testcases = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "in") {
+            continue;
+        }
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let output_size = fs::metadata(path.with_extension("out")).await.ok().map(|m| m.len());
+        testcases.push(TestcaseFile {
+            name,
+            input_size: entry.metadata().await?.len(),
+            output_size,
+        });
+    }
+
+    testcases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(testcases)
+}
+
+/// 테스트케이스를 저장한다. 출력이 없으면 기존 출력 파일을 지운다.
+pub async fn write(dir: &Path, name: &str, input: &[u8], output: Option<&[u8]>) -> io::Result<()> {
+    fs::create_dir_all(dir).await?;
+    fs::write(dir.join(format!("{}.in", name)), input).await?;
+    let output_path = dir.join(format!("{}.out", name));
+    match output {
+        Some(output) => fs::write(&output_path, output).await,
+        None => remove_if_exists(&output_path).await,
+    }
+}
+
+pub async fn remove(dir: &Path, name: &str) -> io::Result<()> {
+    remove_if_exists(&dir.join(format!("{}.in", name))).await?;
+    remove_if_exists(&dir.join(format!("{}.out", name))).await
+}
+
+/// `order` 순서대로 매길 새 이름 (이전 이름, 새 이름).
+///
+/// 가장 길게 일치하는 서브태스크 접두어 안에서 1부터 번호를 매기고, 사전순과 번호 순서가 같도록
+/// 접두어 안의 개수 자릿수만큼 0을 채운다. (`sub1_` 접두어 12개 -> `sub1_01` ... `sub1_12`)
+pub fn renumbered(order: &[String], prefixes: &[&str]) -> Vec<(String, String)> {
+    let prefix_of = |name: &str| -> String {
+        prefixes
+            .iter()
+            .filter(|prefix| name.starts_with(*prefix))
+            .max_by_key(|prefix| prefix.len())
+            .map(|prefix| prefix.to_string())
+            .unwrap_or_default()
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for name in order {
+        *counts.entry(prefix_of(name)).or_default() += 1;
+    }
+
+    let mut next: HashMap<String, usize> = HashMap::new();
+    order
+        .iter()
+        .map(|name| {
+            let prefix = prefix_of(name);
+            let width = counts[&prefix].to_string().len();
+            let number = next.entry(prefix.clone()).or_default();
+            *number += 1;
+            (name.clone(), format!("{}{:0width$}", prefix, number, width = width))
+        })
+        .collect()
+}
+
+/// 이름을 한꺼번에 바꾼다. 새 이름이 다른 테스트케이스의 이전 이름과 겹칠 수 있으므로 임시 이름을 거친다.
+pub async fn rename_all(dir: &Path, renames: &[(String, String)]) -> io::Result<()> {
+    for (index, (from, _)) in renames.iter().enumerate() {
+        for ext in ["in", "out"] {
+            rename_if_exists(&dir.join(format!("{}.{}", from, ext)), &dir.join(format!(".rename-{}.{}", index, ext))).await?;
+        }
+    }
+    for (index, (_, to)) in renames.iter().enumerate() {
+        for ext in ["in", "out"] {
+            rename_if_exists(&dir.join(format!(".rename-{}.{}", index, ext)), &dir.join(format!("{}.{}", to, ext))).await?;
+        }
+    }
+    Ok(())
+}
+
+/// `names` 테스트케이스를 순서대로 `samples/<번호>.in/.out`으로 복사한다. 비어 있으면 `samples/`를 지운다.
+pub async fn write_samples(testcases: &Path, samples: &Path, names: &[String]) -> io::Result<()> {
+    match fs::remove_dir_all(samples).await {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }?;
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(samples).await?;
+    for (index, name) in names.iter().enumerate() {
+        let number = index + 1;
+        fs::copy(testcases.join(format!("{}.in", name)), samples.join(format!("{}.in", number))).await?;
+        let output = testcases.join(format!("{}.out", name));
+        if fs::try_exists(&output).await? {
+            fs::copy(&output, samples.join(format!("{}.out", number))).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path).await {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+async fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to).await {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -1,11 +1,12 @@
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{get, post},
     Router,
     middleware,
 };
 use tower_http::services::ServeDir;
 
-use crate::{handlers, AppState, middleware as app_middleware};
+use crate::{handlers, problem_repository, AppState, middleware as app_middleware};
 
 pub fn create_router() -> Router<AppState> {
     // 관리자 전용 라우트
@@ -18,9 +19,33 @@ pub fn create_router() -> Router<AppState> {
         .route("/admin/join-requests/pending", get(handlers::pending_join_requests))
         .route("/admin/join-requests/:id/review", post(handlers::review_join_request))
         .route("/admin/users/:id/promote", post(handlers::promote_to_admin))
+        .route("/admin/users/:id/setter", post(handlers::grant_problem_setter))
+        .route("/admin/rejudge", post(handlers::rejudge_submissions))
+        .route("/admin/problems/sync", post(handlers::sync_problems))
+        .route(
+            "/admin/problems/import",
+            post(handlers::import_problem_package)
+                .layer(DefaultBodyLimit::max(problem_repository::package::MAX_PACKAGE_BYTES)),
+        )
+        .route("/admin/problems/:id/export", get(handlers::export_problem_package))
         .route("/admin/organizations/:id/delete", post(handlers::delete_organization))
         .layer(middleware::from_fn(app_middleware::require_admin));
 
+    // 문제 편집기 라우트
+    let problem_editor_routes = Router::new()
+        .route("/problems/new", get(handlers::new_problem_page).post(handlers::create_problem))
+        .route("/problems/:id/edit", get(handlers::edit_problem_page).post(handlers::update_problem))
+        .route("/problems/:id/edit/preview", post(handlers::preview_statement))
+        .route("/problems/:id/testcases", post(handlers::update_testcases))
+        .route(
+            "/problems/:id/testcases/upload",
+            post(handlers::upload_testcase)
+                .layer(DefaultBodyLimit::max(problem_repository::testcases::MAX_UPLOAD_BYTES)),
+        )
+        .route("/problems/:id/testcases/:name/delete", post(handlers::delete_testcase))
+        .route("/problems/:id/validate", post(handlers::validate_problem))
+        .layer(middleware::from_fn(app_middleware::require_problem_editor));
+
     // 인증 필요 라우트 (그룹 관리 포함)
     let auth_required_routes = Router::new()
         .route("/organizations/create", post(handlers::create_organization))
@@ -36,6 +61,8 @@ pub fn create_router() -> Router<AppState> {
         .route("/contests/:contest_id/problems/:problem_id/remove", post(handlers::remove_contest_problem))
         .route("/contests/:contest_id/problems/:problem_id", get(handlers::contest_problem_detail))
         .route("/contests/:contest_id/problems/:problem_id/submit", post(handlers::submit_contest_problem))
+        // 실행 모드 (제출 없이 실행)
+        .route("/problems/:id/run", post(handlers::run_solution))
         // 게시판 라우트 (인증 필요)
         .route("/boards/:board_id/posts/new", get(handlers::new_post_form))
         .route("/boards/:board_id/posts", post(handlers::create_post))
@@ -58,6 +85,7 @@ pub fn create_router() -> Router<AppState> {
         .route("/problems/:id/status", get(handlers::problem_status))
         // Submissions
         .route("/submissions/:id", get(handlers::submission_detail))
+        .route("/submissions/:id/events", get(handlers::submission_events))
         // Auth
         .route("/login", get(handlers::login_page).post(handlers::login_action))
         .route("/register", get(handlers::register_page).post(handlers::register_action))
@@ -77,8 +105,15 @@ pub fn create_router() -> Router<AppState> {
         .route("/boards", get(handlers::boards_list))
         .route("/boards/:board_id", get(handlers::board_posts))
         .route("/boards/:board_id/posts/:post_id", get(handlers::post_detail))
+        // 원격 채점 워커 API (토큰 인증)
+        .route("/api/judge/lease", post(handlers::lease_judge_job))
+        .route("/api/judge/jobs/:id/heartbeat", post(handlers::judge_job_heartbeat))
+        .route("/api/judge/jobs/:id/result", post(handlers::judge_job_result))
+        .route("/api/judge/problems/:id/bundle", get(handlers::problem_bundle))
+        .route("/api/judge/problems/:id/files/*path", get(handlers::problem_bundle_file))
         // Merge protected routes
         .merge(admin_routes)
+        .merge(problem_editor_routes)
         .merge(auth_required_routes)
         // Static files
         .nest_service("/static", ServeDir::new("static"))
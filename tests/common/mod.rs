@@ -0,0 +1,217 @@
+// 통합 테스트 공용 하네스: 인메모리 SQLite + 가짜 채점기로 전체 라우터를 띄운다.
+#![allow(dead_code)]
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use nekonic_oj::{build_app, judge::FakeBackend};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+pub struct TestApp {
+    pub router: Router,
+    pub pool: SqlitePool,
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub location: Option<String>,
+    pub set_cookie: Option<String>,
+    pub body: String,
+}
+
+impl TestApp {
+    pub async fn spawn() -> Self {
+        Self::spawn_with_backend(FakeBackend::new()).await
+    }
+
+    pub async fn spawn_with_backend(backend: FakeBackend) -> Self {
+        Self::spawn_with_workers(backend, 1, None).await
+    }
+
+    /// 로컬 워커 수와 원격 워커 토큰을 지정해 띄운다.
+    pub async fn spawn_with_workers(backend: FakeBackend, judge_workers: usize, worker_token: Option<&str>) -> Self {
+        // 인메모리 DB는 연결마다 따로 생기므로 연결 하나를 계속 유지한다
+        let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap();
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .unwrap();
+
+        let router = build_app(pool.clone(), Arc::new(backend), judge_workers, worker_token.map(str::to_string))
+            .await
+            .unwrap();
+        Self { router, pool }
+    }
+
+    /// 원격 워커가 접속할 수 있도록 실제 포트에서 서버를 띄우고 주소를 돌려준다.
+    pub async fn serve(&self) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = self.router.clone();
+        tokio::spawn(async move { axum::serve(listener, router.into_make_service()).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    pub async fn get(&self, path: &str, cookie: Option<&str>) -> TestResponse {
+        let mut builder = Request::builder().method("GET").uri(path);
+        if let Some(cookie) = cookie {
+            builder = builder.header(header::COOKIE, cookie);
+        }
+        self.send(builder.body(Body::empty()).unwrap()).await
+    }
+
+    pub async fn post_form(&self, path: &str, fields: &[(&str, &str)], cookie: Option<&str>) -> TestResponse {
+        let body = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", urlencode(key), urlencode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+        if let Some(cookie) = cookie {
+            builder = builder.header(header::COOKIE, cookie);
+        }
+        self.send(builder.body(Body::from(body)).unwrap()).await
+    }
+
+    pub async fn post_json(&self, path: &str, body: &serde_json::Value, cookie: Option<&str>) -> TestResponse {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(cookie) = cookie {
+            builder = builder.header(header::COOKIE, cookie);
+        }
+        self.send(builder.body(Body::from(body.to_string())).unwrap()).await
+    }
+
+    /// `multipart/form-data` 요청. 각 필드는 (이름, 파일 이름, 내용)이다.
+    pub async fn post_multipart(&self, path: &str, fields: &[(&str, Option<&str>, &[u8])], cookie: Option<&str>) -> TestResponse {
+        let boundary = "nekonic-test-boundary";
+        let mut body = Vec::new();
+        for (name, filename, content) in fields {
+            body.extend_from_slice(format!("--{}\r\nContent-Disposition: form-data; name=\"{}\"", boundary, name).as_bytes());
+            if let Some(filename) = filename {
+                body.extend_from_slice(format!("; filename=\"{}\"", filename).as_bytes());
+            }
+            body.extend_from_slice(b"\r\n\r\n");
+            body.extend_from_slice(content);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary));
+        if let Some(cookie) = cookie {
+            builder = builder.header(header::COOKIE, cookie);
+        }
+        self.send(builder.body(Body::from(body)).unwrap()).await
+    }
+
+    /// 원격 워커 API처럼 Bearer 토큰으로 인증하는 JSON 요청
+    pub async fn post_json_with_token(&self, path: &str, body: &serde_json::Value, token: &str) -> TestResponse {
+        let request = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        self.send(request).await
+    }
+
+    async fn send(&self, request: Request<Body>) -> TestResponse {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let header_value = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let status = response.status();
+        let location = header_value(header::LOCATION);
+        let set_cookie = header_value(header::SET_COOKIE);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        TestResponse {
+            status,
+            location,
+            set_cookie,
+            body: String::from_utf8_lossy(&bytes).to_string(),
+        }
+    }
+
+    /// 회원가입 후 로그인하여 세션 쿠키(`name=value`)를 돌려준다.
+    pub async fn login_new_user(&self, username: &str) -> String {
+        let password = "password123";
+        self.post_form("/register", &[("username", username), ("password", password)], None)
+            .await;
+
+        let response = self
+            .post_form("/login", &[("username", username), ("password", password)], None)
+            .await;
+        let set_cookie = response.set_cookie.expect("login should set a session cookie");
+        set_cookie.split(';').next().unwrap().to_string()
+    }
+
+    pub async fn user_id(&self, username: &str) -> i64 {
+        sqlx::query_scalar("SELECT id FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_one(&self.pool)
+            .await
+            .unwrap()
+    }
+
+    /// 제출 후 리다이렉트 위치에서 제출 ID를 꺼낸다.
+    pub async fn submit(&self, path: &str, language: &str, source_code: &str, cookie: &str) -> i64 {
+        let response = self
+            .post_form(path, &[("language", language), ("source_code", source_code)], Some(cookie))
+            .await;
+        let location = response.location.expect("submission should redirect");
+        location.trim_start_matches("/submissions/").parse().unwrap()
+    }
+
+    /// 채점이 끝날 때까지 기다린 뒤 최종 결과를 돌려준다.
+    pub async fn wait_for_verdict(&self, submission_id: i64) -> String {
+        for _ in 0..200 {
+            let status: String = sqlx::query_scalar("SELECT status FROM submissions WHERE id = ?")
+                .bind(submission_id)
+                .fetch_one(&self.pool)
+                .await
+                .unwrap();
+            if status != "PENDING" && status != "JUDGING" {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+        panic!("submission {} was not judged in time", submission_id);
+    }
+}
+
+fn urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::TestApp;
+
+/// 진행 중인 대회를 만들고 1001번 문제를 `points`점으로 추가한다.
+async fn create_running_contest(app: &TestApp, creator_id: i64, contest_type: &str, points: i64) -> i64 {
+    let contest_id: i64 = sqlx::query_scalar(
+        "INSERT INTO contests (title, start_time, end_time, contest_type, status, created_by)
+         VALUES ('테스트 대회', datetime('now', '-30 minutes'), datetime('now', '+1 hour'), ?, 'approved', ?)
+         RETURNING id",
+    )
+    .bind(contest_type)
+    .bind(creator_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+
+    sqlx::query("INSERT INTO contest_problems (contest_id, problem_id, points, problem_order) VALUES (?, 1001, ?, 1)")
+        .bind(contest_id)
+        .bind(points)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    contest_id
+}
+
+#[tokio::test]
+async fn contest_submissions_update_standings() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("erin").await;
+    let user_id = app.user_id("erin").await;
+    let contest_id = create_running_contest(&app, user_id, "ICPC", 100).await;
+
+    app.post_form(&format!("/contests/{}/register", contest_id), &[], Some(&cookie))
+        .await;
+
+    let submit_path = format!("/contests/{}/problems/1001/submit", contest_id);
+    let wrong = app
+        .submit(&submit_path, "cpp", "// VERDICT: WRONG_ANSWER", &cookie)
+        .await;
+    assert_eq!(app.wait_for_verdict(wrong).await, "WRONG_ANSWER");
+
+    let accepted = app.submit(&submit_path, "cpp", "int main() {}", &cookie).await;
+    assert_eq!(app.wait_for_verdict(accepted).await, "ACCEPTED");
+
+    let (solved, penalty): (i64, i64) = sqlx::query_as(
+        "SELECT total_score, penalty_time FROM contest_participants WHERE contest_id = ? AND user_id = ?",
+    )
+    .bind(contest_id)
+    .bind(user_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(solved, 1);
+    // 약 30분 경과 + 오답 1회(20분)
+    assert!((49..=51).contains(&penalty), "unexpected penalty {}", penalty);
+
+    let page = app.get(&format!("/contests/{}/standings", contest_id), None).await;
+    assert!(page.status.is_success());
+    assert!(page.body.contains("erin"));
+}
+
+#[tokio::test]
+async fn point_contests_scale_scores_by_problem_points() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("judy").await;
+    let user_id = app.user_id("judy").await;
+    let contest_id = create_running_contest(&app, user_id, "IOI", 250).await;
+
+    app.post_form(&format!("/contests/{}/register", contest_id), &[], Some(&cookie))
+        .await;
+
+    let total_score = || async {
+        let (score, penalty): (i64, i64) = sqlx::query_as(
+            "SELECT total_score, penalty_time FROM contest_participants WHERE contest_id = ? AND user_id = ?",
+        )
+        .bind(contest_id)
+        .bind(user_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        assert_eq!(penalty, 0);
+        score
+    };
+
+    let submit_path = format!("/contests/{}/problems/1001/submit", contest_id);
+    let wrong = app
+        .submit(&submit_path, "cpp", "// VERDICT: WRONG_ANSWER", &cookie)
+        .await;
+    assert_eq!(app.wait_for_verdict(wrong).await, "WRONG_ANSWER");
+    let accepted = app.submit(&submit_path, "cpp", "int main() {}", &cookie).await;
+    assert_eq!(app.wait_for_verdict(accepted).await, "ACCEPTED");
+    nekonic_oj::contest_scoring::update_standings(&app.pool, contest_id, user_id)
+        .await
+        .unwrap();
+    assert_eq!(total_score().await, 250);
+
+    // 부분 점수는 배점에 맞춰 환산하고, 가장 높은 제출 점수를 쓴다
+    sqlx::query("UPDATE submissions SET score = 40 WHERE id = ?")
+        .bind(accepted)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE submissions SET score = 20 WHERE id = ?")
+        .bind(wrong)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    nekonic_oj::contest_scoring::update_standings(&app.pool, contest_id, user_id)
+        .await
+        .unwrap();
+    assert_eq!(total_score().await, 100);
+}
+
+#[tokio::test]
+async fn point_contests_use_default_max_score_when_missing() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("kate").await;
+    let user_id = app.user_id("kate").await;
+    let contest_id = create_running_contest(&app, user_id, "IOI", 250).await;
+
+    app.post_form(&format!("/contests/{}/register", contest_id), &[], Some(&cookie))
+        .await;
+
+    let submit_path = format!("/contests/{}/problems/1001/submit", contest_id);
+    let first = app
+        .submit(&submit_path, "cpp", "// VERDICT: WRONG_ANSWER", &cookie)
+        .await;
+    assert_eq!(app.wait_for_verdict(first).await, "WRONG_ANSWER");
+    let second = app
+        .submit(&submit_path, "cpp", "// VERDICT: WRONG_ANSWER", &cookie)
+        .await;
+    assert_eq!(app.wait_for_verdict(second).await, "WRONG_ANSWER");
+
+    let set_score = |submission_id: i64, score: i64, max_score: Option<i64>| {
+        let pool = app.pool.clone();
+        async move {
+            sqlx::query("UPDATE submissions SET score = ?, max_score = ? WHERE id = ?")
+                .bind(score)
+                .bind(max_score)
+                .bind(submission_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+    };
+    let total_score = || async {
+        nekonic_oj::contest_scoring::update_standings(&app.pool, contest_id, user_id)
+            .await
+            .unwrap();
+        let score: i64 = sqlx::query_scalar(
+            "SELECT total_score FROM contest_participants WHERE contest_id = ? AND user_id = ?",
+        )
+        .bind(contest_id)
+        .bind(user_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        score
+    };
+
+    // max_score가 없으면 100점 만점으로 보고 환산한다 (60 / 100 * 250)
+    set_score(first, 60, None).await;
+    set_score(second, 0, Some(5)).await;
+    assert_eq!(total_score().await, 150);
+
+    // max_score가 있으면 그 만점 기준으로 배점에 맞춘다 (4 / 5 * 250)
+    set_score(second, 4, Some(5)).await;
+    assert_eq!(total_score().await, 200);
+}
+
+#[tokio::test]
+async fn non_participants_cannot_submit() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("frank").await;
+    let contest_id = create_running_contest(&app, app.user_id("frank").await, "ICPC", 100).await;
+
+    let response = app
+        .post_form(
+            &format!("/contests/{}/problems/1001/submit", contest_id),
+            &[("language", "cpp"), ("source_code", "int main() {}")],
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, axum::http::StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,74 @@
+use nekonic_oj::judge::diagnostics::{parse, DiagnosticFormat};
+
+#[test]
+fn gcc_output_is_split_into_errors_and_warnings() {
+    let output = "\
+Main.cpp: In function 'int main()':
+Main.cpp:4:3: error: 'x' was not declared in this scope
+    4 |   x = 1;
+      |   ^
+Main.cpp:5:3: error: 'foo' was not declared in this scope
+    5 |   foo();
+      |   ^~~
+Main.cpp:3:7: warning: unused variable 'y' [-Wunused-variable]
+    3 |   int y;
+      |       ^
+";
+    let diagnostics = parse(Some(DiagnosticFormat::Gcc), output);
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].file_name.as_deref(), Some("Main.cpp"));
+    assert_eq!((diagnostics[0].line_number, diagnostics[0].column_number), (Some(4), Some(3)));
+    assert_eq!(diagnostics[0].severity, "error");
+    assert_eq!(diagnostics[0].error_message, "'x' was not declared in this scope");
+    assert_eq!(diagnostics[2].severity, "warning");
+    assert_eq!(diagnostics[2].error_type.as_deref(), Some("-Wunused-variable"));
+    assert_eq!(diagnostics[2].error_message, "unused variable 'y'");
+}
+
+#[test]
+fn javac_column_comes_from_the_caret_line() {
+    let output = "\
+Main.java:4: error: cannot find symbol
+        int y = x + 1;
+                ^
+  symbol:   variable x
+  location: class Main
+Main.java:6: warning: [unchecked] unchecked call to add(E) as a member of the raw type List
+        l.add(1);
+             ^
+2 errors
+1 warning
+";
+    let diagnostics = parse(Some(DiagnosticFormat::Javac), output);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!((diagnostics[0].line_number, diagnostics[0].column_number), (Some(4), Some(17)));
+    assert_eq!(
+        diagnostics[0].error_message,
+        "cannot find symbol\nsymbol:   variable x\nlocation: class Main"
+    );
+    assert_eq!(diagnostics[1].severity, "warning");
+    assert_eq!(diagnostics[1].error_type.as_deref(), Some("unchecked"));
+}
+
+#[test]
+fn python_syntax_error_points_at_the_submission() {
+    let output = "  File \"Main.py\", line 2\n    if x\n        ^\nSyntaxError: expected ':'\n";
+    let diagnostics = parse(Some(DiagnosticFormat::Python), output);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line_number, diagnostics[0].column_number), (Some(2), Some(5)));
+    assert_eq!(diagnostics[0].error_type.as_deref(), Some("SyntaxError"));
+    assert_eq!(diagnostics[0].error_message, "expected ':'");
+}
+
+#[test]
+fn unparsed_output_is_kept_as_a_single_error() {
+    let output = "/usr/bin/ld: main.o: undefined reference to `foo()'\ncollect2: error: ld returned 1 exit status\n";
+    let diagnostics = parse(Some(DiagnosticFormat::Gcc), output);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line_number, None);
+    assert_eq!(diagnostics[0].error_message, output);
+}
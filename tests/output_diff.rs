@@ -0,0 +1,41 @@
+use nekonic_oj::judge::output_diff::compare;
+
+#[test]
+fn first_difference_is_reported_by_line_and_column() {
+    let diff = compare("1 2 3\n4 5 6\n", "1 2 3\n4 7 6\n");
+
+    assert_eq!(diff.diff_line, Some(2));
+    assert_eq!(diff.diff_column, Some(3));
+    assert_eq!(diff.start_line, 1);
+    assert_eq!(diff.expected, "1 2 3\n4 5 6");
+    assert_eq!(diff.actual, "1 2 3\n4 7 6");
+}
+
+#[test]
+fn trailing_whitespace_and_blank_lines_are_not_differences() {
+    let diff = compare("Hello World!\n", "Hello World!   \n\n");
+
+    assert_eq!(diff.diff_line, None);
+    assert_eq!(diff.diff_column, None);
+}
+
+#[test]
+fn missing_lines_point_at_the_first_absent_line() {
+    let diff = compare("1\n2\n3\n", "1\n2\n");
+
+    assert_eq!(diff.diff_line, Some(3));
+    assert_eq!(diff.diff_column, Some(1));
+}
+
+#[test]
+fn long_outputs_keep_only_the_lines_around_the_difference() {
+    let expected: String = (1..=1000).map(|i| format!("{}\n", i)).collect();
+    let actual = expected.replace("\n700\n", "\n-1\n");
+    let diff = compare(&expected, &actual);
+
+    assert_eq!(diff.diff_line, Some(700));
+    assert_eq!(diff.start_line, 695);
+    assert!(diff.expected.starts_with("695\n"));
+    assert!(diff.actual.contains("-1"));
+    assert!(diff.expected.lines().count() <= 30);
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::path::PathBuf;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use nekonic_oj::models::FrontMatter;
+use nekonic_oj::problem_repository::{self, testcases, ProblemRepository};
+
+/// 실제 문제 폴더에 만들고 지우는 문제 번호 (예제 문제와 겹치지 않게 큰 번호를 쓴다)
+const EDITED_PROBLEM: i64 = 990001;
+
+fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("nekonic_editor_test_{}", uuid::Uuid::new_v4()))
+}
+
+fn remove_edited_problem() {
+    let dir = problem_repository::repository().problem_dir(EDITED_PROBLEM);
+    let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+}
+
+#[tokio::test]
+async fn problem_setters_create_edit_and_validate_problems() {
+    remove_edited_problem();
+    let app = TestApp::spawn().await;
+    let user_cookie = app.login_new_user("solver").await;
+    let response = app.get("/problems/new", Some(&user_cookie)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let cookie = app.login_new_user("setter").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'setter'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let id = EDITED_PROBLEM.to_string();
+    let response = app
+        .post_form("/problems/new", &[("id", id.as_str()), ("title", "새 문제")], Some(&cookie))
+        .await;
+    let edit_path = format!("/problems/{}/edit", EDITED_PROBLEM);
+    assert_eq!(response.location.as_deref(), Some(edit_path.as_str()));
+    assert_eq!(app.get(&edit_path, Some(&cookie)).await.status, StatusCode::OK);
+
+    let statement = "두 수를 더하시오.\r\n\r\n### 예제 입력 1\r\n\r\n```\r\n1 2\r\n```\r\n\r\n### 예제 출력 1\r\n\r\n```\r\n3\r\n```\r\n";
+    let response = app
+        .post_form(
+            &edit_path,
+            &[
+                ("title", "A+B"),
+                ("time_limit", "2000"),
+                ("memory_limit", "128"),
+                ("tags", "math, implementation,"),
+                ("statement", statement),
+            ],
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+
+    let repository = problem_repository::repository();
+    let problem = repository.load(EDITED_PROBLEM).await.unwrap();
+    assert_eq!(problem.meta.title, "A+B");
+    assert_eq!((problem.meta.time_limit, problem.meta.memory_limit), (2000, 128));
+    assert_eq!(problem.meta.tags, vec!["math".to_string(), "implementation".to_string()]);
+    assert_eq!(problem.samples.len(), 1);
+
+    let preview = app
+        .post_json(
+            &format!("{}/preview", edit_path),
+            &serde_json::json!({ "statement": "**굵게**\n\n### 예제 입력 1\n\n```\n1\n```\n" }),
+            Some(&cookie),
+        )
+        .await;
+    let preview: serde_json::Value = serde_json::from_str(&preview.body).unwrap();
+    assert!(preview["html"].as_str().unwrap().contains("<strong>굵게</strong>"));
+    assert_eq!(preview["samples"][0]["input"], "1\n");
+
+    // 출제자가 쓴 지문의 HTML은 스크립트와 이벤트 핸들러를 지운다
+    let preview = app
+        .post_json(
+            &format!("{}/preview", edit_path),
+            &serde_json::json!({ "statement": "<script>alert(1)</script>\n\n<img src=\"a.png\" onerror=\"alert(2)\"> x<sub>i</sub>" }),
+            Some(&cookie),
+        )
+        .await;
+    let preview: serde_json::Value = serde_json::from_str(&preview.body).unwrap();
+    let html = preview["html"].as_str().unwrap();
+    assert!(!html.contains("<script") && !html.contains("alert"), "{}", html);
+    assert!(html.contains("<img src=\"a.png\">") && html.contains("<sub>i</sub>"), "{}", html);
+
+    let upload_path = format!("/problems/{}/testcases/upload", EDITED_PROBLEM);
+    for (input, output) in [("1 2\n", "3\n"), ("5 7\n", "12\n")] {
+        let response = app
+            .post_multipart(
+                &upload_path,
+                &[
+                    ("name", None, b"".as_slice()),
+                    ("input", Some("case.in"), input.as_bytes()),
+                    ("output", Some("case.out"), output.as_bytes()),
+                ],
+                Some(&cookie),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::SEE_OTHER);
+    }
+    let names: Vec<String> = repository
+        .testcases(EDITED_PROBLEM)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["1".to_string(), "2".to_string()]);
+
+    let validate_path = format!("/problems/{}/validate", EDITED_PROBLEM);
+    let response = app
+        .post_json(
+            &validate_path,
+            &serde_json::json!({ "language": "cpp", "source_code": "// VERDICT: WRONG_ANSWER tc=2" }),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let result: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(result["status"], "WRONG_ANSWER");
+    assert_eq!(result["total_testcases"], 2);
+
+    // 제출을 만들지 않는다
+    let submissions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM submissions")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(submissions, 0);
+
+    let response = app
+        .post_form(&format!("/problems/{}/testcases/1/delete", EDITED_PROBLEM), &[], Some(&cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    assert_eq!(repository.testcases(EDITED_PROBLEM).await.unwrap().len(), 1);
+
+    remove_edited_problem();
+}
+
+#[tokio::test]
+async fn reordering_renumbers_testcases_and_updates_samples() {
+    let root = temp_root();
+    let repo = ProblemRepository::new(&root);
+    repo.create(1, &FrontMatter::new("A".to_string()), "본문").await.unwrap();
+    for (name, input) in [("a", "first\n"), ("b", "second\n"), ("c", "third\n")] {
+        repo.save_testcase(1, name, input.as_bytes(), Some(b"ok\n".as_slice())).await.unwrap();
+    }
+    assert!(repo.save_testcase(1, "../escape", b"", None).await.is_err());
+
+    let order = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+    repo.set_sample_testcases(1, &order[..1]).await.unwrap();
+    repo.reorder_testcases(1, &order).await.unwrap();
+
+    let names: Vec<String> = repo.testcases(1).await.unwrap().into_iter().map(|t| t.name).collect();
+    assert_eq!(names, vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+    let testcase_dir = repo.problem_dir(1).join("testcases");
+    assert_eq!(std::fs::read_to_string(testcase_dir.join("1.in")).unwrap(), "third\n");
+
+    let samples = repo.samples(1).await.unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].input, "third\n");
+
+    // 목록이 바뀐 뒤의 순서는 받지 않는다
+    assert!(repo.reorder_testcases(1, &order).await.is_err());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn renumbering_keeps_subtask_prefixes() {
+    let order: Vec<String> = ["sub2_x", "sub1_b", "free", "sub1_a", "sub10_1"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mut many: Vec<String> = (0..10).map(|i| format!("sub3_{}", i)).collect();
+    many.extend(order);
+
+    let renamed = testcases::renumbered(&many, &["sub1_", "sub10_", "sub2_", "sub3_"]);
+    let targets: Vec<&str> = renamed.iter().map(|(_, to)| to.as_str()).collect();
+    assert_eq!(&targets[..2], &["sub3_01", "sub3_02"]);
+    assert_eq!(targets[9], "sub3_10");
+    assert_eq!(&targets[10..], &["sub2_1", "sub1_1", "1", "sub1_2", "sub10_1"]);
+}
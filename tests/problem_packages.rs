@@ -0,0 +1,200 @@
+mod common;
+
+use std::io::{Cursor, Read, Write};
+use std::path::PathBuf;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use nekonic_oj::judge::checker::CheckerSpec;
+use nekonic_oj::problem_repository::package::PackageFormat;
+use nekonic_oj::problem_repository::ProblemRepository;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("nekonic_package_test_{}", uuid::Uuid::new_v4()))
+}
+
+fn zip_of(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+const PROBLEM_MD: &str = "---\ntitle: A+B\ntime_limit: 1000\nmemory_limit: 256\ntags: []\nchecker:\n  type: custom\n  source: checker.cpp\n---\n두 수를 더하시오.\n";
+
+#[tokio::test]
+async fn exported_package_imports_under_another_id() {
+    let root = temp_root();
+    let repo = ProblemRepository::new(&root);
+    let package = zip_of(&[
+        ("a_plus_b/problem.md", PROBLEM_MD),
+        ("a_plus_b/checker.cpp", "int main() {}"),
+        ("a_plus_b/testcases/1.in", "1 2\n"),
+        ("a_plus_b/testcases/1.out", "3\n"),
+    ]);
+
+    let report = repo.import_package(5, package, false).await.unwrap();
+    assert_eq!(report.format, PackageFormat::Native);
+    assert_eq!(report.title, "A+B");
+    assert_eq!(report.testcases, 1);
+    assert_eq!(std::fs::read_to_string(repo.markdown_path(5)).unwrap(), PROBLEM_MD);
+
+    let exported = repo.export_package(5).await.unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(exported.clone())).unwrap();
+    let mut statement = String::new();
+    archive.by_name("problem.md").unwrap().read_to_string(&mut statement).unwrap();
+    assert_eq!(statement, PROBLEM_MD);
+
+    repo.import_package(2001, exported, false).await.unwrap();
+    let problem = repo.load(2001).await.unwrap();
+    assert_eq!(problem.meta.title, "A+B");
+    assert!(repo.problem_dir(2001).join("checker.cpp").is_file());
+    assert_eq!(std::fs::read_to_string(repo.problem_dir(2001).join("testcases/1.out")).unwrap(), "3\n");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn import_rejects_existing_problems_and_unsafe_packages() {
+    let root = temp_root();
+    let repo = ProblemRepository::new(&root);
+    let package = zip_of(&[
+        ("problem.md", PROBLEM_MD),
+        ("checker.cpp", "int main() {}"),
+        ("testcases/1.in", "1 2\n"),
+    ]);
+    repo.import_package(7, package.clone(), false).await.unwrap();
+
+    // 덮어쓰기를 지정하지 않으면 기존 문제를 바꾸지 않는다
+    assert!(repo.import_package(7, package.clone(), false).await.is_err());
+    repo.import_package(7, package, true).await.unwrap();
+
+    // front matter가 가리키는 체커가 없는 패키지
+    let missing_checker = zip_of(&[("problem.md", PROBLEM_MD), ("testcases/1.in", "1 2\n")]);
+    assert!(repo.import_package(8, missing_checker, false).await.is_err());
+
+    // 문제 폴더 밖을 가리키는 경로
+    let escaping = zip_of(&[
+        ("problem.md", PROBLEM_MD),
+        ("checker.cpp", "int main() {}"),
+        ("testcases/1.in", "1 2\n"),
+        ("../../escaped.txt", "oops"),
+    ]);
+    assert!(repo.import_package(9, escaping, false).await.is_err());
+    assert!(!repo.problem_dir(9).exists());
+    assert!(!root.parent().unwrap().join("escaped.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+const POLYGON_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<problem revision="3" short-name="a-plus-b">
+  <names>
+    <name language="english" value="A + B"/>
+  </names>
+  <judging>
+    <testset name="tests">
+      <time-limit>2000</time-limit>
+      <memory-limit>268435456</memory-limit>
+      <test-count>3</test-count>
+      <input-path-pattern>tests/%02d</input-path-pattern>
+      <answer-path-pattern>tests/%02d.a</answer-path-pattern>
+      <tests>
+        <test method="manual" sample="true" group="0"/>
+        <test method="generated" group="1" points="20"/>
+        <test method="generated" group="1" points="20"/>
+      </tests>
+      <groups>
+        <group name="0" points="0" points-policy="complete-group"/>
+        <group name="1" points-policy="complete-group"/>
+      </groups>
+    </testset>
+  </judging>
+  <assets>
+    <checker name="check.cpp" type="testlib">
+      <source path="files/check.cpp" type="cpp.g++17"/>
+    </checker>
+    <validators>
+      <validator>
+        <source path="files/val.cpp" type="cpp.g++17"/>
+      </validator>
+    </validators>
+  </assets>
+  <tags>
+    <tag value="math"/>
+  </tags>
+</problem>
+"#;
+
+#[tokio::test]
+async fn polygon_packages_are_converted() {
+    let root = temp_root();
+    let repo = ProblemRepository::new(&root);
+    let package = zip_of(&[
+        ("problem.xml", POLYGON_XML),
+        ("statement-sections/english/legend.tex", "Print $a+b$."),
+        ("statement-sections/english/input.tex", "Two integers."),
+        ("statement-sections/english/output.tex", "Their sum."),
+        ("tests/01", "1 2\n"),
+        ("tests/01.a", "3\n"),
+        ("tests/02", "5 7\n"),
+        ("tests/02.a", "12\n"),
+        ("tests/03", "0 0\n"),
+        ("tests/03.a", "0\n"),
+        ("files/check.cpp", "#include \"testlib.h\"\nint main() {}"),
+        ("files/testlib.h", "// testlib"),
+        ("files/val.cpp", "#include \"testlib.h\"\nint main() {}"),
+    ]);
+
+    let report = repo.import_package(10, package, false).await.unwrap();
+    assert_eq!(report.format, PackageFormat::Polygon);
+    assert_eq!(report.testcases, 3);
+
+    let problem = repo.load(10).await.unwrap();
+    assert_eq!(problem.meta.title, "A + B");
+    assert_eq!((problem.meta.time_limit, problem.meta.memory_limit), (2000, 256));
+    assert_eq!(problem.meta.tags, vec!["math".to_string()]);
+    assert_eq!(
+        problem.meta.checker,
+        CheckerSpec::Custom { source: "checker.cpp".to_string(), testlib: true }
+    );
+    assert_eq!(problem.meta.validator.as_deref(), Some("validator.cpp"));
+    assert!(problem.content.contains("### 입력"));
+
+    let subtasks: Vec<(String, i32)> = problem.meta.subtasks.iter().map(|s| (s.prefix.clone(), s.score)).collect();
+    assert_eq!(subtasks, vec![("g0_".to_string(), 0), ("g1_".to_string(), 40)]);
+
+    let dir = repo.problem_dir(10);
+    assert!(dir.join("testlib.h").is_file());
+    assert_eq!(std::fs::read_to_string(dir.join("testcases/g1_02.out")).unwrap(), "12\n");
+    let samples = repo.samples(10).await.unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].input, "1 2\n");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn admins_can_download_problem_packages() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("setter").await;
+    let response = app.get("/admin/problems/1001/export", Some(&cookie)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let admin_cookie = app.login_new_user("admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let response = app.get("/admin/problems/1001/export", Some(&admin_cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.starts_with("PK"));
+
+    let missing = app.get("/admin/problems/999999/export", Some(&admin_cookie)).await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,232 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use nekonic_oj::models::{FrontMatter, ProblemVisibility};
+use nekonic_oj::problem_repository::repository;
+
+/// 테스트마다 실제 문제 폴더에 만들고 지우는 문제 번호 (예제 문제와 다른 bucket)
+const SETTER_PROBLEM: i64 = 991001;
+const HIDDEN_PROBLEM: i64 = 991002;
+const DRAFT_PROBLEM: i64 = 991003;
+
+fn remove_problem(id: i64) {
+    let dir = repository().problem_dir(id);
+    let _ = std::fs::remove_dir_all(&dir);
+    // 다른 테스트의 문제가 남아 있으면 bucket은 지워지지 않는다
+    let _ = std::fs::remove_dir(dir.parent().unwrap());
+}
+
+async fn login_with_role(app: &TestApp, username: &str, role: &str) -> String {
+    let cookie = app.login_new_user(username).await;
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    cookie
+}
+
+async fn create_problem(app: &TestApp, id: i64, title: &str, visibility: ProblemVisibility) {
+    remove_problem(id);
+    let meta = FrontMatter {
+        visibility,
+        ..FrontMatter::new(title.to_string())
+    };
+    repository().create(id, &meta, "두 수를 더하시오.").await.unwrap();
+    repository().refresh_index(&app.pool, id).await.unwrap();
+}
+
+/// `start`/`end`는 SQLite `datetime('now', ...)` 수정자 (예: `'-30 minutes'`)
+async fn create_contest(app: &TestApp, start: &str, end: &str, problems: &[i64], participant: i64) -> i64 {
+    let contest_id: i64 = sqlx::query_scalar(
+        "INSERT INTO contests (title, start_time, end_time, status, created_by)
+         VALUES ('비공개 문제 대회', datetime('now', ?), datetime('now', ?), 'approved', ?)
+         RETURNING id",
+    )
+    .bind(start)
+    .bind(end)
+    .bind(participant)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+
+    for (order, problem_id) in problems.iter().enumerate() {
+        sqlx::query("INSERT INTO contest_problems (contest_id, problem_id, points, problem_order) VALUES (?, ?, 100, ?)")
+            .bind(contest_id)
+            .bind(problem_id)
+            .bind(order as i64 + 1)
+            .execute(&app.pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query("INSERT INTO contest_participants (contest_id, user_id) VALUES (?, ?)")
+        .bind(contest_id)
+        .bind(participant)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    contest_id
+}
+
+#[tokio::test]
+async fn setters_see_only_their_own_unpublished_problems() {
+    remove_problem(SETTER_PROBLEM);
+    let app = TestApp::spawn().await;
+    let setter = login_with_role(&app, "setter", "setter").await;
+    let other_setter = login_with_role(&app, "other_setter", "setter").await;
+    let viewer = app.login_new_user("viewer").await;
+
+    let id = SETTER_PROBLEM.to_string();
+    let response = app
+        .post_form("/problems/new", &[("id", id.as_str()), ("title", "출제 중인 문제")], Some(&setter))
+        .await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    assert_eq!(repository().load(SETTER_PROBLEM).await.unwrap().meta.visibility, ProblemVisibility::Draft);
+
+    let problem_path = format!("/problems/{}", SETTER_PROBLEM);
+    assert_eq!(app.get(&problem_path, Some(&setter)).await.status, StatusCode::OK);
+    for cookie in [None, Some(viewer.as_str()), Some(other_setter.as_str())] {
+        assert_eq!(app.get(&problem_path, cookie).await.status, StatusCode::NOT_FOUND);
+        assert_eq!(app.get(&format!("{}/status", problem_path), cookie).await.status, StatusCode::NOT_FOUND);
+    }
+    let response = app
+        .post_form(
+            &format!("{}/submit", problem_path),
+            &[("language", "cpp"), ("source_code", "int main() {}")],
+            Some(&viewer),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    assert!(app.get("/problems", Some(&setter)).await.body.contains("출제 중인 문제"));
+    assert!(!app.get("/problems", Some(&viewer)).await.body.contains("출제 중인 문제"));
+    assert!(!app.get("/problems", Some(&other_setter)).await.body.contains("출제 중인 문제"));
+
+    let edit_path = format!("{}/edit", problem_path);
+    assert_eq!(app.get(&edit_path, Some(&other_setter)).await.status, StatusCode::FORBIDDEN);
+    assert_eq!(app.get(&edit_path, Some(&viewer)).await.status, StatusCode::FORBIDDEN);
+
+    // 출제자는 공개할 수 없고, 관리자가 공개한다
+    let edit = |visibility: &'static str| {
+        [
+            ("title", "출제 중인 문제"),
+            ("time_limit", "1000"),
+            ("memory_limit", "256"),
+            ("statement", "두 수를 더하시오."),
+            ("visibility", visibility),
+        ]
+    };
+    let response = app.post_form(&edit_path, &edit("public"), Some(&setter)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+    let response = app.post_form(&edit_path, &edit("hidden"), Some(&setter)).await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    assert_eq!(app.get(&problem_path, Some(&viewer)).await.status, StatusCode::NOT_FOUND);
+
+    let admin = login_with_role(&app, "admin", "admin").await;
+    let response = app.post_form(&edit_path, &edit("public"), Some(&admin)).await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    assert_eq!(app.get(&problem_path, Some(&viewer)).await.status, StatusCode::OK);
+    assert!(app.get("/problems", None).await.body.contains("출제 중인 문제"));
+
+    remove_problem(SETTER_PROBLEM);
+}
+
+#[tokio::test]
+async fn hidden_problems_open_only_through_running_contests() {
+    let app = TestApp::spawn().await;
+    create_problem(&app, HIDDEN_PROBLEM, "대회 전용 문제", ProblemVisibility::Hidden).await;
+    create_problem(&app, DRAFT_PROBLEM, "작성 중인 대회 문제", ProblemVisibility::Draft).await;
+    let cookie = app.login_new_user("contestant").await;
+    let user_id = app.user_id("contestant").await;
+
+    assert_eq!(app.get(&format!("/problems/{}", HIDDEN_PROBLEM), Some(&cookie)).await.status, StatusCode::NOT_FOUND);
+    assert!(!app.get("/problems", Some(&cookie)).await.body.contains("대회 전용 문제"));
+    let run = |problem_id: i64, cookie: String| {
+        let app = &app;
+        async move {
+            app.post_json(
+                &format!("/problems/{}/run", problem_id),
+                &serde_json::json!({ "language": "cpp", "source_code": "int main() {}", "input": "1 2" }),
+                Some(&cookie),
+            )
+            .await
+            .status
+        }
+    };
+    assert_eq!(run(HIDDEN_PROBLEM, cookie.clone()).await, StatusCode::NOT_FOUND);
+
+    let running = create_contest(&app, "-30 minutes", "+1 hour", &[HIDDEN_PROBLEM, DRAFT_PROBLEM], user_id).await;
+    let page = app
+        .get(&format!("/contests/{}/problems/{}", running, HIDDEN_PROBLEM), Some(&cookie))
+        .await;
+    assert_eq!(page.status, StatusCode::OK);
+    assert!(page.body.contains("대회 전용 문제"));
+    assert!(page.body.contains("두 수를 더하시오."));
+    assert!(page.body.contains("run-button"));
+
+    // 진행 중인 대회의 참가자는 대회 전용 문제도 제출 없이 실행해 볼 수 있다
+    assert_eq!(run(HIDDEN_PROBLEM, cookie.clone()).await, StatusCode::OK);
+    assert_eq!(run(DRAFT_PROBLEM, cookie.clone()).await, StatusCode::NOT_FOUND);
+    let outsider = app.login_new_user("outsider").await;
+    assert_eq!(run(HIDDEN_PROBLEM, outsider).await, StatusCode::NOT_FOUND);
+
+    let submit_path = format!("/contests/{}/problems/{}/submit", running, HIDDEN_PROBLEM);
+    let submission = app.submit(&submit_path, "cpp", "int main() {}", &cookie).await;
+    assert!(submission > 0);
+
+    // 비공개 문제의 제출은 제출한 사용자만 본다
+    let submission_path = format!("/submissions/{}", submission);
+    assert_eq!(app.get(&submission_path, Some(&cookie)).await.status, StatusCode::OK);
+    let other = app.login_new_user("spectator").await;
+    for viewer in [None, Some(other.as_str())] {
+        assert_eq!(app.get(&submission_path, viewer).await.status, StatusCode::NOT_FOUND);
+        assert_eq!(app.get(&format!("{}/events", submission_path), viewer).await.status, StatusCode::NOT_FOUND);
+    }
+
+    // 작성 중인 문제는 대회에 들어 있어도 열리지 않는다
+    let draft_page = app
+        .get(&format!("/contests/{}/problems/{}", running, DRAFT_PROBLEM), Some(&cookie))
+        .await;
+    assert_eq!(draft_page.status, StatusCode::NOT_FOUND);
+    let response = app
+        .post_form(
+            &format!("/contests/{}/problems/{}/submit", running, DRAFT_PROBLEM),
+            &[("language", "cpp"), ("source_code", "int main() {}")],
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    // 대회에 없는 문제는 대회로 제출할 수 없다
+    let response = app
+        .post_form(
+            &format!("/contests/{}/problems/1001/submit", running),
+            &[("language", "cpp"), ("source_code", "int main() {}")],
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    // 대회를 만든 사람이라도 편집할 수 없는 비공개 문제는 대회에 넣지 못한다
+    let hidden_id = HIDDEN_PROBLEM.to_string();
+    let response = app
+        .post_form(
+            &format!("/contests/{}/problems/add", running),
+            &[("problem_id", hidden_id.as_str()), ("points", "100"), ("problem_order", "3")],
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let upcoming = create_contest(&app, "+1 hour", "+2 hours", &[HIDDEN_PROBLEM], user_id).await;
+    let page = app
+        .get(&format!("/contests/{}/problems/{}", upcoming, HIDDEN_PROBLEM), Some(&cookie))
+        .await;
+    assert_eq!(page.status, StatusCode::FORBIDDEN);
+
+    remove_problem(HIDDEN_PROBLEM);
+    remove_problem(DRAFT_PROBLEM);
+}
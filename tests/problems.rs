@@ -0,0 +1,112 @@
+mod common;
+
+use common::TestApp;
+use nekonic_oj::problem_repository::samples::extract_from_markdown;
+use nekonic_oj::problem_repository::{repository, ProblemRepository};
+
+#[test]
+fn problem_folders_are_bucketed_by_thousands() {
+    let repo = repository();
+    assert!(repo.problem_dir(1001).ends_with("problems/001000/1001"));
+    assert!(repo.problem_dir(999).ends_with("problems/000000/999"));
+    assert!(repo.problem_dir(2000).ends_with("problems/002000/2000"));
+}
+
+#[tokio::test]
+async fn problem_index_is_synced_at_startup() {
+    let app = TestApp::spawn().await;
+
+    let (title, time_limit, memory_limit, tags): (String, i64, i64, String) =
+        sqlx::query_as("SELECT title, time_limit, memory_limit, tags FROM problems WHERE id = 1001")
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(title, "Hello World!");
+    assert_eq!((time_limit, memory_limit), (1000, 128));
+    assert_eq!(tags, r#"["시작하기"]"#);
+
+    let page = app.get("/problems", None).await;
+    assert!(page.status.is_success());
+    assert!(page.body.contains("Hello World!"));
+}
+
+#[tokio::test]
+async fn problems_removed_from_disk_leave_the_index() {
+    let app = TestApp::spawn().await;
+    sqlx::query(
+        "INSERT INTO problems (id, title, time_limit, memory_limit, checksum) VALUES (999999, '사라진 문제', 1000, 256, '')",
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let report = repository().sync_index(&app.pool).await.unwrap();
+    assert_eq!(report.removed, 1);
+    assert_eq!(report.updated, 0);
+
+    let page = app.get("/problems?page=5", None).await;
+    assert!(page.status.is_success());
+    assert!(!page.body.contains("사라진 문제"));
+}
+
+#[test]
+fn samples_are_extracted_from_markdown_and_removed_from_the_body() {
+    let markdown = "\
+두 수를 더하시오.
+
+### 예제 입력 1
+```
+1 2
+```
+
+### 예제 출력 1
+
+```
+3
+```
+
+### 예제 입력 설명
+입력 설명은 본문에 남는다.
+";
+    let (samples, body) = extract_from_markdown(markdown);
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].number, 1);
+    assert_eq!(samples[0].input, "1 2\n");
+    assert_eq!(samples[0].output, "3\n");
+    assert!(body.contains("두 수를 더하시오."));
+    assert!(body.contains("입력 설명은 본문에 남는다."));
+    assert!(!body.contains("1 2"));
+}
+
+#[tokio::test]
+async fn testcases_matching_samples_are_reported() {
+    let root = std::env::temp_dir().join(format!("nekonic_samples_test_{}", uuid::Uuid::new_v4()));
+    let repo = ProblemRepository::new(&root);
+    let testcases = repo.problem_dir(5).join("testcases");
+    std::fs::create_dir_all(&testcases).unwrap();
+    std::fs::write(
+        repo.markdown_path(5),
+        "---\ntitle: A+B\ntime_limit: 1000\nmemory_limit: 256\ntags: []\n---\n### 예제 입력 1\n```\n1 2\n```\n### 예제 출력 1\n```\n3\n```\n",
+    )
+    .unwrap();
+    std::fs::write(testcases.join("1.in"), "1 2\n").unwrap();
+    std::fs::write(testcases.join("1.out"), "3 \n\n").unwrap();
+    std::fs::write(testcases.join("2.in"), "5 7\n").unwrap();
+    std::fs::write(testcases.join("2.out"), "12\n").unwrap();
+
+    let samples = repo.samples(5).await.unwrap();
+    assert_eq!(repo.sample_testcases(5, &samples).await, vec!["1".to_string()]);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn problem_page_shows_samples_with_copy_buttons() {
+    let app = TestApp::spawn().await;
+    let page = app.get("/problems/1001", None).await;
+
+    assert!(page.status.is_success());
+    assert!(page.body.contains("sample-output-1"));
+    assert!(page.body.contains("copy-sample"));
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use nekonic_oj::judge::FakeBackend;
+use nekonic_oj::judge_worker::{RemoteWorker, WorkerConfig};
+use serde_json::json;
+
+const TOKEN: &str = "test-worker-token";
+
+fn worker(server_url: String, cache_dir: PathBuf) -> RemoteWorker {
+    let config = WorkerConfig {
+        server_url,
+        token: TOKEN.to_string(),
+        worker_id: "test-worker".to_string(),
+        cache_dir,
+        lease_secs: 30,
+    };
+    RemoteWorker::new(config, Arc::new(FakeBackend::new()))
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("nekonic_worker_test_{}", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn remote_worker_judges_leased_submission() {
+    let app = TestApp::spawn_with_workers(FakeBackend::new(), 0, Some(TOKEN)).await;
+    let cookie = app.login_new_user("alice").await;
+    let submission_id = app
+        .submit("/problems/1001/submit", "cpp", "int main() {}", &cookie)
+        .await;
+
+    let cache = cache_dir();
+    let worker = worker(app.serve().await, cache.clone());
+    assert!(worker.run_once().await.unwrap());
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let (testcases,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM testcase_results WHERE submission_id = ?")
+        .bind(submission_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(testcases, 1);
+    // 테스트케이스는 워커가 받아 둔 번들에서 읽는다
+    assert!(cache.join("problems").join("1001").is_dir());
+
+    let _ = std::fs::remove_dir_all(&cache);
+}
+
+#[tokio::test]
+async fn expired_lease_is_requeued_and_stale_result_rejected() {
+    let app = TestApp::spawn_with_workers(FakeBackend::new(), 0, Some(TOKEN)).await;
+    let cookie = app.login_new_user("bob").await;
+    let submission_id = app
+        .submit("/problems/1001/submit", "cpp", "int main() {}", &cookie)
+        .await;
+
+    // 하트비트를 보내지 않고 사라지는 워커
+    let lease = app
+        .post_json_with_token(
+            "/api/judge/lease",
+            &json!({ "worker_id": "ghost", "lease_secs": 1, "wait_secs": 0 }),
+            TOKEN,
+        )
+        .await;
+    assert_eq!(lease.status, StatusCode::OK);
+    let job: serde_json::Value = serde_json::from_str(&lease.body).unwrap();
+
+    let mut requeued = false;
+    for _ in 0..100 {
+        let status: String = sqlx::query_scalar("SELECT status FROM judge_queue WHERE submission_id = ?")
+            .bind(submission_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+        if status == "queued" {
+            requeued = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(requeued, "expired lease should be requeued");
+
+    let cache = cache_dir();
+    let worker = worker(app.serve().await, cache.clone());
+    assert!(worker.run_once().await.unwrap());
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    // 늦게 도착한 결과는 받지 않는다
+    let stale = app
+        .post_json_with_token(
+            &format!("/api/judge/jobs/{}/result", job["queue_id"]),
+            &json!({ "lease_token": job["lease_token"], "error": "late" }),
+            TOKEN,
+        )
+        .await;
+    assert_eq!(stale.status, StatusCode::CONFLICT);
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let _ = std::fs::remove_dir_all(&cache);
+}
+
+#[tokio::test]
+async fn rejudge_during_a_lease_waits_for_it_and_discards_its_result() {
+    let app = TestApp::spawn_with_workers(FakeBackend::new(), 0, Some(TOKEN)).await;
+    let cookie = app.login_new_user("carol").await;
+    let submission_id = app
+        .submit("/problems/1001/submit", "cpp", "int main() {}", &cookie)
+        .await;
+
+    let lease = |worker_id: &'static str| {
+        let app = &app;
+        async move {
+            app.post_json_with_token(
+                "/api/judge/lease",
+                &json!({ "worker_id": worker_id, "lease_secs": 30, "wait_secs": 0 }),
+                TOKEN,
+            )
+            .await
+        }
+    };
+    let first = lease("first").await;
+    assert_eq!(first.status, StatusCode::OK);
+    let job: serde_json::Value = serde_json::from_str(&first.body).unwrap();
+
+    let admin_cookie = app.login_new_user("admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    app.post_form("/admin/rejudge", &[("problem_id", "1001")], Some(&admin_cookie))
+        .await;
+
+    // 채점 중인 작업은 다른 워커가 또 가져가지 않는다
+    assert_eq!(lease("second").await.status, StatusCode::NO_CONTENT);
+
+    // 재채점 전에 시작한 채점 결과는 버리고 작업을 다시 대기열에 넣는다
+    let stale = app
+        .post_json_with_token(
+            &format!("/api/judge/jobs/{}/result", job["queue_id"]),
+            &json!({ "lease_token": job["lease_token"], "error": "judged before the rejudge" }),
+            TOKEN,
+        )
+        .await;
+    assert_eq!(stale.status, StatusCode::CONFLICT);
+    let status: String = sqlx::query_scalar("SELECT status FROM submissions WHERE id = ?")
+        .bind(submission_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "PENDING");
+
+    let cache = cache_dir();
+    let worker = worker(app.serve().await, cache.clone());
+    assert!(worker.run_once().await.unwrap());
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let _ = std::fs::remove_dir_all(&cache);
+}
+
+#[tokio::test]
+async fn worker_api_requires_the_token() {
+    let app = TestApp::spawn_with_workers(FakeBackend::new(), 0, Some(TOKEN)).await;
+    let response = app
+        .post_json_with_token("/api/judge/lease", &json!({ "worker_id": "w", "lease_secs": 30 }), "wrong")
+        .await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    // 토큰이 설정되지 않은 서버에는 API가 없다
+    let app = TestApp::spawn().await;
+    let response = app
+        .post_json_with_token("/api/judge/lease", &json!({ "worker_id": "w", "lease_secs": 30 }), TOKEN)
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,66 @@
+use nekonic_oj::judge::runtime_error::classify;
+
+#[test]
+fn signals_are_reported_by_name() {
+    assert_eq!(classify(11, 139, "").error_type, "SIGSEGV");
+    assert_eq!(classify(8, 136, "").error_type, "SIGFPE");
+    assert_eq!(classify(64, 192, "").error_type, "SIGNAL_64");
+}
+
+#[test]
+fn uncaught_cpp_exception_is_named_in_the_abort_message() {
+    let stderr = "\
+terminate called after throwing an instance of 'std::out_of_range'
+  what():  vector::_M_range_check: __n (which is 5) >= this->size() (which is 3)
+";
+    let error = classify(6, 134, stderr);
+
+    assert_eq!(error.error_type, "SIGABRT");
+    assert!(error.message.contains("std::out_of_range"));
+    assert!(error.message.contains("vector::_M_range_check"));
+}
+
+#[test]
+fn java_exception_class_becomes_the_error_type() {
+    let stderr = "\
+Exception in thread \"main\" java.lang.ArrayIndexOutOfBoundsException: Index 5 out of bounds for length 3
+\tat Main.main(Main.java:6)
+";
+    let error = classify(0, 1, stderr);
+
+    assert_eq!(error.error_type, "ArrayIndexOutOfBoundsException");
+    assert_eq!(error.message, "ArrayIndexOutOfBoundsException: Index 5 out of bounds for length 3");
+}
+
+#[test]
+fn python_exception_type_comes_from_the_last_traceback_line() {
+    let stderr = "\
+debug output
+Traceback (most recent call last):
+  File \"Main.py\", line 3, in <module>
+    print(a // b)
+          ~~^^~~
+ZeroDivisionError: integer division or modulo by zero
+";
+    let error = classify(0, 1, stderr);
+
+    assert_eq!(error.error_type, "ZeroDivisionError");
+    assert_eq!(error.message, "ZeroDivisionError: integer division or modulo by zero");
+}
+
+#[test]
+fn plain_exit_code_does_not_leak_stderr() {
+    let error = classify(0, 3, "secret testcase data\n");
+
+    assert_eq!(error.error_type, "NON_ZERO_EXIT");
+    assert!(error.message.contains('3'));
+    assert!(!error.message.contains("secret"));
+}
+
+#[test]
+fn long_exception_messages_are_trimmed() {
+    let stderr = format!("Exception in thread \"main\" java.lang.IllegalStateException: {}\n", "x".repeat(1000));
+    let error = classify(0, 1, &stderr);
+
+    assert!(error.message.chars().count() < 300);
+}
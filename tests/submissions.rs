@@ -0,0 +1,255 @@
+mod common;
+
+use common::TestApp;
+use nekonic_oj::judge::FakeBackend;
+
+#[tokio::test]
+async fn accepted_submission_is_judged_and_counted() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("alice").await;
+
+    let submission_id = app
+        .submit("/problems/1001/submit", "cpp", "int main() {}", &cookie)
+        .await;
+
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let (total_solved,): (i64,) = sqlx::query_as("SELECT total_solved FROM user_stats WHERE user_id = ?")
+        .bind(app.user_id("alice").await)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(total_solved, 1);
+
+    let page = app.get(&format!("/submissions/{}", submission_id), None).await;
+    assert!(page.status.is_success());
+    assert!(page.body.contains("정답"));
+}
+
+#[tokio::test]
+async fn verdict_marker_fails_the_given_testcase() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("bob").await;
+
+    let source = "// VERDICT: WRONG_ANSWER tc=3\nint main() {}";
+    let submission_id = app.submit("/problems/1001/submit", "cpp", source, &cookie).await;
+
+    assert_eq!(app.wait_for_verdict(submission_id).await, "WRONG_ANSWER");
+
+    let rows: Vec<(i32, String)> = sqlx::query_as(
+        "SELECT testcase_number, status FROM testcase_results WHERE submission_id = ? ORDER BY testcase_number",
+    )
+    .bind(submission_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2], (3, "WRONG_ANSWER".to_string()));
+    assert!(rows[..2].iter().all(|(_, status)| status == "ACCEPTED"));
+}
+
+#[tokio::test]
+async fn compilation_error_is_recorded() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("carol").await;
+
+    let submission_id = app
+        .submit("/problems/1001/submit", "python", "# VERDICT: COMPILATION_ERROR", &cookie)
+        .await;
+
+    assert_eq!(app.wait_for_verdict(submission_id).await, "COMPILATION_ERROR");
+
+    let (errors,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM compile_errors WHERE submission_id = ?")
+        .bind(submission_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(errors, 1);
+}
+
+#[tokio::test]
+async fn rankings_reflect_accepted_submissions() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("dave").await;
+
+    let submission_id = app
+        .submit("/problems/1001/submit", "java", "class Main {}", &cookie)
+        .await;
+    app.wait_for_verdict(submission_id).await;
+
+    let page = app.get("/rankings", None).await;
+    assert!(page.status.is_success());
+    assert!(page.body.contains("dave"));
+}
+
+#[tokio::test]
+async fn run_mode_does_not_create_submissions() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("erin").await;
+
+    let body = serde_json::json!({
+        "language": "cpp",
+        "source_code": "int main() {}",
+        "input": "1 2\n",
+    });
+    let response = app.post_json("/problems/1001/run", &body, Some(&cookie)).await;
+    assert!(response.status.is_success(), "{}", response.body);
+
+    let result: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(result["status"], "OK");
+    assert_eq!(result["cases"].as_array().unwrap().len(), 1);
+
+    let (submissions,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM submissions")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(submissions, 0);
+
+    let stats: Option<(i64,)> = sqlx::query_as("SELECT total_submissions FROM user_stats WHERE user_id = ?")
+        .bind(app.user_id("erin").await)
+        .fetch_optional(&app.pool)
+        .await
+        .unwrap();
+    assert!(stats.is_none_or(|(total,)| total == 0));
+}
+
+#[tokio::test]
+async fn run_mode_rejects_unknown_language() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("frank").await;
+
+    let body = serde_json::json!({ "language": "cobol", "source_code": "", "input": "" });
+    let response = app.post_json("/problems/1001/run", &body, Some(&cookie)).await;
+    assert_eq!(response.status, axum::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn admin_rejudge_replaces_results_and_stats() {
+    // 처음 채점은 오답, 재채점은 정답이 되도록 스크립트를 둔다
+    let app = TestApp::spawn_with_backend(FakeBackend::with_script(["WRONG_ANSWER tc=2"])).await;
+    let cookie = app.login_new_user("grace").await;
+    let submission_id = app.submit("/problems/1001/submit", "cpp", "int main() {}", &cookie).await;
+    assert_eq!(app.wait_for_verdict(submission_id).await, "WRONG_ANSWER");
+
+    let admin_cookie = app.login_new_user("admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let response = app
+        .post_form("/admin/rejudge", &[("problem_id", "1001"), ("status", "WRONG_ANSWER")], Some(&admin_cookie))
+        .await;
+    assert_eq!(response.location.as_deref(), Some("/admin"));
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let failed: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM testcase_results WHERE submission_id = ? AND status != 'ACCEPTED'",
+    )
+    .bind(submission_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(failed, 0);
+
+    let (total_solved, total_submissions): (i64, i64) =
+        sqlx::query_as("SELECT total_solved, total_submissions FROM user_stats WHERE user_id = ?")
+            .bind(app.user_id("grace").await)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!((total_solved, total_submissions), (1, 1));
+
+    let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM admin_actions WHERE action_type = 'rejudge'")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(logged, 1);
+}
+
+#[tokio::test]
+async fn rejudging_accepted_submissions_keeps_streaks() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("ivan").await;
+    let submission_id = app.submit("/problems/1001/submit", "cpp", "int main() {}", &cookie).await;
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    let admin_cookie = app.login_new_user("admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // 정답 트리거가 재채점마다 다시 실행되어도 연속 일수는 늘지 않는다
+    for _ in 0..2 {
+        let response = app
+            .post_form("/admin/rejudge", &[("problem_id", "1001")], Some(&admin_cookie))
+            .await;
+        assert_eq!(response.location.as_deref(), Some("/admin"));
+        assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+    }
+
+    // 통계는 결과를 저장한 뒤에 다시 계산하므로 잠시 기다린다
+    let user_id = app.user_id("ivan").await;
+    let mut streaks = (0, 0);
+    for _ in 0..40 {
+        streaks = sqlx::query_as("SELECT current_streak, max_streak FROM user_stats WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+        if streaks == (1, 1) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    assert_eq!(streaks, (1i64, 1i64));
+}
+
+#[tokio::test]
+async fn rejudge_requires_a_filter() {
+    let app = TestApp::spawn().await;
+    let admin_cookie = app.login_new_user("admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let response = app.post_form("/admin/rejudge", &[("problem_id", "")], Some(&admin_cookie)).await;
+    assert_eq!(response.status, axum::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn submission_events_end_with_the_final_verdict() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("heidi").await;
+    let submission_id = app.submit("/problems/1001/submit", "cpp", "int main() {}", &cookie).await;
+    assert_eq!(app.wait_for_verdict(submission_id).await, "ACCEPTED");
+
+    // 이미 끝난 제출은 결과 이벤트 하나를 보내고 스트림을 닫는다
+    let events = app.get(&format!("/submissions/{}/events", submission_id), None).await;
+    assert!(events.status.is_success());
+    assert!(events.body.contains(r#""type":"finished""#));
+    assert!(events.body.contains(r#""status":"ACCEPTED""#));
+
+    let missing = app.get("/submissions/999999/events", None).await;
+    assert_eq!(missing.status, axum::http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn oversized_source_is_rejected() {
+    let app = TestApp::spawn().await;
+    let cookie = app.login_new_user("ivan").await;
+
+    let source = "a".repeat(nekonic_oj::judge_pipeline::DEFAULT_MAX_SOURCE_BYTES + 1);
+    let response = app
+        .post_form("/problems/1001/submit", &[("language", "cpp"), ("source_code", &source)], Some(&cookie))
+        .await;
+    assert_eq!(response.status, axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+
+    let (submissions,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM submissions")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(submissions, 0);
+}